@@ -7,6 +7,9 @@ import SyncTab from "./components/SyncTab";
 import SyncDisclaimerDialog from "./components/SyncDisclaimerDialog";
 import AppOutdatedModal from "./components/AppOutdatedModal";
 import FetchErrorModal from "./components/FetchErrorModal";
+import StaleFilesNotice from "./components/StaleFilesNotice";
+import { ContentModule } from "./components/ContentModulesPicker";
+import { ReleaseChannel } from "./components/SyncRefPicker";
 
 interface AppState {
   textures_path: string | null;
@@ -15,12 +18,15 @@ interface AppState {
   last_sync_timestamp: string | null;
   github_token: string | null;
   sync_disclaimer_acknowledged: boolean;
+  temp_path: string | null;
 }
 
 interface InstallerData {
   min_download_app_version: string;
   total_size: string;
   downloader_app_url: string;
+  modules: ContentModule[];
+  channels?: ReleaseChannel[];
 }
 
 interface InstallerDataResult {
@@ -32,6 +38,7 @@ type Tab = "install" | "sync";
 
 function App() {
   const [texturesDir, setTexturesDir] = useState("");
+  const [tempDir, setTempDir] = useState("");
   const [gitAvailable, setGitAvailable] = useState<boolean | null>(null);
   const [gitError, setGitError] = useState("");
   const [activeTab, setActiveTab] = useState<Tab>("install");
@@ -58,6 +65,7 @@ function App() {
         if (state.textures_path) {
           setTexturesDir(state.textures_path);
         }
+        setTempDir(state.temp_path || "");
         setInitialSetupDone(state.initial_setup_done);
         setLastSyncCommit(state.last_sync_commit);
         setLastSyncTimestamp(state.last_sync_timestamp);
@@ -180,6 +188,16 @@ function App() {
     }
   };
 
+  // Save scratch location when it changes (empty uses the textures directory)
+  const handleTempDirChange = async (dir: string) => {
+    setTempDir(dir);
+    try {
+      await invoke("set_temp_path", { path: dir });
+    } catch (e) {
+      console.error("Failed to save temp path:", e);
+    }
+  };
+
   // Handle install complete
   const handleInstallComplete = async (commitSha: string) => {
     try {
@@ -203,6 +221,17 @@ function App() {
     }
   };
 
+  // Reload the restored sync commit after undoing a sync
+  const handleSyncUndone = async () => {
+    try {
+      const state = await invoke<AppState>("load_state");
+      setLastSyncCommit(state.last_sync_commit);
+      setLastSyncTimestamp(state.last_sync_timestamp);
+    } catch (e) {
+      console.error("Failed to reload state:", e);
+    }
+  };
+
   // Handle manual setup toggle
   const handleSetupToggle = async (done: boolean) => {
     try {
@@ -298,6 +327,8 @@ function App() {
       <div className="max-w-xl mx-auto space-y-6 flex-1">
         <Header version={appVersion || undefined} />
 
+        <StaleFilesNotice texturesDir={texturesDir} />
+
         {/* Tabs */}
         <div className="flex gap-1 border-b border-zinc-700">
           <TabButton
@@ -328,8 +359,11 @@ function App() {
               <InstallTab
                 texturesDir={texturesDir}
                 setTexturesDir={handleTexturesDirChange}
+                tempDir={tempDir}
+                setTempDir={handleTempDirChange}
                 gitAvailable={gitAvailable}
                 gitError={gitError}
+                modules={installerData?.modules || []}
                 onInstallComplete={handleInstallComplete}
               />
 
@@ -362,7 +396,10 @@ function App() {
                 lastSyncCommit={lastSyncCommit}
                 lastSyncTimestamp={lastSyncTimestamp}
                 githubToken={githubToken}
+                modules={installerData?.modules || []}
+                channels={installerData?.channels || []}
                 onSyncComplete={handleSyncComplete}
+                onSyncUndone={handleSyncUndone}
                 onTokenChange={handleTokenChange}
               />
             </>
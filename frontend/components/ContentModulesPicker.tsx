@@ -0,0 +1,52 @@
+export interface ContentModule {
+  id: string;
+  name: string;
+  description: string;
+  path: string;
+  size: string;
+  default_enabled: boolean;
+}
+
+interface ContentModulesPickerProps {
+  modules: ContentModule[];
+  selected: string[];
+  onChange: (selected: string[]) => void;
+  disabled?: boolean;
+}
+
+function ContentModulesPicker({ modules, selected, onChange, disabled }: ContentModulesPickerProps) {
+  const toggleModule = (id: string) => {
+    if (selected.includes(id)) {
+      onChange(selected.filter((s) => s !== id));
+    } else {
+      onChange([...selected, id]);
+    }
+  };
+
+  return (
+    <div className="space-y-2">
+      {modules.map((module) => (
+        <label key={module.id} className="flex items-start gap-3 cursor-pointer">
+          <input
+            type="checkbox"
+            checked={selected.includes(module.id)}
+            onChange={() => toggleModule(module.id)}
+            disabled={disabled}
+            className="mt-0.5 w-4 h-4 rounded bg-zinc-700 border-zinc-600 text-blue-500 focus:ring-blue-500 focus:ring-offset-zinc-800"
+          />
+          <div>
+            <span className="text-sm text-zinc-200">{module.name}</span>
+            {module.size && (
+              <span className="text-xs text-zinc-500 ml-2">({module.size} GB)</span>
+            )}
+            {module.description && (
+              <p className="text-xs text-zinc-500">{module.description}</p>
+            )}
+          </div>
+        </label>
+      ))}
+    </div>
+  );
+}
+
+export default ContentModulesPicker;
@@ -4,15 +4,25 @@ interface DirectoryPickerProps {
   value: string;
   onChange: (value: string) => void;
   disabled?: boolean;
+  label?: string;
+  placeholder?: string;
+  hint?: string;
 }
 
-function DirectoryPicker({ value, onChange, disabled }: DirectoryPickerProps) {
+function DirectoryPicker({
+  value,
+  onChange,
+  disabled,
+  label = "PCSX2 Textures Directory",
+  placeholder = "Select your textures directory...",
+  hint = "Example: C:\\PCSX2\\textures or ~/Library/Application Support/PCSX2/textures",
+}: DirectoryPickerProps) {
   const handleBrowse = async () => {
     try {
       const selected = await open({
         directory: true,
         multiple: false,
-        title: "Select PCSX2 Textures Directory",
+        title: `Select ${label}`,
       });
 
       if (selected && typeof selected === "string") {
@@ -26,14 +36,14 @@ function DirectoryPicker({ value, onChange, disabled }: DirectoryPickerProps) {
   return (
     <div>
       <label className="block text-sm font-medium text-zinc-300 mb-2">
-        PCSX2 Textures Directory
+        {label}
       </label>
       <div className="flex gap-2">
         <input
           type="text"
           value={value}
           onChange={(e) => onChange(e.target.value)}
-          placeholder="Select your textures directory..."
+          placeholder={placeholder}
           disabled={disabled}
           className="flex-1 px-3 py-2 bg-zinc-900 border border-zinc-600 rounded-lg
                      text-zinc-100 placeholder-zinc-500 text-sm
@@ -50,9 +60,7 @@ function DirectoryPicker({ value, onChange, disabled }: DirectoryPickerProps) {
           Browse
         </button>
       </div>
-      <p className="mt-1 text-xs text-zinc-500">
-        Example: C:\PCSX2\textures or ~/Library/Application Support/PCSX2/textures
-      </p>
+      <p className="mt-1 text-xs text-zinc-500">{hint}</p>
     </div>
   );
 }
@@ -0,0 +1,130 @@
+import { useState, useEffect } from "react";
+import { invoke } from "@tauri-apps/api/core";
+
+export interface DisabledConflict {
+  serial: string;
+  path: string;
+}
+
+type ConflictPolicy = "prefer_enabled" | "prefer_disabled" | "ask";
+
+const POLICY_LABELS: Record<ConflictPolicy, string> = {
+  prefer_enabled: "keep the enabled file",
+  prefer_disabled: "keep the disabled (-) file",
+  ask: "ask me",
+};
+
+interface DisabledConflictsProps {
+  texturesDir: string;
+  disabled?: boolean;
+  /** Conflicts reported by the last sync or verification */
+  conflicts: DisabledConflict[];
+  onResolved: () => void;
+}
+
+function DisabledConflicts({ texturesDir, disabled, conflicts, onResolved }: DisabledConflictsProps) {
+  const [policy, setPolicy] = useState<ConflictPolicy>("prefer_enabled");
+  const [resolving, setResolving] = useState(false);
+  const [message, setMessage] = useState<string | null>(null);
+
+  useEffect(() => {
+    invoke<{ conflict_policy: ConflictPolicy }>("load_state")
+      .then((state) => setPolicy(state.conflict_policy))
+      .catch(console.error);
+  }, []);
+
+  const handlePolicyChange = async (value: ConflictPolicy) => {
+    try {
+      await invoke("set_conflict_policy", { policy: value });
+      setPolicy(value);
+    } catch (e) {
+      console.error("Failed to save conflict policy:", e);
+    }
+  };
+
+  const handleResolve = async (keepDisabled: boolean) => {
+    setResolving(true);
+    setMessage(null);
+    try {
+      let deleted = 0;
+      for (const serial of new Set(conflicts.map((c) => c.serial))) {
+        deleted += await invoke<number>("resolve_disabled_conflicts", {
+          texturesDir,
+          serial,
+          paths: conflicts.filter((c) => c.serial === serial).map((c) => c.path),
+          keepDisabled,
+        });
+      }
+      setMessage(`Deleted ${deleted} duplicate file(s)`);
+      onResolved();
+    } catch (e) {
+      setMessage(`Failed to resolve conflicts: ${e}`);
+    }
+    setResolving(false);
+  };
+
+  const unresolved = policy === "ask" ? conflicts : [];
+
+  return (
+    <div className="bg-zinc-900 border border-zinc-700 rounded-lg p-4 space-y-3">
+      <div className="flex items-center justify-between">
+        <h3 className="text-sm font-medium text-zinc-300">Enabled &amp; Disabled Copies</h3>
+        <label className="flex items-center gap-2 text-xs text-zinc-500">
+          When both exist
+          <select
+            value={policy}
+            onChange={(e) => handlePolicyChange(e.target.value as ConflictPolicy)}
+            disabled={disabled || resolving}
+            className="px-1 py-0.5 bg-zinc-800 border border-zinc-600 rounded text-zinc-300"
+          >
+            {(Object.keys(POLICY_LABELS) as ConflictPolicy[]).map((value) => (
+              <option key={value} value={value}>
+                {POLICY_LABELS[value]}
+              </option>
+            ))}
+          </select>
+        </label>
+      </div>
+
+      {unresolved.length > 0 ? (
+        <div className="space-y-2">
+          <p className="text-xs text-yellow-400">
+            {unresolved.length} file(s) exist both enabled and disabled (-). They were left alone;
+            choose which copy to keep.
+          </p>
+          <div className="bg-zinc-800 border border-zinc-700 rounded p-2 max-h-40 overflow-y-auto">
+            <ul className="text-xs text-zinc-300 font-mono space-y-0.5">
+              {unresolved.map((c) => (
+                <li key={`${c.serial}/${c.path}`} className="truncate">
+                  {c.serial}/{c.path}
+                </li>
+              ))}
+            </ul>
+          </div>
+          <div className="flex justify-end gap-2">
+            <button
+              onClick={() => handleResolve(false)}
+              disabled={disabled || resolving}
+              className="px-3 py-1 bg-zinc-700 hover:bg-zinc-600 disabled:bg-zinc-800 disabled:text-zinc-600 text-sm rounded transition-colors"
+            >
+              Keep Enabled
+            </button>
+            <button
+              onClick={() => handleResolve(true)}
+              disabled={disabled || resolving}
+              className="px-3 py-1 bg-zinc-700 hover:bg-zinc-600 disabled:bg-zinc-800 disabled:text-zinc-600 text-sm rounded transition-colors"
+            >
+              Keep Disabled
+            </button>
+          </div>
+        </div>
+      ) : (
+        <p className="text-xs text-zinc-500">
+          {message ?? "Syncs and verification keep one copy of files that exist both enabled and disabled."}
+        </p>
+      )}
+    </div>
+  );
+}
+
+export default DisabledConflicts;
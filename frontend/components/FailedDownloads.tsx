@@ -0,0 +1,111 @@
+import { useState, useEffect } from "react";
+import { invoke } from "@tauri-apps/api/core";
+
+export interface FailedFile {
+  folder: string;
+  path: string;
+  to_disabled: boolean;
+  sha: string | null;
+  /** "delete" when the file failed to be deleted */
+  action: "download" | "delete";
+  reason: string;
+}
+
+interface FailedSync {
+  commit_sha: string;
+  failed: FailedFile[];
+  /** Failed verification fixes, which don't change the synced commit */
+  keeps_commit: boolean;
+}
+
+interface FailedDownloadsState {
+  continue_on_errors: boolean;
+  failed_sync: FailedSync | null;
+}
+
+interface FailedDownloadsProps {
+  disabled?: boolean;
+  /** Changes whenever a sync or retry finishes, to reload the failed files */
+  refreshKey: unknown;
+  onRetry: () => void;
+}
+
+function FailedDownloads({ disabled, refreshKey, onRetry }: FailedDownloadsProps) {
+  const [continueOnErrors, setContinueOnErrors] = useState(false);
+  const [failedSync, setFailedSync] = useState<FailedSync | null>(null);
+
+  useEffect(() => {
+    invoke<FailedDownloadsState>("load_state")
+      .then((state) => {
+        setContinueOnErrors(state.continue_on_errors);
+        setFailedSync(state.failed_sync);
+      })
+      .catch(console.error);
+  }, [refreshKey]);
+
+  const handleToggle = async (enabled: boolean) => {
+    try {
+      await invoke("set_continue_on_errors", { enabled });
+      setContinueOnErrors(enabled);
+    } catch (e) {
+      console.error("Failed to save setting:", e);
+    }
+  };
+
+  return (
+    <div className="bg-zinc-900 border border-zinc-700 rounded-lg p-4 space-y-3">
+      <label className="flex items-center gap-2 text-xs text-zinc-400">
+        <input
+          type="checkbox"
+          checked={continueOnErrors}
+          onChange={(e) => handleToggle(e.target.checked)}
+          disabled={disabled}
+        />
+        Keep syncing when a file fails to download, and retry the failed files later
+      </label>
+
+      {failedSync && (
+        <div className="space-y-2">
+          <div className="flex items-center justify-between gap-3">
+            <p className="text-sm text-yellow-300">
+              {failedSync.failed.length} file(s) failed to update.
+              {!failedSync.keeps_commit && (
+                <>
+                  {" "}
+                  The sync to <span className="font-mono">{failedSync.commit_sha.substring(0, 7)}</span> is recorded
+                  once they are retried.
+                </>
+              )}
+            </p>
+            <button
+              onClick={onRetry}
+              disabled={disabled}
+              className="px-3 py-1 bg-zinc-700 hover:bg-zinc-600 disabled:bg-zinc-800 disabled:text-zinc-600 text-sm rounded transition-colors whitespace-nowrap"
+            >
+              Retry Failed
+            </button>
+          </div>
+          <details className="text-xs">
+            <summary className="cursor-pointer text-zinc-400">Show failed files</summary>
+            <ul className="mt-1 space-y-1 max-h-40 overflow-y-auto">
+              {failedSync.failed.map((file) => (
+                <li key={`${file.folder}/${file.path}`}>
+                  <span className="font-mono break-all text-zinc-300">
+                    {file.folder}/{file.path}
+                  </span>
+                  <span className="text-zinc-500">
+                    {" "}
+                    - {file.action === "delete" ? "delete failed: " : ""}
+                    {file.reason}
+                  </span>
+                </li>
+              ))}
+            </ul>
+          </details>
+        </div>
+      )}
+    </div>
+  );
+}
+
+export default FailedDownloads;
@@ -0,0 +1,155 @@
+import { useState, useEffect } from "react";
+import { invoke } from "@tauri-apps/api/core";
+
+interface HeldPath {
+  pattern: string;
+  serial: string | null;
+  held_at: string;
+  commit_sha: string | null;
+}
+
+interface HeldChange {
+  serial: string;
+  path: string;
+  pattern: string;
+  held_sha: string | null;
+  latest_sha: string | null;
+}
+
+interface HeldPathsProps {
+  githubToken: string | null;
+  disabled?: boolean;
+}
+
+function HeldPaths({ githubToken, disabled }: HeldPathsProps) {
+  const [holds, setHolds] = useState<HeldPath[]>([]);
+  const [patternInput, setPatternInput] = useState("");
+  const [changes, setChanges] = useState<HeldChange[] | null>(null);
+  const [checking, setChecking] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    invoke<HeldPath[]>("get_held_paths").then(setHolds).catch(console.error);
+  }, []);
+
+  const handleHold = async () => {
+    setError(null);
+    try {
+      setHolds(await invoke<HeldPath[]>("hold_paths", { patterns: [patternInput], serial: null }));
+      setPatternInput("");
+      setChanges(null);
+    } catch (e) {
+      setError(`${e}`);
+    }
+  };
+
+  const handleRelease = async (hold: HeldPath) => {
+    setError(null);
+    try {
+      setHolds(await invoke<HeldPath[]>("release_held_paths", { patterns: [hold.pattern], serial: hold.serial }));
+      setChanges(null);
+    } catch (e) {
+      setError(`${e}`);
+    }
+  };
+
+  const handleCheckChanges = async () => {
+    setChecking(true);
+    setError(null);
+    try {
+      setChanges(await invoke<HeldChange[]>("get_held_upstream_changes", { githubToken }));
+    } catch (e) {
+      setError(`Failed to check held files: ${e}`);
+    }
+    setChecking(false);
+  };
+
+  return (
+    <details className="bg-zinc-900 border border-zinc-700 rounded-lg p-4">
+      <summary className="text-sm font-medium text-zinc-300 cursor-pointer">
+        Held Textures{holds.length > 0 ? ` (${holds.length})` : ""}
+      </summary>
+
+      <div className="mt-3 space-y-3">
+        <p className="text-xs text-zinc-500">
+          Syncs and verification never change held files, so they stay at the version you have.
+          Use a path inside the serial folder, a folder, or a pattern like
+          <code className="mx-1 px-1 bg-zinc-800 rounded">textures/**/abc*.png</code>.
+        </p>
+
+        <div className="flex gap-2">
+          <input
+            type="text"
+            value={patternInput}
+            onChange={(e) => setPatternInput(e.target.value)}
+            placeholder="Path or pattern to hold"
+            disabled={disabled}
+            className="flex-1 px-2 py-1 bg-zinc-800 border border-zinc-600 rounded text-sm text-zinc-200 placeholder-zinc-500 focus:outline-none focus:border-blue-500"
+          />
+          <button
+            onClick={handleHold}
+            disabled={disabled || patternInput.trim() === ""}
+            className="px-3 py-1 bg-zinc-700 hover:bg-zinc-600 disabled:bg-zinc-800 disabled:text-zinc-600 text-sm rounded transition-colors"
+          >
+            Hold
+          </button>
+        </div>
+
+        {holds.length > 0 && (
+          <ul className="text-xs space-y-1">
+            {holds.map((hold) => (
+              <li key={`${hold.serial ?? ""}:${hold.pattern}`} className="flex items-center justify-between gap-2">
+                <span className="font-mono text-zinc-300 truncate">
+                  {hold.serial ? `${hold.serial}/` : ""}
+                  {hold.pattern}
+                </span>
+                <button
+                  onClick={() => handleRelease(hold)}
+                  disabled={disabled}
+                  className="text-zinc-400 hover:text-zinc-200 disabled:text-zinc-600 transition-colors"
+                >
+                  Release
+                </button>
+              </li>
+            ))}
+          </ul>
+        )}
+
+        {holds.length > 0 && (
+          <div className="space-y-2">
+            <button
+              onClick={handleCheckChanges}
+              disabled={disabled || checking}
+              className="px-3 py-1 bg-zinc-700 hover:bg-zinc-600 disabled:bg-zinc-800 disabled:text-zinc-600 text-sm rounded transition-colors"
+            >
+              {checking ? "Checking..." : "Check for Upstream Changes"}
+            </button>
+            {changes &&
+              (changes.length === 0 ? (
+                <p className="text-xs text-zinc-500">No held file changed upstream since it was held.</p>
+              ) : (
+                <ul className="text-xs space-y-0.5 max-h-40 overflow-y-auto">
+                  {changes.map((change) => (
+                    <li key={`${change.serial}/${change.path}`} className="text-zinc-400">
+                      <span className="font-mono text-zinc-300 break-all">
+                        {change.serial}/{change.path}
+                      </span>{" "}
+                      {change.latest_sha === null
+                        ? "removed upstream"
+                        : change.held_sha === null
+                          ? "added upstream"
+                          : "updated upstream"}
+                    </li>
+                  ))}
+                </ul>
+              ))}
+          </div>
+        )}
+
+        {error && <p className="text-xs text-red-400">{error}</p>}
+      </div>
+    </details>
+  );
+}
+
+export default HeldPaths;
@@ -0,0 +1,136 @@
+export interface PreviewFile {
+  serial: string;
+  path: string;
+  previous_path: string | null;
+  size_bytes: number | null;
+  is_disabled: boolean;
+}
+
+interface IncrementalPreviewDialogProps {
+  filesToAdd: PreviewFile[];
+  filesToReplace: PreviewFile[];
+  filesToRename: PreviewFile[];
+  filesToDelete: PreviewFile[];
+  onConfirm: () => void;
+  onCancel: () => void;
+  onExport?: () => void;
+}
+
+function formatSize(bytes: number | null): string {
+  if (bytes === null) return "";
+  if (bytes >= 1024 ** 2) return `${(bytes / 1024 ** 2).toFixed(1)} MB`;
+  if (bytes >= 1024) return `${(bytes / 1024).toFixed(1)} KB`;
+  return `${bytes} B`;
+}
+
+function FileList({ files, showPrevious }: { files: PreviewFile[]; showPrevious?: boolean }) {
+  return (
+    <div className="bg-zinc-900 border border-zinc-700 rounded p-2 max-h-48 overflow-y-auto">
+      <ul className="text-xs text-zinc-300 font-mono space-y-0.5">
+        {files.map((file) => (
+          <li key={`${file.serial}/${file.path}`} className="flex gap-2">
+            <span className="truncate flex-1">
+              {file.serial}/{showPrevious && file.previous_path ? `${file.previous_path} -> ` : ""}
+              {file.path}
+            </span>
+            {file.is_disabled && <span className="text-zinc-500">disabled</span>}
+            {file.size_bytes !== null && (
+              <span className="text-zinc-500">{formatSize(file.size_bytes)}</span>
+            )}
+          </li>
+        ))}
+      </ul>
+    </div>
+  );
+}
+
+function IncrementalPreviewDialog({
+  filesToAdd,
+  filesToReplace,
+  filesToRename,
+  filesToDelete,
+  onConfirm,
+  onCancel,
+  onExport,
+}: IncrementalPreviewDialogProps) {
+  const downloadBytes = [...filesToAdd, ...filesToReplace].reduce((sum, file) => sum + (file.size_bytes ?? 0), 0);
+
+  return (
+    <div className="fixed inset-0 bg-black/70 flex items-center justify-center z-50 p-4">
+      <div className="bg-zinc-800 border border-zinc-600 rounded-lg max-w-2xl w-full max-h-[80vh] flex flex-col">
+        {/* Header */}
+        <div className="p-4 border-b border-zinc-700">
+          <h2 className="text-lg font-semibold text-zinc-100">Review New Content</h2>
+          <p className="text-sm text-zinc-400">
+            Nothing has been changed yet. Disabled textures stay disabled.
+            {downloadBytes > 0 && ` About ${formatSize(downloadBytes)} will be downloaded.`}
+          </p>
+        </div>
+
+        {/* Content */}
+        <div className="p-4 overflow-y-auto flex-1 space-y-4">
+          {filesToAdd.length > 0 && (
+            <div className="space-y-2">
+              <span className="text-green-400 font-medium">{filesToAdd.length} file(s) will be ADDED</span>
+              <FileList files={filesToAdd} />
+            </div>
+          )}
+
+          {filesToReplace.length > 0 && (
+            <div className="space-y-2">
+              <span className="text-orange-400 font-medium">
+                {filesToReplace.length} file(s) will be REPLACED
+              </span>
+              <p className="text-sm text-zinc-400">
+                If any of these are custom textures, copy them to the
+                <code className="mx-1 px-1 bg-zinc-700 rounded">user-customs</code>
+                folder before proceeding.
+              </p>
+              <FileList files={filesToReplace} />
+            </div>
+          )}
+
+          {filesToRename.length > 0 && (
+            <div className="space-y-2">
+              <span className="text-blue-400 font-medium">{filesToRename.length} file(s) will be MOVED</span>
+              <FileList files={filesToRename} showPrevious />
+            </div>
+          )}
+
+          {filesToDelete.length > 0 && (
+            <div className="space-y-2">
+              <span className="text-red-400 font-medium">{filesToDelete.length} file(s) will be DELETED</span>
+              <FileList files={filesToDelete} />
+            </div>
+          )}
+        </div>
+
+        {/* Footer */}
+        <div className="p-4 border-t border-zinc-700 flex justify-end gap-3">
+          {onExport && (
+            <button
+              onClick={onExport}
+              className="mr-auto px-4 py-2 text-sm text-zinc-400 hover:text-zinc-200 transition-colors"
+            >
+              Export plan...
+            </button>
+          )}
+          <button
+            onClick={onCancel}
+            className="px-4 py-2 text-sm text-zinc-400 hover:text-zinc-200 transition-colors"
+          >
+            Cancel
+          </button>
+          <button
+            onClick={onConfirm}
+            className="px-4 py-2 bg-blue-600 hover:bg-blue-500 text-white text-sm font-medium rounded transition-colors"
+          >
+            Apply Changes
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+}
+
+export default IncrementalPreviewDialog;
@@ -5,6 +5,8 @@ import DirectoryPicker from "./DirectoryPicker";
 import InstallButton from "./InstallButton";
 import ProgressDisplay from "./ProgressDisplay";
 import ExistingFolderDialog from "./ExistingFolderDialog";
+import ContentModulesPicker, { ContentModule } from "./ContentModulesPicker";
+import SerialFoldersSettings from "./SerialFoldersSettings";
 import { TARGET_FOLDER } from "../config";
 
 interface ProgressPayload {
@@ -13,21 +15,38 @@ interface ProgressPayload {
   percent: number | null;
 }
 
+// Structured error returned by start_installation
+interface InstallError {
+  code: string;
+  message: string;
+  hint: string;
+  details: string;
+}
+
 type InstallStatus = "idle" | "installing" | "complete" | "error";
 
+// Clone implementation picked by the user ("" lets the backend choose git or HTTP)
+type InstallBackend = "" | "git" | "http" | "native";
+
 interface InstallTabProps {
   texturesDir: string;
   setTexturesDir: (dir: string) => void;
+  tempDir: string;
+  setTempDir: (dir: string) => void;
   gitAvailable: boolean | null;
   gitError: string;
+  modules: ContentModule[];
   onInstallComplete: (commitSha: string) => void;
 }
 
 function InstallTab({
   texturesDir,
   setTexturesDir,
+  tempDir,
+  setTempDir,
   gitAvailable,
   gitError,
+  modules,
   onInstallComplete,
 }: InstallTabProps) {
   const [showFolderDialog, setShowFolderDialog] = useState(false);
@@ -36,6 +55,33 @@ function InstallTab({
   const [progressPercent, setProgressPercent] = useState<number | null>(null);
   const [currentStage, setCurrentStage] = useState<string | null>(null);
   const [errorMessage, setErrorMessage] = useState<string | null>(null);
+  const [installError, setInstallError] = useState<InstallError | null>(null);
+  const [selectedModules, setSelectedModules] = useState<string[]>([]);
+  const [backend, setBackend] = useState<InstallBackend>("");
+  const [nativeGitAvailable, setNativeGitAvailable] = useState(false);
+
+  useEffect(() => {
+    invoke<boolean>("check_native_git_available")
+      .then(setNativeGitAvailable)
+      .catch(console.error);
+  }, []);
+
+  // Load the optional module selection
+  useEffect(() => {
+    if (modules.length === 0) return;
+    invoke<string[]>("get_selected_modules")
+      .then(setSelectedModules)
+      .catch(console.error);
+  }, [modules]);
+
+  const handleModulesChange = async (ids: string[]) => {
+    setSelectedModules(ids);
+    try {
+      await invoke("set_selected_modules", { moduleIds: ids });
+    } catch (e) {
+      console.error("Failed to save module selection:", e);
+    }
+  };
 
   // Listen for progress events
   useEffect(() => {
@@ -50,17 +96,13 @@ function InstallTab({
 
       if (stage === "complete") {
         setInstallStatus("complete");
-        // Get the latest commit SHA and notify parent
-        invoke<string>("get_latest_commit")
-          .then((sha) => onInstallComplete(sha))
-          .catch(console.error);
       }
     });
 
     return () => {
       unlisten.then((fn) => fn());
     };
-  }, [onInstallComplete]);
+  }, []);
 
   const handleStartInstall = async () => {
     if (!texturesDir) {
@@ -90,12 +132,19 @@ function InstallTab({
     setProgressPercent(0);
     setCurrentStage(null);
     setErrorMessage(null);
+    setInstallError(null);
 
     try {
-      await invoke("start_installation", { texturesDir });
+      // The commit that was actually installed, not whatever main is by now
+      const commitSha = await invoke<string>("start_installation", { texturesDir, backend: backend || null });
+      onInstallComplete(commitSha);
     } catch (e) {
       setInstallStatus("error");
-      setErrorMessage(`Installation failed: ${e}`);
+      if (typeof e === "object" && e !== null && "code" in e) {
+        setInstallError(e as InstallError);
+      } else {
+        setErrorMessage(`Installation failed: ${e}`);
+      }
     }
   };
 
@@ -132,11 +181,14 @@ function InstallTab({
   return (
     <>
       <div className="space-y-4">
-        {/* Git availability warning */}
+        {/* Git availability notice - installation falls back to a direct download */}
         {gitAvailable === false && (
-          <div className="bg-red-900/50 border border-red-700 rounded-lg p-4">
-            <p className="text-red-200 font-medium">Git not available</p>
-            <p className="text-red-300 text-sm mt-1">{gitError}</p>
+          <div className="bg-yellow-900/30 border border-yellow-700 rounded-lg p-4">
+            <p className="text-yellow-200 font-medium">Git not available</p>
+            <p className="text-yellow-300 text-sm mt-1">{gitError}</p>
+            <p className="text-yellow-300 text-sm mt-1">
+              The installer will download the textures directly instead. This works, but is slower.
+            </p>
           </div>
         )}
 
@@ -146,9 +198,66 @@ function InstallTab({
           disabled={isInstalling}
         />
 
+        {/* Optional content modules */}
+        {modules.length > 0 && (
+          <div className="bg-zinc-900 border border-zinc-700 rounded-lg p-4 space-y-3">
+            <h3 className="text-sm font-medium text-zinc-300">Optional Content</h3>
+            <ContentModulesPicker
+              modules={modules}
+              selected={selectedModules}
+              onChange={handleModulesChange}
+              disabled={isInstalling}
+            />
+          </div>
+        )}
+
+        {/* Scratch location for the download before it is moved into place */}
+        <details className="text-sm text-zinc-400">
+          <summary className="cursor-pointer">Advanced: temporary download location</summary>
+          <div className="mt-3">
+            <DirectoryPicker
+              value={tempDir}
+              onChange={setTempDir}
+              disabled={isInstalling}
+              label="Temporary Download Directory"
+              placeholder="Same as textures directory"
+              hint="Needs room for the full pack. Leave empty to use the textures directory."
+            />
+          </div>
+        </details>
+
+        <details className="text-sm text-zinc-400">
+          <summary className="cursor-pointer">Advanced: download method</summary>
+          <div className="mt-3 space-y-2">
+            <select
+              value={backend}
+              onChange={(e) => setBackend(e.target.value as InstallBackend)}
+              disabled={isInstalling}
+              className="px-2 py-1 bg-zinc-800 border border-zinc-600 rounded text-zinc-300"
+            >
+              <option value="">Automatic (git, or direct download without git)</option>
+              {gitAvailable && <option value="git">Git sparse checkout</option>}
+              <option value="http">Direct download (no git)</option>
+              {nativeGitAvailable && <option value="native">Built-in git</option>}
+            </select>
+            {backend === "native" && (
+              <p className="text-xs text-zinc-500">
+                Downloads only the texture pack without installing git, like a git sparse checkout.
+              </p>
+            )}
+          </div>
+        </details>
+
+        <details className="text-sm text-zinc-400">
+          <summary className="cursor-pointer">Advanced: additional game serials</summary>
+          <div className="mt-3">
+            <SerialFoldersSettings disabled={isInstalling} />
+          </div>
+        </details>
+
         <InstallButton
           onClick={handleStartInstall}
-          disabled={!texturesDir || isInstalling || gitAvailable === false}
+          disabled={!texturesDir || isInstalling}
           isInstalling={isInstalling}
         />
 
@@ -159,6 +268,24 @@ function InstallTab({
           </div>
         )}
 
+        {/* Classified installation error */}
+        {installError && (
+          <div className="p-3 bg-red-900/30 border border-red-800 rounded text-red-300 text-sm space-y-2">
+            <p className="font-medium">Installation failed: {installError.message}</p>
+            <p>{installError.hint}</p>
+            {installError.details && (
+              <details>
+                <summary className="cursor-pointer text-red-400">
+                  Details ({installError.code})
+                </summary>
+                <pre className="mt-2 whitespace-pre-wrap text-xs text-red-300/80">
+                  {installError.details}
+                </pre>
+              </details>
+            )}
+          </div>
+        )}
+
         {/* Progress display */}
         {(isInstalling || installStatus === "complete") && (
           <ProgressDisplay
@@ -0,0 +1,51 @@
+interface MassChangeDialogProps {
+  message: string;
+  onConfirm: () => void;
+  onCancel: () => void;
+}
+
+function MassChangeDialog({ message, onConfirm, onCancel }: MassChangeDialogProps) {
+  return (
+    <div className="fixed inset-0 bg-black/70 flex items-center justify-center z-50 p-4">
+      <div className="bg-zinc-800 border border-red-700 rounded-lg max-w-lg w-full">
+        {/* Header */}
+        <div className="p-4 border-b border-zinc-700">
+          <div className="flex items-center gap-2 text-red-400">
+            <svg className="h-6 w-6 flex-shrink-0" fill="none" viewBox="0 0 24 24" stroke="currentColor">
+              <path strokeLinecap="round" strokeLinejoin="round" strokeWidth={2} d="M12 9v2m0 4h.01m-6.938 4h13.856c1.54 0 2.502-1.667 1.732-3L13.732 4c-.77-1.333-2.694-1.333-3.464 0L3.34 16c-.77 1.333.192 3 1.732 3z" />
+            </svg>
+            <h2 className="text-lg font-semibold">Sync Stopped: Unusually Large Change</h2>
+          </div>
+        </div>
+
+        {/* Content */}
+        <div className="p-4 space-y-3">
+          <p className="text-sm text-zinc-300">{message}</p>
+          <p className="text-sm text-zinc-400">
+            This can happen when the texture pack is reorganized, but also when something went
+            wrong upstream. Nothing has been changed yet. If unsure, cancel and check the mod's
+            announcements first.
+          </p>
+        </div>
+
+        {/* Footer */}
+        <div className="p-4 border-t border-zinc-700 flex justify-end gap-3">
+          <button
+            onClick={onCancel}
+            className="px-4 py-2 text-sm text-zinc-400 hover:text-zinc-200 transition-colors"
+          >
+            Cancel
+          </button>
+          <button
+            onClick={onConfirm}
+            className="px-4 py-2 bg-red-700 hover:bg-red-600 text-white text-sm font-medium rounded transition-colors"
+          >
+            Continue Anyway
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+}
+
+export default MassChangeDialog;
@@ -0,0 +1,86 @@
+import { useState, useEffect } from "react";
+import { invoke } from "@tauri-apps/api/core";
+
+export interface SyncPreview {
+  preview_ref: string;
+  commit_sha: string;
+  undo_id: string;
+  previous_commit: string | null;
+  previous_timestamp: string | null;
+  started_at: string;
+}
+
+interface PreviewPanelProps {
+  disabled?: boolean;
+  /** Changes whenever a sync, preview or undo finishes, to reload the preview */
+  refreshKey: unknown;
+  onStart: (previewRef: string) => void;
+  onEnd: (preview: SyncPreview) => void;
+}
+
+function PreviewPanel({ disabled, refreshKey, onStart, onEnd }: PreviewPanelProps) {
+  const [preview, setPreview] = useState<SyncPreview | null>(null);
+  const [refInput, setRefInput] = useState("");
+
+  useEffect(() => {
+    invoke<{ preview: SyncPreview | null }>("load_state")
+      .then((state) => setPreview(state.preview))
+      .catch(console.error);
+  }, [refreshKey]);
+
+  if (preview) {
+    return (
+      <div className="bg-purple-900/30 border border-purple-700 rounded-lg p-4 space-y-3">
+        <div className="flex items-center justify-between gap-3">
+          <p className="text-sm text-purple-200">
+            Previewing <span className="font-mono">{preview.preview_ref}</span>{" "}
+            <span className="text-xs text-purple-300 font-mono">({preview.commit_sha.substring(0, 7)})</span>
+            <span className="text-xs text-purple-300"> since {new Date(preview.started_at).toLocaleString()}</span>
+          </p>
+          <button
+            onClick={() => onEnd(preview)}
+            disabled={disabled}
+            className="px-3 py-1 bg-purple-700 hover:bg-purple-600 disabled:bg-zinc-800 disabled:text-zinc-600 text-sm rounded transition-colors whitespace-nowrap"
+          >
+            End Preview
+          </button>
+        </div>
+        <p className="text-xs text-purple-300">
+          Syncs are paused. Ending the preview puts back every file it changed, added or removed.
+        </p>
+      </div>
+    );
+  }
+
+  return (
+    <details className="bg-zinc-900 border border-zinc-700 rounded-lg p-4">
+      <summary className="text-sm font-medium text-zinc-300 cursor-pointer">Preview a Branch or Pull Request</summary>
+
+      <div className="mt-3 space-y-3">
+        <p className="text-xs text-zinc-500">
+          Try textures from a branch, tag or pull request (e.g. <code className="px-1 bg-zinc-800 rounded">#123</code>)
+          in-game, then end the preview to go back to exactly what you had.
+        </p>
+        <div className="flex gap-2">
+          <input
+            type="text"
+            value={refInput}
+            onChange={(e) => setRefInput(e.target.value)}
+            placeholder="Branch, tag or #pull request"
+            disabled={disabled}
+            className="flex-1 px-2 py-1 bg-zinc-800 border border-zinc-600 rounded text-sm text-zinc-200 placeholder-zinc-500 focus:outline-none focus:border-blue-500"
+          />
+          <button
+            onClick={() => onStart(refInput.trim())}
+            disabled={disabled || refInput.trim() === ""}
+            className="px-3 py-1 bg-zinc-700 hover:bg-zinc-600 disabled:bg-zinc-800 disabled:text-zinc-600 text-sm rounded transition-colors"
+          >
+            Start Preview
+          </button>
+        </div>
+      </div>
+    </details>
+  );
+}
+
+export default PreviewPanel;
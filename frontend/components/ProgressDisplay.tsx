@@ -19,7 +19,7 @@ const STAGE_LABELS: Record<string, string> = {
 };
 
 // Stages that show indeterminate progress (no percentage available)
-const INDETERMINATE_STAGES = ["preparing", "moving", "cleanup"];
+const INDETERMINATE_STAGES = ["preparing", "cleanup"];
 
 function ProgressDisplay({ messages, percent, stage, isComplete }: ProgressDisplayProps) {
   const messagesEndRef = useRef<HTMLDivElement>(null);
@@ -0,0 +1,145 @@
+import { useState, useEffect } from "react";
+import { invoke } from "@tauri-apps/api/core";
+
+type SerialSource =
+  | { type: "mirror"; link: "hardlink" | "copy" }
+  | { type: "subpath"; path: string };
+
+interface SerialFolder {
+  serial: string;
+  source: SerialSource;
+}
+
+interface SerialFoldersSettingsProps {
+  disabled?: boolean;
+}
+
+function SerialFoldersSettings({ disabled }: SerialFoldersSettingsProps) {
+  const [folders, setFolders] = useState<SerialFolder[]>([]);
+  const [isSaving, setIsSaving] = useState(false);
+  const [message, setMessage] = useState<string | null>(null);
+
+  useEffect(() => {
+    invoke<{ serial_folders: SerialFolder[] }>("load_state")
+      .then((state) => setFolders(state.serial_folders ?? []))
+      .catch(() => setFolders([]));
+  }, []);
+
+  const updateFolder = (index: number, folder: SerialFolder) => {
+    setFolders((prev) => prev.map((f, i) => (i === index ? folder : f)));
+    setMessage(null);
+  };
+
+  const addFolder = () => {
+    setFolders((prev) => [...prev, { serial: "", source: { type: "mirror", link: "copy" } }]);
+    setMessage(null);
+  };
+
+  const removeFolder = (index: number) => {
+    setFolders((prev) => prev.filter((_, i) => i !== index));
+    setMessage(null);
+  };
+
+  const handleSave = async () => {
+    setIsSaving(true);
+    try {
+      await invoke("set_serial_folders", { folders });
+      setMessage("Saved. Folders are filled in on the next install or sync.");
+    } catch (e) {
+      setMessage(`${e}`);
+    }
+    setIsSaving(false);
+  };
+
+  const inputClass =
+    "px-2 py-1 bg-zinc-900 border border-zinc-700 rounded text-sm text-zinc-200 " +
+    "focus:outline-none focus:border-blue-500 disabled:opacity-50";
+
+  return (
+    <div className="space-y-3">
+      <p className="text-xs text-zinc-500">
+        Other releases of the game (PAL, reprints) look for textures in a folder named after
+        their serial. A mirror gets the same textures as SLUS-21770; a repository path gets its
+        own pack.
+      </p>
+      <p className="text-xs text-zinc-500">
+        Mirrors are read-only copies: changes made in them are undone on the next sync. A folder
+        that isn't a mirror yet is backed up first. Hardlinked files are shared with SLUS-21770,
+        so editing one edits both.
+      </p>
+
+      {folders.map((folder, index) => (
+        <div key={index} className="flex flex-wrap items-center gap-2">
+          <input
+            type="text"
+            value={folder.serial}
+            onChange={(e) => updateFolder(index, { ...folder, serial: e.target.value.trim() })}
+            placeholder="SLES-55123"
+            disabled={disabled || isSaving}
+            className={`${inputClass} w-32 font-mono`}
+          />
+          <select
+            value={folder.source.type === "mirror" ? folder.source.link : "subpath"}
+            onChange={(e) => {
+              const value = e.target.value;
+              updateFolder(index, {
+                ...folder,
+                source:
+                  value === "subpath"
+                    ? { type: "subpath", path: "" }
+                    : { type: "mirror", link: value as "hardlink" | "copy" },
+              });
+            }}
+            disabled={disabled || isSaving}
+            className={inputClass}
+          >
+            <option value="copy">Mirror (copies)</option>
+            <option value="hardlink">Mirror (hardlinks, shared edits)</option>
+            <option value="subpath">Own pack</option>
+          </select>
+          {folder.source.type === "subpath" && (
+            <input
+              type="text"
+              value={folder.source.path}
+              onChange={(e) =>
+                updateFolder(index, { ...folder, source: { type: "subpath", path: e.target.value.trim() } })
+              }
+              placeholder="textures/SLES-55123"
+              disabled={disabled || isSaving}
+              className={`${inputClass} flex-1 min-w-40 font-mono`}
+            />
+          )}
+          <button
+            onClick={() => removeFolder(index)}
+            disabled={disabled || isSaving}
+            className="text-xs text-zinc-400 hover:text-zinc-200 disabled:opacity-50"
+          >
+            Remove
+          </button>
+        </div>
+      ))}
+
+      <div className="flex items-center gap-3">
+        <button
+          onClick={addFolder}
+          disabled={disabled || isSaving}
+          className="px-3 py-1 bg-zinc-700 hover:bg-zinc-600 text-zinc-100 rounded text-sm
+                     disabled:opacity-50 disabled:cursor-not-allowed"
+        >
+          Add serial
+        </button>
+        <button
+          onClick={handleSave}
+          disabled={disabled || isSaving}
+          className="px-3 py-1 bg-zinc-700 hover:bg-zinc-600 text-zinc-100 rounded text-sm
+                     disabled:opacity-50 disabled:cursor-not-allowed"
+        >
+          {isSaving ? "Saving..." : "Save"}
+        </button>
+        {message && <span className="text-xs text-zinc-400">{message}</span>}
+      </div>
+    </div>
+  );
+}
+
+export default SerialFoldersSettings;
@@ -0,0 +1,153 @@
+import { useState, useEffect } from "react";
+import { invoke } from "@tauri-apps/api/core";
+
+interface StaleItem {
+  path: string;
+  kind: "temp_clone" | "staging_folder" | "backup" | "partial_file";
+  size_bytes: number;
+}
+
+interface StaleScanResult {
+  items: StaleItem[];
+  total_bytes: number;
+}
+
+interface StaleFilesNoticeProps {
+  texturesDir: string;
+}
+
+const KIND_LABELS: Record<StaleItem["kind"], string> = {
+  temp_clone: "Interrupted installation",
+  staging_folder: "Interrupted move",
+  backup: "Backup folder",
+  partial_file: "Partial download",
+};
+
+function formatSize(bytes: number): string {
+  if (bytes >= 1024 ** 3) return `${(bytes / 1024 ** 3).toFixed(1)} GB`;
+  if (bytes >= 1024 ** 2) return `${(bytes / 1024 ** 2).toFixed(1)} MB`;
+  if (bytes >= 1024) return `${(bytes / 1024).toFixed(1)} KB`;
+  return `${bytes} B`;
+}
+
+function StaleFilesNotice({ texturesDir }: StaleFilesNoticeProps) {
+  const [items, setItems] = useState<StaleItem[]>([]);
+  const [selected, setSelected] = useState<Set<string>>(new Set());
+  const [isReclaiming, setIsReclaiming] = useState(false);
+  const [message, setMessage] = useState<string | null>(null);
+  const [recovered, setRecovered] = useState<string[]>([]);
+
+  // Sweep for leftovers on startup and whenever the textures directory changes
+  useEffect(() => {
+    if (!texturesDir) {
+      setItems([]);
+      return;
+    }
+
+    // Finish or roll back interrupted syncs before looking for leftovers
+    invoke<string[]>("recover_interrupted_syncs")
+      .then(setRecovered)
+      .catch((e) => setRecovered([`Failed to recover interrupted syncs: ${e}`]))
+      .then(() => invoke<StaleScanResult>("scan_stale_files", { texturesDir }))
+      .then((result) => {
+        setItems(result.items);
+        // Backups are kept unless the user opts in
+        setSelected(
+          new Set(result.items.filter((i) => i.kind !== "backup").map((i) => i.path))
+        );
+      })
+      .catch(() => setItems([]));
+  }, [texturesDir]);
+
+  const toggleItem = (path: string) => {
+    setSelected((prev) => {
+      const next = new Set(prev);
+      if (next.has(path)) {
+        next.delete(path);
+      } else {
+        next.add(path);
+      }
+      return next;
+    });
+  };
+
+  const handleReclaim = async () => {
+    setIsReclaiming(true);
+    try {
+      const reclaimed = await invoke<number>("reclaim_stale_files", {
+        texturesDir,
+        paths: Array.from(selected),
+      });
+      setItems((prev) => prev.filter((i) => !selected.has(i.path)));
+      setSelected(new Set());
+      setMessage(`Freed ${formatSize(reclaimed)}`);
+    } catch (e) {
+      setMessage(`Cleanup failed: ${e}`);
+    }
+    setIsReclaiming(false);
+  };
+
+  const recoveredNotice = recovered.length > 0 && (
+    <div className="bg-blue-900/30 border border-blue-700 rounded-lg p-4 space-y-1">
+      {recovered.map((line) => (
+        <p key={line} className="text-blue-200 text-sm">{line}</p>
+      ))}
+    </div>
+  );
+
+  if (items.length === 0) {
+    if (recoveredNotice) return recoveredNotice;
+    return message ? <p className="text-xs text-zinc-500">{message}</p> : null;
+  }
+
+  const selectedBytes = items
+    .filter((i) => selected.has(i.path))
+    .reduce((sum, i) => sum + i.size_bytes, 0);
+
+  return (
+    <>
+      {recoveredNotice}
+      <div className="bg-yellow-900/30 border border-yellow-700 rounded-lg p-4 space-y-3">
+        <p className="text-yellow-200 font-medium">Leftover files found</p>
+        <p className="text-yellow-300 text-sm">
+          These were left behind by interrupted installs, syncs or earlier reinstalls.
+        </p>
+
+        <ul className="space-y-1">
+          {items.map((item) => (
+            <li key={item.path}>
+              <label className="flex items-start gap-2 text-sm text-zinc-300 cursor-pointer">
+                <input
+                  type="checkbox"
+                  checked={selected.has(item.path)}
+                  onChange={() => toggleItem(item.path)}
+                  disabled={isReclaiming}
+                  className="mt-0.5 w-4 h-4 rounded bg-zinc-700 border-zinc-600 text-blue-500"
+                />
+                <span className="flex-1 min-w-0">
+                  <span className="text-zinc-200">{KIND_LABELS[item.kind]}</span>{" "}
+                  <span className="text-zinc-500">({formatSize(item.size_bytes)})</span>
+                  <span className="block text-xs text-zinc-500 font-mono break-all">
+                    {item.path}
+                  </span>
+                </span>
+              </label>
+            </li>
+          ))}
+        </ul>
+
+        <button
+          onClick={handleReclaim}
+          disabled={selected.size === 0 || isReclaiming}
+          className="px-4 py-2 bg-zinc-700 hover:bg-zinc-600 text-zinc-100 rounded-lg
+                     transition-colors text-sm font-medium
+                     disabled:opacity-50 disabled:cursor-not-allowed disabled:hover:bg-zinc-700"
+        >
+          {isReclaiming ? "Cleaning up..." : `Delete selected (${formatSize(selectedBytes)})`}
+        </button>
+      </div>
+    </>
+  );
+}
+
+export default StaleFilesNotice;
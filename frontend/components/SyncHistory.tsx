@@ -0,0 +1,132 @@
+import { useState, useEffect } from "react";
+import { invoke } from "@tauri-apps/api/core";
+
+interface MassChangeTrip {
+  folder: string;
+  affected: number;
+  rescued: number;
+  tracked: number;
+  limit_percent: number;
+  confirmed: boolean;
+}
+
+interface SyncHistoryEntry {
+  timestamp: string;
+  kind: string;
+  outcome: "completed" | "blocked";
+  commit_sha: string | null;
+  files_downloaded: number;
+  files_deleted: number;
+  files_renamed: number;
+  mass_changes: MassChangeTrip[];
+}
+
+interface SyncHistoryProps {
+  disabled?: boolean;
+  /** Changes whenever a sync finishes, to reload the history */
+  refreshKey: unknown;
+}
+
+const SHOWN_ENTRIES = 20;
+
+function SyncHistory({ disabled, refreshKey }: SyncHistoryProps) {
+  const [entries, setEntries] = useState<SyncHistoryEntry[]>([]);
+  const [limitInput, setLimitInput] = useState("");
+  const [savedLimit, setSavedLimit] = useState("");
+  const [message, setMessage] = useState<string | null>(null);
+
+  useEffect(() => {
+    invoke<{ mass_change_limit_percent: number | null }>("load_state")
+      .then((state) => {
+        const limit = state.mass_change_limit_percent?.toString() ?? "";
+        setLimitInput(limit);
+        setSavedLimit(limit);
+      })
+      .catch(console.error);
+  }, []);
+
+  useEffect(() => {
+    invoke<SyncHistoryEntry[]>("get_sync_history")
+      .then((history) => setEntries(history.slice(-SHOWN_ENTRIES).reverse()))
+      .catch(() => setEntries([]));
+  }, [refreshKey]);
+
+  const handleSaveLimit = async () => {
+    try {
+      const percent = limitInput.trim() === "" ? null : Number(limitInput);
+      await invoke("set_mass_change_limit", { percent });
+      setSavedLimit(limitInput.trim());
+      setMessage("Saved");
+    } catch (e) {
+      setMessage(`${e}`);
+    }
+  };
+
+  return (
+    <details className="bg-zinc-900 border border-zinc-700 rounded-lg p-4">
+      <summary className="text-sm font-medium text-zinc-300 cursor-pointer">Sync History &amp; Safety</summary>
+
+      <div className="mt-3 space-y-3">
+        <div className="space-y-1">
+          <div className="flex items-center gap-2 text-sm text-zinc-400">
+            <span>Ask before a sync deletes or replaces more than</span>
+            <input
+              type="number"
+              min={0}
+              max={100}
+              value={limitInput}
+              onChange={(e) => {
+                setLimitInput(e.target.value);
+                setMessage(null);
+              }}
+              placeholder="default"
+              disabled={disabled}
+              className="w-20 px-2 py-1 bg-zinc-800 border border-zinc-600 rounded text-sm text-zinc-200 placeholder-zinc-500 focus:outline-none focus:border-blue-500"
+            />
+            <span>% of a folder</span>
+            <button
+              onClick={handleSaveLimit}
+              disabled={disabled || limitInput.trim() === savedLimit}
+              className="px-3 py-1 bg-zinc-700 hover:bg-zinc-600 disabled:bg-zinc-800 disabled:text-zinc-600 text-sm rounded transition-colors"
+            >
+              Save
+            </button>
+          </div>
+          <p className="text-xs text-zinc-500">
+            Leave empty to use the limit recommended by the mod.
+            {message && <span className="ml-2 text-zinc-400">{message}</span>}
+          </p>
+        </div>
+
+        {entries.length === 0 ? (
+          <p className="text-xs text-zinc-500">No syncs recorded yet.</p>
+        ) : (
+          <ul className="text-xs space-y-1 max-h-60 overflow-y-auto">
+            {entries.map((entry) => (
+              <li key={`${entry.timestamp}-${entry.kind}`} className="text-zinc-400">
+                <span className="text-zinc-300">{new Date(entry.timestamp).toLocaleString()}</span>{" "}
+                {entry.kind}{" "}
+                {entry.outcome === "blocked" ? (
+                  <span className="text-red-400">stopped by safety limit</span>
+                ) : (
+                  <span>
+                    {entry.files_downloaded} downloaded, {entry.files_deleted} deleted, {entry.files_renamed} moved
+                  </span>
+                )}
+                {entry.mass_changes.map((trip) => (
+                  <span key={trip.folder} className="block ml-4 text-yellow-400">
+                    {trip.folder}: {trip.affected} of {trip.tracked} files (limit {trip.limit_percent}%)
+                    {trip.rescued > 0 ? `, ${trip.rescued} to rescue` : ""}
+                    {trip.confirmed ? ", confirmed by user" : ""}
+                  </span>
+                ))}
+              </li>
+            ))}
+          </ul>
+        )}
+      </div>
+    </details>
+  );
+}
+
+export default SyncHistory;
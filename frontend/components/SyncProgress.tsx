@@ -15,6 +15,11 @@ interface SyncProgressProps {
     files_deleted: number;
     files_renamed: number;
     files_skipped: number;
+    skipped: { path: string; reason: string }[];
+    rescued: string[];
+    conflicts: { serial: string; path: string }[];
+    held: string[];
+    failed?: { folder: string; path: string; reason: string }[];
   } | null;
 }
 
@@ -154,13 +159,44 @@ function SyncProgress({ messages, isComplete, result }: SyncProgressProps) {
       {/* Completion summary */}
       {isComplete && result && (
         <div className="bg-green-900/30 border border-green-700 rounded-lg p-3 text-green-300 text-sm">
-          <p className="font-medium mb-2">Sync completed successfully!</p>
+          <p className="font-medium mb-2">
+            {result.failed?.length ? "Sync completed with failed downloads" : "Sync completed successfully!"}
+          </p>
           <ul className="text-xs space-y-1">
             <li>Files downloaded: {result.files_downloaded}</li>
             <li>Files deleted: {result.files_deleted}</li>
             {result.files_renamed > 0 && <li>Files renamed/moved: {result.files_renamed}</li>}
             {result.files_skipped > 0 && <li>Files skipped: {result.files_skipped}</li>}
+            {result.rescued.length > 0 && <li>Your files moved to user-customs/rescued: {result.rescued.length}</li>}
+            {result.conflicts.length > 0 && <li>Files with both an enabled and a disabled copy: {result.conflicts.length}</li>}
+            {result.held.length > 0 && <li>Held files left alone: {result.held.length}</li>}
+            {!!result.failed?.length && (
+              <li className="text-yellow-300">Failed to download: {result.failed.length} (retry them below)</li>
+            )}
           </ul>
+          {result.rescued.length > 0 && (
+            <details className="mt-2 text-xs">
+              <summary className="cursor-pointer">Show rescued files</summary>
+              <ul className="mt-1 space-y-1 max-h-40 overflow-y-auto">
+                {result.rescued.map((path) => (
+                  <li key={path} className="font-mono break-all">{path}</li>
+                ))}
+              </ul>
+            </details>
+          )}
+          {result.skipped.length > 0 && (
+            <details className="mt-2 text-xs">
+              <summary className="cursor-pointer">Show skipped changes</summary>
+              <ul className="mt-1 space-y-1 max-h-40 overflow-y-auto">
+                {result.skipped.map((s) => (
+                  <li key={s.path}>
+                    <span className="font-mono break-all">{s.path}</span>
+                    <span className="text-green-400/70"> - {s.reason}</span>
+                  </li>
+                ))}
+              </ul>
+            </details>
+          )}
         </div>
       )}
     </div>
@@ -0,0 +1,135 @@
+import { useState, useEffect } from "react";
+import { invoke } from "@tauri-apps/api/core";
+
+export interface ReleaseChannel {
+  id: string;
+  name: string;
+  description: string;
+  ref: string;
+  min_download_app_version: string;
+}
+
+interface SyncRefState {
+  sync_ref: string | null;
+  release_channel: string | null;
+}
+
+interface SyncRefPickerProps {
+  /** Branch, tag or commit SHA syncs currently install */
+  currentRef: string;
+  /** Release channels from installer-data.json */
+  channels: ReleaseChannel[];
+  githubToken: string | null;
+  disabled?: boolean;
+  /** Called after the sync target changed, to check the status again */
+  onChanged: () => void;
+}
+
+function SyncRefPicker({ currentRef, channels, githubToken, disabled, onChanged }: SyncRefPickerProps) {
+  const [pinnedRef, setPinnedRef] = useState<string | null>(null);
+  const [channel, setChannel] = useState<string | null>(null);
+  const [refInput, setRefInput] = useState("");
+  const [saving, setSaving] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const loadSettings = () => {
+    invoke<SyncRefState>("load_state")
+      .then((state) => {
+        setPinnedRef(state.sync_ref);
+        setChannel(state.release_channel);
+      })
+      .catch(console.error);
+  };
+
+  useEffect(loadSettings, []);
+
+  const apply = async (command: string, args: Record<string, unknown>) => {
+    setSaving(true);
+    setError(null);
+    try {
+      await invoke<string>(command, { ...args, githubToken });
+      setRefInput("");
+      loadSettings();
+      onChanged();
+    } catch (e) {
+      setError(`${e}`);
+    }
+    setSaving(false);
+  };
+
+  return (
+    <details className="bg-zinc-900 border border-zinc-700 rounded-lg p-4" open={pinnedRef !== null}>
+      <summary className="text-sm font-medium text-zinc-300 cursor-pointer">
+        Sync Target: <span className="font-mono">{currentRef}</span>
+      </summary>
+
+      <div className="mt-3 space-y-3">
+        {channels.length > 0 && (
+          <div className="space-y-1">
+            <label className="flex items-center justify-between gap-2 text-xs text-zinc-400">
+              Release channel
+              <select
+                value={channel ?? ""}
+                onChange={(e) => apply("set_release_channel", { channel: e.target.value || null })}
+                disabled={disabled || saving}
+                className="px-1 py-0.5 bg-zinc-800 border border-zinc-600 rounded text-zinc-300"
+              >
+                <option value="">Latest (main)</option>
+                {channels.map((c) => (
+                  <option key={c.id} value={c.id}>
+                    {c.name}
+                    {c.min_download_app_version ? ` (app ${c.min_download_app_version}+)` : ""}
+                  </option>
+                ))}
+              </select>
+            </label>
+            {channel && (
+              <p className="text-xs text-zinc-500">{channels.find((c) => c.id === channel)?.description}</p>
+            )}
+          </div>
+        )}
+
+        <p className="text-xs text-zinc-500">
+          Enter a tag, branch or commit SHA to pin syncs to it, e.g. to roll the whole pack back after a broken update.
+        </p>
+
+        <div className="flex gap-2">
+          <input
+            type="text"
+            value={refInput}
+            onChange={(e) => setRefInput(e.target.value)}
+            placeholder="Tag, branch or commit SHA"
+            disabled={disabled || saving}
+            className="flex-1 px-2 py-1 bg-zinc-800 border border-zinc-600 rounded text-sm text-zinc-200 placeholder-zinc-500 focus:outline-none focus:border-blue-500"
+          />
+          <button
+            onClick={() => apply("set_sync_ref", { syncRef: refInput })}
+            disabled={disabled || saving || refInput.trim() === ""}
+            className="px-3 py-1 bg-zinc-700 hover:bg-zinc-600 disabled:bg-zinc-800 disabled:text-zinc-600 text-sm rounded transition-colors"
+          >
+            Pin
+          </button>
+          {pinnedRef !== null && (
+            <button
+              onClick={() => apply("set_sync_ref", { syncRef: null })}
+              disabled={disabled || saving}
+              className="px-3 py-1 bg-zinc-700 hover:bg-zinc-600 disabled:bg-zinc-800 disabled:text-zinc-600 text-sm rounded transition-colors"
+            >
+              Unpin
+            </button>
+          )}
+        </div>
+
+        {pinnedRef !== null && (
+          <p className="text-xs text-yellow-400">
+            Syncs stay on {pinnedRef} until you unpin it. Run a sync to apply the change.
+          </p>
+        )}
+
+        {error && <p className="text-xs text-red-400">{error}</p>}
+      </div>
+    </details>
+  );
+}
+
+export default SyncRefPicker;
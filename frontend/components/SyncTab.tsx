@@ -1,14 +1,34 @@
 import { useState, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
+import { open, save } from "@tauri-apps/plugin-dialog";
 import SyncProgress from "./SyncProgress";
 import SyncWarningDialog from "./SyncWarningDialog";
+import IncrementalPreviewDialog, { PreviewFile } from "./IncrementalPreviewDialog";
+import ContentModulesPicker, { ContentModule } from "./ContentModulesPicker";
+import UndoLastSync, { SyncUndoSummary } from "./UndoLastSync";
+import MassChangeDialog from "./MassChangeDialog";
+import SyncHistory from "./SyncHistory";
+import DisabledConflicts, { DisabledConflict } from "./DisabledConflicts";
+import HeldPaths from "./HeldPaths";
+import TextureHistory from "./TextureHistory";
+import SyncRefPicker, { ReleaseChannel } from "./SyncRefPicker";
+import PreviewPanel, { SyncPreview } from "./PreviewPanel";
+import FailedDownloads, { FailedFile } from "./FailedDownloads";
 
 interface SyncStatusResult {
   latest_commit_sha: string;
   latest_commit_date: string;
   last_sync_commit: string | null;
   has_changes: boolean;
+  sync_ref: string;
+}
+
+interface SkippedFile {
+  path: string;
+  reason: string;
+  /** Set when the path was refused as unsafe (e.g. "PARENT_TRAVERSAL") */
+  violation?: string | null;
 }
 
 interface SyncResult {
@@ -17,6 +37,11 @@ interface SyncResult {
   files_renamed: number;
   files_skipped: number;
   new_commit_sha: string;
+  skipped: SkippedFile[];
+  rescued: string[];
+  conflicts: DisabledConflict[];
+  held: string[];
+  failed: FailedFile[];
 }
 
 interface SyncProgressPayload {
@@ -37,11 +62,62 @@ interface SyncFile {
   to_disabled: boolean;
 }
 
+interface SyncMove {
+  from: string;
+  to: string;
+  to_disabled: boolean;
+}
+
+interface TargetAnalysis {
+  serial: string;
+  files_to_add: SyncFile[];
+  files_to_replace: SyncFile[];
+  files_to_delete: string[];
+  files_to_move: SyncMove[];
+  files_to_rescue: SyncMove[];
+  conflicts: string[];
+  held: string[];
+}
+
+interface SyncPlan {
+  version: number;
+  commit_sha: string;
+  created_at: string;
+  targets: unknown[];
+}
+
 interface SyncAnalysis {
   files_to_add: SyncFile[];
   files_to_replace: SyncFile[];
   files_to_delete: string[];
+  files_to_move: SyncMove[];
+  files_to_rescue: SyncMove[];
+  conflicts: string[];
+  held: string[];
+  skipped: SkippedFile[];
+  commit_sha: string;
+  serial_targets: TargetAnalysis[];
+  plan: SyncPlan;
+}
+
+interface IncrementalAnalysis {
+  files_to_add: PreviewFile[];
+  files_to_replace: PreviewFile[];
+  files_to_rename: PreviewFile[];
+  files_to_delete: PreviewFile[];
+  skipped: SkippedFile[];
+  conflicts: DisabledConflict[];
+  held: string[];
   commit_sha: string;
+  plan: SyncPlan;
+}
+
+interface ModuleSelectionResult {
+  files_downloaded: number;
+  files_deleted: number;
+  rescued: string[];
+  held: string[];
+  failed: FailedFile[];
 }
 
 type SyncStatus = "idle" | "checking" | "syncing" | "complete" | "error";
@@ -52,7 +128,10 @@ interface SyncTabProps {
   lastSyncCommit: string | null;
   lastSyncTimestamp: string | null;
   githubToken: string | null;
+  modules: ContentModule[];
+  channels: ReleaseChannel[];
   onSyncComplete: (commitSha: string) => void;
+  onSyncUndone: () => void;
   onTokenChange: (token: string) => void;
 }
 
@@ -78,7 +157,10 @@ function SyncTab({
   lastSyncCommit,
   lastSyncTimestamp,
   githubToken,
+  modules,
+  channels,
   onSyncComplete,
+  onSyncUndone,
   onTokenChange,
 }: SyncTabProps) {
   const [syncStatus, setSyncStatus] = useState<SyncStatus>("idle");
@@ -94,7 +176,16 @@ function SyncTab({
   const [showTokenRequired, setShowTokenRequired] = useState(false);
   const [quickCheckResult, setQuickCheckResult] = useState<QuickCheckResult | null>(null);
   const [pendingAnalysis, setPendingAnalysis] = useState<SyncAnalysis | null>(null);
+  const [pendingPreview, setPendingPreview] = useState<IncrementalAnalysis | null>(null);
   const [showWarningDialog, setShowWarningDialog] = useState(false);
+  const [appliedModules, setAppliedModules] = useState<string[]>([]);
+  const [pendingModules, setPendingModules] = useState<string[]>([]);
+  const [moduleResult, setModuleResult] = useState<ModuleSelectionResult | null>(null);
+  const [historyKey, setHistoryKey] = useState(0);
+  const [pendingMassChange, setPendingMassChange] = useState<{
+    message: string;
+    retry: () => Promise<void>;
+  } | null>(null);
 
   // Listen for sync progress events
   useEffect(() => {
@@ -118,6 +209,17 @@ function SyncTab({
     }
   }, [texturesDir, githubToken]);
 
+  // Load the optional module selection
+  useEffect(() => {
+    if (modules.length === 0) return;
+    invoke<string[]>("get_selected_modules")
+      .then((ids) => {
+        setAppliedModules(ids);
+        setPendingModules(ids);
+      })
+      .catch(console.error);
+  }, [modules]);
+
   // Update token input when prop changes
   useEffect(() => {
     setTokenInput(githubToken || "");
@@ -165,62 +267,155 @@ function SyncTab({
     setShowOutput(true);
 
     try {
-      if (syncMode === "full") {
-        // For full sync: analyze first, then warn if needed
-        const analysis = await invoke<SyncAnalysis>("analyze_full_sync", {
-          texturesDir,
-          githubToken,
-        });
-
-        // Check if there are files that will be replaced or deleted
-        if (analysis.files_to_replace.length > 0 || analysis.files_to_delete.length > 0) {
-          // Show warning dialog and wait for confirmation
-          setPendingAnalysis(analysis);
-          setShowWarningDialog(true);
-          setSyncStatus("idle"); // Pause until user confirms
-          return;
-        }
+      if (syncMode === "full" || !lastSyncCommit) {
+        await runFullAnalysis();
+        return;
+      }
 
-        // No warnings needed, proceed directly
-        await executeAnalyzedSync(analysis);
-      } else {
-        // Incremental sync - run directly
-        const result = await invoke<SyncResult>("run_sync", {
+      // Incremental sync: preview the changes first
+      let preview: IncrementalAnalysis;
+      try {
+        preview = await invoke<IncrementalAnalysis>("analyze_incremental_sync", {
           texturesDir,
           lastSyncCommit,
           githubToken,
-          fullSync: false,
         });
+      } catch (e) {
+        const message = String(e);
+        // Previous commit gone, too many changes or a new serial folder: fall back to Full Sync
+        if (["TRUNCATED", "FULL_SYNC_REQUIRED", "404", "Not Found"].some((s) => message.includes(s))) {
+          setProgressMessages((prev) => [
+            ...prev,
+            { stage: "fetching", message: "Incremental sync not possible, running Full Sync analysis...", current: null, total: null },
+          ]);
+          await runFullAnalysis();
+          return;
+        }
+        throw e;
+      }
 
-        await finishSync(result);
+      // Changes to existing files are shown for review; pure additions proceed directly
+      if (
+        preview.files_to_replace.length > 0 ||
+        preview.files_to_rename.length > 0 ||
+        preview.files_to_delete.length > 0
+      ) {
+        setPendingPreview(preview);
+        setSyncStatus("idle"); // Pause until user confirms
+        return;
       }
+
+      await executePreview(preview);
     } catch (e) {
       setErrorMessage(`Sync failed: ${e}`);
       setSyncStatus("error");
     }
   };
 
-  const executeAnalyzedSync = async (analysis: SyncAnalysis) => {
+  const runFullAnalysis = async () => {
+    // For full sync: analyze first, then warn if needed
+    const analysis = await invoke<SyncAnalysis>("analyze_full_sync", {
+      texturesDir,
+      githubToken,
+    });
+
+    // Check if there are files that will be replaced or deleted (in any serial folder)
+    const hasChanges = [analysis, ...analysis.serial_targets].some(
+      (t) => t.files_to_replace.length > 0 || t.files_to_delete.length > 0 || t.files_to_rescue.length > 0
+    );
+    if (hasChanges) {
+      // Show warning dialog and wait for confirmation
+      setPendingAnalysis(analysis);
+      setShowWarningDialog(true);
+      setSyncStatus("idle"); // Pause until user confirms
+      return;
+    }
+
+    // No warnings needed, proceed directly
+    await executePlan(analysis.plan, analysis.skipped);
+  };
+
+  // Stopped by the mass-change limit: ask before running it again with the limit lifted
+  const handleSyncError = (e: unknown, label: string, retry: () => Promise<void>) => {
+    const message = String(e);
+    const marker = message.indexOf("MASS_CHANGE:");
+    if (marker >= 0) {
+      setPendingMassChange({ message: message.substring(marker + "MASS_CHANGE:".length).trim(), retry });
+      setHistoryKey((k) => k + 1);
+      setSyncStatus("idle"); // Pause until user confirms
+      return;
+    }
+    const unsafeMarker = message.indexOf("UNSAFE_PATH:");
+    if (unsafeMarker >= 0) {
+      setErrorMessage(
+        `${label}: a file list contained a path outside the textures folder or in a protected folder.\n` +
+          message.substring(unsafeMarker + "UNSAFE_PATH:".length).trim()
+      );
+      setSyncStatus("error");
+      return;
+    }
+    setErrorMessage(`${label}: ${e}`);
+    setSyncStatus("error");
+  };
+
+  const handleMassChangeConfirm = async () => {
+    if (pendingMassChange) {
+      const { retry } = pendingMassChange;
+      setPendingMassChange(null);
+      await retry();
+    }
+  };
+
+  const handleMassChangeCancel = () => {
+    setPendingMassChange(null);
+    setSyncStatus("idle");
+    setProgressMessages((prev) => [
+      ...prev,
+      { stage: "cancelled", message: "Sync cancelled by user.", current: null, total: null },
+    ]);
+  };
+
+  const executePreview = async (preview: IncrementalAnalysis, allowMassChanges = false) => {
     setSyncStatus("syncing");
     setShowOutput(true);
 
     try {
-      const result = await invoke<SyncResult>("execute_analyzed_sync", {
+      const result = await invoke<SyncResult>("execute_sync_plan", {
         texturesDir,
-        filesToAdd: analysis.files_to_add,
-        filesToReplace: analysis.files_to_replace,
-        filesToDelete: analysis.files_to_delete,
-        commitSha: analysis.commit_sha,
+        plan: preview.plan,
         githubToken,
+        allowMassChanges,
       });
 
-      await finishSync(result);
+      await finishSync({
+        ...result,
+        skipped: preview.skipped,
+        files_skipped: preview.skipped.length,
+        conflicts: preview.conflicts,
+        held: preview.held,
+      });
     } catch (e) {
-      setErrorMessage(`Sync failed: ${e}`);
-      setSyncStatus("error");
+      handleSyncError(e, "Sync failed", () => executePreview(preview, true));
     }
   };
 
+  const handlePreviewConfirm = async () => {
+    if (pendingPreview) {
+      const preview = pendingPreview;
+      setPendingPreview(null);
+      await executePreview(preview);
+    }
+  };
+
+  const handlePreviewCancel = () => {
+    setPendingPreview(null);
+    setSyncStatus("idle");
+    setProgressMessages((prev) => [
+      ...prev,
+      { stage: "cancelled", message: "Sync cancelled by user.", current: null, total: null },
+    ]);
+  };
+
   const finishSync = async (result: SyncResult) => {
     // Run quick count check (fast, no SHA computation)
     try {
@@ -234,19 +429,164 @@ function SyncTab({
     }
 
     setSyncResult(result);
-    onSyncComplete(result.new_commit_sha);
+    // The commit is only recorded once every file made it, failed files are retried first
+    if (result.failed.length === 0) {
+      onSyncComplete(result.new_commit_sha);
+    }
     setSyncStatus("complete");
-    await checkSyncStatus(result.new_commit_sha);
+    setHistoryKey((k) => k + 1);
+    await checkSyncStatus(result.failed.length === 0 ? result.new_commit_sha : undefined);
+  };
+
+  const handleRetryFailed = async (allowMassChanges = false) => {
+    setSyncStatus("syncing");
+    setProgressMessages([]);
+    setSyncResult(null);
+    setQuickCheckResult(null);
+    setErrorMessage(null);
+    setShowOutput(true);
+
+    try {
+      const result = await invoke<SyncResult>("retry_failed_files", { texturesDir, githubToken, allowMassChanges });
+      await finishSync(result);
+    } catch (e) {
+      handleSyncError(e, "Retry failed", () => handleRetryFailed(true));
+      setHistoryKey((k) => k + 1);
+    }
+  };
+
+  const handleUndoLastSync = async () => {
+    setSyncStatus("syncing");
+    setProgressMessages([]);
+    setSyncResult(null);
+    setQuickCheckResult(null);
+    setErrorMessage(null);
+    setShowOutput(true);
+
+    try {
+      const summary = await invoke<SyncUndoSummary>("undo_last_sync", { texturesDir });
+      onSyncUndone();
+      setSyncStatus("complete");
+      await checkSyncStatus(summary.previous_commit || undefined);
+    } catch (e) {
+      setErrorMessage(`Undo failed: ${e}`);
+      setSyncStatus("error");
+    }
+    setHistoryKey((k) => k + 1);
+  };
+
+  const handleStartPreview = async (previewRef: string, allowMassChanges = false) => {
+    setSyncStatus("syncing");
+    setProgressMessages([]);
+    setSyncResult(null);
+    setQuickCheckResult(null);
+    setErrorMessage(null);
+    setShowOutput(true);
+
+    try {
+      const result = await invoke<SyncResult>("start_preview", {
+        texturesDir,
+        previewRef,
+        githubToken,
+        allowMassChanges,
+      });
+      setSyncResult(result);
+      // The preview commit is saved by the backend, reload it
+      onSyncUndone();
+      setSyncStatus("complete");
+      await checkSyncStatus(result.new_commit_sha);
+    } catch (e) {
+      handleSyncError(e, "Preview failed", () => handleStartPreview(previewRef, true));
+    }
+    setHistoryKey((k) => k + 1);
+  };
+
+  const handleEndPreview = async (preview: SyncPreview) => {
+    setSyncStatus("syncing");
+    setProgressMessages([]);
+    setSyncResult(null);
+    setQuickCheckResult(null);
+    setErrorMessage(null);
+    setShowOutput(true);
+
+    try {
+      await invoke<SyncUndoSummary | null>("end_preview", { texturesDir });
+      onSyncUndone();
+      setSyncStatus("complete");
+      await checkSyncStatus(preview.previous_commit || undefined);
+    } catch (e) {
+      setErrorMessage(`Ending the preview failed: ${e}`);
+      setSyncStatus("error");
+    }
+    setHistoryKey((k) => k + 1);
   };
 
   const handleWarningConfirm = async () => {
     setShowWarningDialog(false);
     if (pendingAnalysis) {
-      await executeAnalyzedSync(pendingAnalysis);
+      await executePlan(pendingAnalysis.plan, pendingAnalysis.skipped);
       setPendingAnalysis(null);
     }
   };
 
+  const handleExportPlan = async (plan: SyncPlan) => {
+    try {
+      const path = await save({
+        title: "Export Sync Plan",
+        defaultPath: `sync-plan-${plan.commit_sha.substring(0, 7)}.json`,
+        filters: [{ name: "Sync plan", extensions: ["json"] }],
+      });
+      if (path) {
+        await invoke("export_sync_plan", { plan, path });
+      }
+    } catch (e) {
+      setErrorMessage(`Failed to export plan: ${e}`);
+    }
+  };
+
+  const handleRunPlan = async () => {
+    setErrorMessage(null);
+    try {
+      const path = await open({
+        title: "Run Exported Sync Plan",
+        multiple: false,
+        filters: [{ name: "Sync plan", extensions: ["json"] }],
+      });
+      if (!path || typeof path !== "string") return;
+
+      const plan = await invoke<SyncPlan>("import_sync_plan", { path });
+      setProgressMessages([]);
+      setSyncResult(null);
+      setQuickCheckResult(null);
+      await executePlan(plan);
+    } catch (e) {
+      setErrorMessage(`Sync plan failed: ${e}`);
+      setSyncStatus("error");
+    }
+  };
+
+  // `skipped` are repository files the analysis already left out, shown with the result
+  const executePlan = async (plan: SyncPlan, skipped: SkippedFile[] = [], allowMassChanges = false) => {
+    setSyncStatus("syncing");
+    setShowOutput(true);
+
+    try {
+      const result = await invoke<SyncResult>("execute_sync_plan", {
+        texturesDir,
+        plan,
+        githubToken,
+        allowMassChanges,
+      });
+      await finishSync({
+        ...result,
+        skipped: [...skipped, ...result.skipped],
+        files_skipped: result.files_skipped + skipped.length,
+      });
+    } catch (e) {
+      handleSyncError(e, "Sync plan failed", () => executePlan(plan, skipped, true));
+    }
+  };
+
   const handleWarningCancel = () => {
     setShowWarningDialog(false);
     setPendingAnalysis(null);
@@ -257,6 +597,40 @@ function SyncTab({
     ]);
   };
 
+  const handleApplyModules = async (allowMassChanges = false) => {
+    if (!githubToken) {
+      setShowTokenRequired(true);
+      setTokenSectionExpanded(true);
+      return;
+    }
+
+    setSyncStatus("syncing");
+    setProgressMessages([]);
+    setSyncResult(null);
+    setModuleResult(null);
+    setErrorMessage(null);
+    setShowOutput(true);
+
+    try {
+      const result = await invoke<ModuleSelectionResult>("apply_module_selection", {
+        texturesDir,
+        moduleIds: pendingModules,
+        githubToken,
+        allowMassChanges,
+      });
+      setAppliedModules(pendingModules);
+      setModuleResult(result);
+      setHistoryKey((k) => k + 1);
+      setSyncStatus("complete");
+    } catch (e) {
+      handleSyncError(e, "Updating modules failed", () => handleApplyModules(true));
+    }
+  };
+
+  const modulesChanged =
+    pendingModules.length !== appliedModules.length ||
+    pendingModules.some((id) => !appliedModules.includes(id));
+
   const handleSaveToken = () => {
     onTokenChange(tokenInput);
     if (tokenInput) {
@@ -393,6 +767,15 @@ function SyncTab({
         )}
       </div>
 
+      {/* Branch, tag or commit syncs install */}
+      <SyncRefPicker
+        currentRef={statusResult?.sync_ref ?? "main"}
+        channels={channels}
+        githubToken={githubToken}
+        disabled={isSyncing || isChecking}
+        onChanged={() => checkSyncStatus()}
+      />
+
       {/* Sync Mode */}
       <div className="bg-zinc-900 border border-zinc-700 rounded-lg p-4 space-y-3">
         <h3 className="text-sm font-medium text-zinc-300">Sync Mode</h3>
@@ -429,8 +812,47 @@ function SyncTab({
             </div>
           </label>
         </div>
+        {syncMode === "full" && (
+          <button
+            onClick={handleRunPlan}
+            disabled={!texturesDir || isSyncing || isChecking}
+            className="text-xs text-zinc-400 hover:text-zinc-200 underline disabled:opacity-50"
+          >
+            Run an exported sync plan...
+          </button>
+        )}
       </div>
 
+      {/* Optional content modules */}
+      {modules.length > 0 && (
+        <div className="bg-zinc-900 border border-zinc-700 rounded-lg p-4 space-y-3">
+          <h3 className="text-sm font-medium text-zinc-300">Optional Content</h3>
+          <ContentModulesPicker
+            modules={modules}
+            selected={pendingModules}
+            onChange={setPendingModules}
+            disabled={isSyncing}
+          />
+          {modulesChanged && (
+            <button
+              onClick={() => handleApplyModules()}
+              disabled={!texturesDir || isSyncing || isChecking}
+              className="px-3 py-2 bg-zinc-700 hover:bg-zinc-600 disabled:bg-zinc-800 disabled:text-zinc-600 text-sm rounded transition-colors"
+            >
+              Apply module changes
+            </button>
+          )}
+          {moduleResult && (
+            <p className="text-xs text-zinc-500">
+              Modules updated: {moduleResult.files_downloaded} downloaded, {moduleResult.files_deleted} deleted
+              {moduleResult.rescued.length > 0 && `, ${moduleResult.rescued.length} edited file(s) moved to user-customs/rescued`}
+              {moduleResult.held.length > 0 && `, ${moduleResult.held.length} held file(s) left alone`}
+              {moduleResult.failed.length > 0 && `, ${moduleResult.failed.length} failed (see below to retry)`}
+            </p>
+          )}
+        </div>
+      )}
+
       {/* Sync button */}
       <button
         onClick={handleRunSync}
@@ -446,6 +868,50 @@ function SyncTab({
         {isSyncing ? "Syncing..." : syncMode === "full" ? "Run Full Sync" : "Run Sync"}
       </button>
 
+      {/* Temporary preview of a branch or pull request */}
+      <PreviewPanel
+        disabled={isSyncing || isChecking}
+        refreshKey={historyKey}
+        onStart={(previewRef) => handleStartPreview(previewRef)}
+        onEnd={handleEndPreview}
+      />
+
+      {/* Downloads that failed and can be retried */}
+      <FailedDownloads disabled={isSyncing || isChecking} refreshKey={historyKey} onRetry={() => handleRetryFailed()} />
+
+      {/* Undo last sync */}
+      <UndoLastSync
+        texturesDir={texturesDir}
+        disabled={isSyncing || isChecking}
+        refreshKey={historyKey}
+        onUndo={handleUndoLastSync}
+      />
+
+      {/* Policy for files that exist both enabled and disabled */}
+      <DisabledConflicts
+        texturesDir={texturesDir}
+        disabled={isSyncing || isChecking}
+        conflicts={syncResult?.conflicts ?? []}
+        onResolved={() => {
+          setSyncResult((r) => r && { ...r, conflicts: [] });
+          setHistoryKey((k) => k + 1);
+        }}
+      />
+
+      {/* Textures syncs leave alone */}
+      <HeldPaths key={historyKey} githubToken={githubToken} disabled={isSyncing || isChecking} />
+
+      {/* Earlier versions of a single texture */}
+      <TextureHistory
+        texturesDir={texturesDir}
+        githubToken={githubToken}
+        disabled={isSyncing || isChecking}
+        onRestored={() => setHistoryKey((k) => k + 1)}
+      />
+
+      {/* Sync history and mass-change limit */}
+      <SyncHistory disabled={isSyncing || isChecking} refreshKey={historyKey} />
+
       {/* Token required warning */}
       {showTokenRequired && (
         <div className="p-3 bg-yellow-900/30 border border-yellow-700 rounded text-yellow-300 text-sm">
@@ -464,7 +930,7 @@ function SyncTab({
 
       {/* Error message */}
       {errorMessage && (
-        <div className="p-3 bg-red-900/30 border border-red-800 rounded text-red-300 text-sm">
+        <div className="p-3 bg-red-900/30 border border-red-800 rounded text-red-300 text-sm whitespace-pre-line">
           {errorMessage}
         </div>
       )}
@@ -473,7 +939,7 @@ function SyncTab({
       {showOutput && progressMessages.length > 0 && (
         <SyncProgress
           messages={progressMessages}
-          isComplete={!isSyncing && syncResult !== null}
+          isComplete={!isSyncing && (syncResult !== null || syncStatus === "complete")}
           result={syncResult}
         />
       )}
@@ -517,16 +983,57 @@ function SyncTab({
           <li>Disabled textures (dash-prefixed) stay disabled but get updated</li>
           <li>Deleted textures are removed (including disabled versions)</li>
           <li>Renamed textures are moved (preserving disabled state)</li>
+          <li>Optional content you haven't selected is left alone</li>
+          <li>Additional serial folders (PAL, reprints) are synced too</li>
         </ul>
       </div>
 
       {/* Warning dialog for files that will be replaced/deleted */}
       {showWarningDialog && pendingAnalysis && (
         <SyncWarningDialog
-          filesToReplace={pendingAnalysis.files_to_replace}
-          filesToDelete={pendingAnalysis.files_to_delete}
+          filesToReplace={[
+            ...pendingAnalysis.files_to_replace,
+            ...pendingAnalysis.serial_targets.flatMap((t) =>
+              t.files_to_replace.map((f) => ({ ...f, path: `${t.serial}/${f.path}` }))
+            ),
+          ]}
+          filesToDelete={[
+            ...pendingAnalysis.files_to_delete,
+            ...pendingAnalysis.serial_targets.flatMap((t) =>
+              t.files_to_delete.map((path) => `${t.serial}/${path}`)
+            ),
+          ]}
+          filesToRescue={[
+            ...pendingAnalysis.files_to_rescue.map((r) => r.from),
+            ...pendingAnalysis.serial_targets.flatMap((t) =>
+              t.files_to_rescue.map((r) => `${t.serial}/${r.from}`)
+            ),
+          ]}
           onConfirm={handleWarningConfirm}
           onCancel={handleWarningCancel}
+          onExport={() => handleExportPlan(pendingAnalysis.plan)}
+        />
+      )}
+
+      {/* Mass-change limit confirmation */}
+      {pendingMassChange && (
+        <MassChangeDialog
+          message={pendingMassChange.message}
+          onConfirm={handleMassChangeConfirm}
+          onCancel={handleMassChangeCancel}
+        />
+      )}
+
+      {/* Preview of "Download New Content" changes */}
+      {pendingPreview && (
+        <IncrementalPreviewDialog
+          filesToAdd={pendingPreview.files_to_add}
+          filesToReplace={pendingPreview.files_to_replace}
+          filesToRename={pendingPreview.files_to_rename}
+          filesToDelete={pendingPreview.files_to_delete}
+          onConfirm={handlePreviewConfirm}
+          onCancel={handlePreviewCancel}
+          onExport={() => handleExportPlan(pendingPreview.plan)}
         />
       )}
     </div>
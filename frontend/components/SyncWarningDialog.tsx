@@ -6,18 +6,24 @@ interface SyncFile {
 interface SyncWarningDialogProps {
   filesToReplace: SyncFile[];
   filesToDelete: string[];
+  /** User-made or edited files that are moved to user-customs/rescued first */
+  filesToRescue?: string[];
   onConfirm: () => void;
   onCancel: () => void;
+  onExport?: () => void;
 }
 
 function SyncWarningDialog({
   filesToReplace,
   filesToDelete,
+  filesToRescue = [],
   onConfirm,
   onCancel,
+  onExport,
 }: SyncWarningDialogProps) {
   const hasReplacements = filesToReplace.length > 0;
   const hasDeletions = filesToDelete.length > 0;
+  const hasRescues = filesToRescue.length > 0;
 
   return (
     <div className="fixed inset-0 bg-black/70 flex items-center justify-center z-50 p-4">
@@ -34,6 +40,29 @@ function SyncWarningDialog({
 
         {/* Content */}
         <div className="p-4 overflow-y-auto flex-1 space-y-4">
+          {hasRescues && (
+            <div>
+              <div className="flex items-center gap-2 mb-2">
+                <span className="text-blue-400 font-medium">
+                  {filesToRescue.length} file(s) will be MOVED to user-customs/rescued
+                </span>
+              </div>
+              <p className="text-sm text-zinc-400 mb-2">
+                These files don't match any version of the mod's files, so they were probably
+                made or edited by you. They will be moved to
+                <code className="mx-1 px-1 bg-zinc-700 rounded">user-customs/rescued/&lt;date&gt;</code>
+                with their folder structure kept, instead of being replaced or deleted.
+              </p>
+              <div className="bg-zinc-900 border border-zinc-700 rounded p-2 max-h-60 overflow-y-auto">
+                <ul className="text-xs text-zinc-300 font-mono space-y-0.5">
+                  {filesToRescue.map((path) => (
+                    <li key={path} className="truncate">{path}</li>
+                  ))}
+                </ul>
+              </div>
+            </div>
+          )}
+
           {hasReplacements && (
             <div>
               <div className="flex items-center gap-2 mb-2">
@@ -85,6 +114,14 @@ function SyncWarningDialog({
 
         {/* Footer */}
         <div className="p-4 border-t border-zinc-700 flex justify-end gap-3">
+          {onExport && (
+            <button
+              onClick={onExport}
+              className="mr-auto px-4 py-2 text-sm text-zinc-400 hover:text-zinc-200 transition-colors"
+            >
+              Export plan...
+            </button>
+          )}
           <button
             onClick={onCancel}
             className="px-4 py-2 text-sm text-zinc-400 hover:text-zinc-200 transition-colors"
@@ -0,0 +1,149 @@
+import { useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+
+interface TextureVersion {
+  commit_sha: string;
+  date: string;
+  message: string;
+  author: string;
+}
+
+interface RestoredTexture {
+  path: string;
+  sha: string;
+  held: boolean;
+}
+
+interface TextureHistoryProps {
+  texturesDir: string;
+  githubToken: string | null;
+  disabled?: boolean;
+  /** Called after a texture was restored, to reload undo data */
+  onRestored: () => void;
+}
+
+function TextureHistory({ texturesDir, githubToken, disabled, onRestored }: TextureHistoryProps) {
+  const [pathInput, setPathInput] = useState("");
+  const [serialInput, setSerialInput] = useState("");
+  const [versions, setVersions] = useState<TextureVersion[] | null>(null);
+  const [holdAfterRestore, setHoldAfterRestore] = useState(true);
+  const [loading, setLoading] = useState(false);
+  const [message, setMessage] = useState<string | null>(null);
+
+  const serial = serialInput.trim() || null;
+
+  const handleShowHistory = async () => {
+    setLoading(true);
+    setMessage(null);
+    setVersions(null);
+    try {
+      setVersions(
+        await invoke<TextureVersion[]>("get_texture_history", {
+          path: pathInput,
+          serial,
+          githubToken,
+        })
+      );
+    } catch (e) {
+      setMessage(`Failed to load history: ${e}`);
+    }
+    setLoading(false);
+  };
+
+  const handleRestore = async (version: TextureVersion) => {
+    setLoading(true);
+    setMessage(null);
+    try {
+      const restored = await invoke<RestoredTexture>("restore_texture_version", {
+        texturesDir,
+        path: pathInput,
+        commitSha: version.commit_sha,
+        serial,
+        hold: holdAfterRestore,
+        githubToken,
+      });
+      setMessage(
+        `Restored ${restored.path} to ${version.commit_sha.substring(0, 7)}` +
+          (restored.held ? " and put it on hold" : "")
+      );
+      onRestored();
+    } catch (e) {
+      setMessage(`Restore failed: ${e}`);
+    }
+    setLoading(false);
+  };
+
+  return (
+    <details className="bg-zinc-900 border border-zinc-700 rounded-lg p-4">
+      <summary className="text-sm font-medium text-zinc-300 cursor-pointer">Texture Version History</summary>
+
+      <div className="mt-3 space-y-3">
+        <div className="flex gap-2">
+          <input
+            type="text"
+            value={pathInput}
+            onChange={(e) => setPathInput(e.target.value)}
+            placeholder="Texture path inside the serial folder"
+            disabled={disabled || loading}
+            className="flex-1 px-2 py-1 bg-zinc-800 border border-zinc-600 rounded text-sm text-zinc-200 placeholder-zinc-500 focus:outline-none focus:border-blue-500"
+          />
+          <input
+            type="text"
+            value={serialInput}
+            onChange={(e) => setSerialInput(e.target.value)}
+            placeholder="SLUS-21770"
+            disabled={disabled || loading}
+            className="w-32 px-2 py-1 bg-zinc-800 border border-zinc-600 rounded text-sm text-zinc-200 placeholder-zinc-500 focus:outline-none focus:border-blue-500"
+          />
+          <button
+            onClick={handleShowHistory}
+            disabled={disabled || loading || pathInput.trim() === ""}
+            className="px-3 py-1 bg-zinc-700 hover:bg-zinc-600 disabled:bg-zinc-800 disabled:text-zinc-600 text-sm rounded transition-colors"
+          >
+            Show History
+          </button>
+        </div>
+
+        {versions && (
+          <>
+            <label className="flex items-center gap-2 text-xs text-zinc-400">
+              <input
+                type="checkbox"
+                checked={holdAfterRestore}
+                onChange={(e) => setHoldAfterRestore(e.target.checked)}
+                disabled={disabled || loading}
+              />
+              Hold the restored texture so syncs keep this version
+            </label>
+            {versions.length === 0 ? (
+              <p className="text-xs text-zinc-500">No commits changed this texture.</p>
+            ) : (
+              <ul className="text-xs space-y-1 max-h-60 overflow-y-auto">
+                {versions.map((version, i) => (
+                  <li key={version.commit_sha} className="flex items-center justify-between gap-2">
+                    <span className="text-zinc-400 truncate">
+                      <span className="font-mono text-zinc-300">{version.commit_sha.substring(0, 7)}</span>{" "}
+                      {new Date(version.date).toLocaleDateString()} {version.message}
+                      {i === 0 && <span className="ml-1 text-green-400">(latest)</span>}
+                    </span>
+                    <button
+                      onClick={() => handleRestore(version)}
+                      disabled={disabled || loading}
+                      className="px-2 py-0.5 bg-zinc-700 hover:bg-zinc-600 disabled:bg-zinc-800 disabled:text-zinc-600 rounded transition-colors whitespace-nowrap"
+                    >
+                      Restore
+                    </button>
+                  </li>
+                ))}
+              </ul>
+            )}
+          </>
+        )}
+
+        {message && <p className="text-xs text-zinc-400">{message}</p>}
+      </div>
+    </details>
+  );
+}
+
+export default TextureHistory;
@@ -0,0 +1,108 @@
+import { useState, useEffect } from "react";
+import { invoke } from "@tauri-apps/api/core";
+
+export interface SyncUndoSummary {
+  id: string;
+  kind: string;
+  created_at: string;
+  previous_commit: string | null;
+  new_commit: string | null;
+  files_restored: number;
+  files_removed: number;
+  files_moved: number;
+}
+
+interface UndoLastSyncProps {
+  texturesDir: string;
+  disabled?: boolean;
+  /** Changes whenever a sync or undo finishes, to reload the undo data */
+  refreshKey: unknown;
+  onUndo: () => void;
+}
+
+const RETENTION_OPTIONS = [0, 3, 7, 14, 30];
+const DEFAULT_RETENTION_DAYS = 14;
+
+function UndoLastSync({ texturesDir, disabled, refreshKey, onUndo }: UndoLastSyncProps) {
+  const [summary, setSummary] = useState<SyncUndoSummary | null>(null);
+  const [retentionDays, setRetentionDays] = useState(DEFAULT_RETENTION_DAYS);
+
+  useEffect(() => {
+    invoke<{ undo_retention_days: number | null }>("load_state")
+      .then((state) => setRetentionDays(state.undo_retention_days ?? DEFAULT_RETENTION_DAYS))
+      .catch(console.error);
+  }, []);
+
+  useEffect(() => {
+    if (!texturesDir) return;
+    invoke<SyncUndoSummary | null>("get_last_sync_undo", { texturesDir })
+      .then(setSummary)
+      .catch(() => setSummary(null));
+  }, [texturesDir, refreshKey, retentionDays]);
+
+  const handleRetentionChange = async (days: number) => {
+    try {
+      await invoke("set_undo_retention_days", { days });
+      setRetentionDays(days);
+    } catch (e) {
+      console.error("Failed to save undo retention:", e);
+    }
+  };
+
+  return (
+    <div className="bg-zinc-900 border border-zinc-700 rounded-lg p-4 space-y-3">
+      <div className="flex items-center justify-between">
+        <h3 className="text-sm font-medium text-zinc-300">Undo Last Sync</h3>
+        <label className="flex items-center gap-2 text-xs text-zinc-500">
+          Keep undo data
+          <select
+            value={retentionDays}
+            onChange={(e) => handleRetentionChange(Number(e.target.value))}
+            disabled={disabled}
+            className="px-1 py-0.5 bg-zinc-800 border border-zinc-600 rounded text-zinc-300"
+          >
+            {RETENTION_OPTIONS.map((days) => (
+              <option key={days} value={days}>
+                {days === 0 ? "off" : `${days} days`}
+              </option>
+            ))}
+          </select>
+        </label>
+      </div>
+
+      {summary ? (
+        <div className="flex items-center justify-between gap-3">
+          <p className="text-xs text-zinc-400">
+            {summary.kind === "verification"
+              ? "Verification fixes"
+              : summary.kind === "conflicts"
+                ? "Conflict resolution"
+                : summary.kind === "restore"
+                  ? "Texture restore"
+                  : summary.kind === "retry"
+                    ? "Retry of failed downloads"
+                    : summary.kind === "modules"
+                      ? "Module change"
+                      : "Sync"}{" "}
+            of{" "}
+            {new Date(summary.created_at).toLocaleString()}: restores {summary.files_restored},
+            removes {summary.files_removed}, moves back {summary.files_moved} file(s)
+          </p>
+          <button
+            onClick={onUndo}
+            disabled={disabled}
+            className="px-3 py-2 bg-zinc-700 hover:bg-zinc-600 disabled:bg-zinc-800 disabled:text-zinc-600 text-sm rounded transition-colors whitespace-nowrap"
+          >
+            Undo
+          </button>
+        </div>
+      ) : (
+        <p className="text-xs text-zinc-500">
+          {retentionDays === 0 ? "Undo data is turned off." : "No sync to undo."}
+        </p>
+      )}
+    </div>
+  );
+}
+
+export default UndoLastSync;
@@ -1,11 +1,14 @@
 interface VerificationFile {
   path: string;
   to_disabled: boolean;
+  sha?: string | null;
 }
 
 interface VerificationDialogProps {
   filesToDownload: VerificationFile[];
   filesToDelete: string[];
+  /** Files with both an enabled and a disabled copy */
+  conflicts?: string[];
   onConfirm: () => void;
   onCancel: () => void;
   isApplying: boolean;
@@ -14,6 +17,7 @@ interface VerificationDialogProps {
 function VerificationDialog({
   filesToDownload,
   filesToDelete,
+  conflicts = [],
   onConfirm,
   onCancel,
   isApplying,
@@ -74,6 +78,24 @@ function VerificationDialog({
               </div>
             </div>
           )}
+
+          {conflicts.length > 0 && (
+            <div>
+              <h4 className="text-sm font-medium text-yellow-400 mb-2">
+                Enabled and disabled copies ({conflicts.length})
+              </h4>
+              <p className="text-xs text-zinc-400 mb-2">
+                Handled by your setting for files that exist both enabled and disabled.
+              </p>
+              <div className="bg-zinc-900 border border-zinc-700 rounded p-2 max-h-40 overflow-y-auto">
+                <ul className="text-xs text-zinc-300 space-y-0.5 font-mono">
+                  {conflicts.map((path, i) => (
+                    <li key={i} className="truncate">{path}</li>
+                  ))}
+                </ul>
+              </div>
+            </div>
+          )}
         </div>
 
         <div className="p-4 border-t border-zinc-700 flex gap-3 justify-end">
@@ -27,6 +27,49 @@ pub struct InstallerData {
     pub total_size: String,
     /// URL where users can download the latest version of the app
     pub downloader_app_url: String,
+    /// Optional content modules users can choose to install
+    #[serde(default)]
+    pub modules: Vec<ContentModule>,
+    /// Share of tracked files (percent) a sync may delete or replace without confirmation
+    #[serde(default)]
+    pub mass_change_limit_percent: Option<f64>,
+    /// Release channels users can follow instead of main
+    #[serde(default)]
+    pub channels: Vec<ReleaseChannel>,
+}
+
+/// Release channel (e.g. stable or beta) following a branch or tag of the mod repository
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseChannel {
+    /// Stable identifier stored in the user's settings
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Branch or tag syncs install on this channel
+    #[serde(rename = "ref")]
+    pub git_ref: String,
+    /// Minimum version of this download app for the channel (empty for none)
+    #[serde(default, deserialize_with = "string_or_number")]
+    pub min_download_app_version: String,
+}
+
+/// Optional content module (a subfolder of the pack the user can opt in or out of)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContentModule {
+    /// Stable identifier stored in the user's selection
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Folder relative to the SLUS folder (e.g. "crowds/hd")
+    pub path: String,
+    /// Download size (e.g. "1.2 GB" or just "1.2")
+    #[serde(default, deserialize_with = "string_or_number")]
+    pub size: String,
+    /// Whether the module is selected for users who never changed their selection
+    #[serde(default)]
+    pub default_enabled: bool,
 }
 
 /// Result of fetching installer data
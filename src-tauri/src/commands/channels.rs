@@ -0,0 +1,65 @@
+use super::app_info::{compare_versions, fetch_installer_data, ReleaseChannel};
+use super::state::{load_state, save_state};
+use super::sync::{get_commit_details_with_token, DEFAULT_SYNC_REF};
+use tauri::AppHandle;
+
+/// Fetch the release channel declarations from installer-data.json
+async fn fetch_release_channels() -> Result<Vec<ReleaseChannel>, String> {
+    let result = fetch_installer_data().await;
+    match result.data {
+        Some(data) => Ok(data.channels),
+        None => Err(result
+            .error
+            .unwrap_or_else(|| "Failed to fetch installer data".to_string())),
+    }
+}
+
+/// Look up a channel, failing if it is gone or needs a newer app
+async fn find_channel(app: &AppHandle, id: &str) -> Result<ReleaseChannel, String> {
+    let channel = fetch_release_channels()
+        .await?
+        .into_iter()
+        .find(|channel| channel.id == id)
+        .ok_or_else(|| format!("Release channel \"{}\" is no longer offered. Pick another channel.", id))?;
+
+    let app_version = app.package_info().version.to_string();
+    if !channel.min_download_app_version.is_empty()
+        && compare_versions(app_version.clone(), channel.min_download_app_version.clone()) < 0
+    {
+        return Err(format!(
+            "The {} channel needs app version {} or newer (this is {}). Update the app or pick another channel.",
+            channel.name, channel.min_download_app_version, app_version
+        ));
+    }
+
+    Ok(channel)
+}
+
+/// Branch or tag of the user's release channel (None when following main)
+pub(crate) async fn load_channel_ref(app: &AppHandle) -> Result<Option<String>, String> {
+    match load_state(app.clone())?.release_channel {
+        Some(id) => Ok(Some(find_channel(app, &id).await?.git_ref)),
+        None => Ok(None),
+    }
+}
+
+/// Follow a release channel (None for main); ends a pinned sync ref so the channel takes effect
+/// Returns the commit SHA of the channel's head; the next sync moves the folders to it
+#[tauri::command]
+pub async fn set_release_channel(
+    app: AppHandle,
+    channel: Option<String>,
+    github_token: Option<String>,
+) -> Result<String, String> {
+    let commit_ref = match &channel {
+        Some(id) => find_channel(&app, id).await?.git_ref,
+        None => DEFAULT_SYNC_REF.to_string(),
+    };
+    let (sha, _) = get_commit_details_with_token(&commit_ref, &github_token).await?;
+
+    let mut state = load_state(app.clone())?;
+    state.release_channel = channel;
+    state.sync_ref = None;
+    save_state(app, state)?;
+    Ok(sha)
+}
@@ -0,0 +1,189 @@
+use super::state::{load_state, AppState};
+use crate::config::{SLUS_FOLDER, TEMP_DIR_NAME};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+/// Suffix of files that are still being downloaded
+pub(crate) const PARTIAL_SUFFIX: &str = ".part";
+
+/// Kind of leftover found by the stale file scan
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StaleItemKind {
+    /// Temp clone left behind by an interrupted installation
+    TempClone,
+    /// Staging folder left behind by an interrupted cross-drive move
+    StagingFolder,
+    /// SLUS_backup_<timestamp> folder created when reinstalling
+    Backup,
+    /// Download that never finished
+    PartialFile,
+}
+
+/// A leftover file or folder that can be deleted to reclaim space
+#[derive(Debug, Clone, Serialize)]
+pub struct StaleItem {
+    pub path: String,
+    pub kind: StaleItemKind,
+    pub size_bytes: u64,
+}
+
+/// Result of the stale file scan
+#[derive(Debug, Clone, Serialize)]
+pub struct StaleScanResult {
+    pub items: Vec<StaleItem>,
+    pub total_bytes: u64,
+}
+
+/// Resolve the scratch directory used for installation clones
+/// Falls back to the textures directory when no custom location is configured
+pub(crate) fn resolve_temp_root(app: &AppHandle, textures_dir: &Path) -> PathBuf {
+    load_state(app.clone())
+        .ok()
+        .and_then(|state| state.temp_path)
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| textures_dir.to_path_buf())
+}
+
+/// Total size of a file or directory (symlinks are not followed)
+fn path_size(path: &Path) -> u64 {
+    let metadata = match fs::symlink_metadata(path) {
+        Ok(m) => m,
+        Err(_) => return 0,
+    };
+
+    if !metadata.is_dir() {
+        return metadata.len();
+    }
+
+    fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| path_size(&entry.path()))
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+/// Find partial downloads below a directory
+fn find_partial_files(dir: &Path, items: &mut Vec<StaleItem>) {
+    let entries = match fs::read_dir(dir) {
+        Ok(e) => e,
+        Err(_) => return,
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+
+        if file_type.is_dir() {
+            find_partial_files(&path, items);
+        } else if entry.file_name().to_string_lossy().ends_with(PARTIAL_SUFFIX) {
+            items.push(StaleItem {
+                path: path.to_string_lossy().to_string(),
+                kind: StaleItemKind::PartialFile,
+                size_bytes: entry.metadata().map(|m| m.len()).unwrap_or(0),
+            });
+        }
+    }
+}
+
+/// SLUS folder plus every configured serial folder
+fn serial_folder_names(state: &AppState) -> Vec<String> {
+    let mut folders = vec![SLUS_FOLDER.to_string()];
+    folders.extend(state.serial_folders.iter().map(|f| f.serial.clone()));
+    folders
+}
+
+/// Collect all leftovers for a textures directory and scratch location
+fn collect_stale_items(textures_dir: &Path, temp_root: &Path, folders: &[String]) -> Vec<StaleItem> {
+    let mut items = Vec::new();
+    let mut add_dir = |path: PathBuf, kind: StaleItemKind| {
+        if path.is_dir() && !items.iter().any(|i: &StaleItem| Path::new(&i.path) == path) {
+            items.push(StaleItem {
+                path: path.to_string_lossy().to_string(),
+                kind,
+                size_bytes: path_size(&path),
+            });
+        }
+    };
+
+    add_dir(textures_dir.join(TEMP_DIR_NAME), StaleItemKind::TempClone);
+    add_dir(temp_root.join(TEMP_DIR_NAME), StaleItemKind::TempClone);
+    for folder in folders {
+        add_dir(
+            textures_dir.join(format!("{}.incoming", folder)),
+            StaleItemKind::StagingFolder,
+        );
+    }
+
+    let backup_prefixes: Vec<String> = folders.iter().map(|f| format!("{}_backup_", f)).collect();
+    if let Ok(entries) = fs::read_dir(textures_dir) {
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if backup_prefixes.iter().any(|prefix| name.starts_with(prefix)) {
+                add_dir(entry.path(), StaleItemKind::Backup);
+            }
+        }
+    }
+
+    for folder in folders {
+        find_partial_files(&textures_dir.join(folder), &mut items);
+    }
+
+    items
+}
+
+/// Scan for orphaned temp clones, staging folders, backups and partial downloads
+#[tauri::command]
+pub fn scan_stale_files(app: AppHandle, textures_dir: String) -> Result<StaleScanResult, String> {
+    let textures_path = PathBuf::from(&textures_dir);
+    if !textures_path.is_dir() {
+        return Err(format!("Textures directory not found: {}", textures_dir));
+    }
+
+    let temp_root = resolve_temp_root(&app, &textures_path);
+    let folders = serial_folder_names(&load_state(app.clone())?);
+    let items = collect_stale_items(&textures_path, &temp_root, &folders);
+    let total_bytes = items.iter().map(|i| i.size_bytes).sum();
+
+    Ok(StaleScanResult { items, total_bytes })
+}
+
+/// Delete the given leftovers and return the number of bytes reclaimed
+/// Only paths found by a fresh scan are deleted, anything else is ignored
+#[tauri::command]
+pub fn reclaim_stale_files(
+    app: AppHandle,
+    textures_dir: String,
+    paths: Vec<String>,
+) -> Result<u64, String> {
+    let textures_path = PathBuf::from(&textures_dir);
+    let temp_root = resolve_temp_root(&app, &textures_path);
+    let folders = serial_folder_names(&load_state(app.clone())?);
+    let mut reclaimed = 0;
+
+    for item in collect_stale_items(&textures_path, &temp_root, &folders) {
+        if !paths.contains(&item.path) {
+            continue;
+        }
+
+        let path = Path::new(&item.path);
+        let result = if item.kind == StaleItemKind::PartialFile {
+            fs::remove_file(path)
+        } else {
+            fs::remove_dir_all(path)
+        };
+        result.map_err(|e| format!("Failed to delete {}: {}", item.path, e))?;
+
+        reclaimed += item.size_bytes;
+    }
+
+    Ok(reclaimed)
+}
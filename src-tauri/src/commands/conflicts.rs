@@ -0,0 +1,165 @@
+use super::serials::{refresh_mirror_folders, resolve_target};
+use super::state::{load_state, save_state};
+use super::sync::{apply_target_changes, get_disabled_path, get_enabled_path, SyncProgressPayload};
+use super::undo::SyncUndo;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::path::PathBuf;
+use tauri::{AppHandle, Emitter, Manager, Window};
+
+/// What a sync does when a file exists both enabled and dash-disabled ("foo.png" and "-foo.png")
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictPolicy {
+    /// Keep the enabled copy and delete the disabled one
+    #[default]
+    PreferEnabled,
+    /// Keep the disabled copy and delete the enabled one
+    PreferDisabled,
+    /// Leave both copies alone and report them for the user to decide
+    Ask,
+}
+
+impl ConflictPolicy {
+    /// Whether the disabled copy is kept (None when the user decides)
+    pub(crate) fn keeps_disabled(self) -> Option<bool> {
+        match self {
+            ConflictPolicy::PreferEnabled => Some(false),
+            ConflictPolicy::PreferDisabled => Some(true),
+            ConflictPolicy::Ask => None,
+        }
+    }
+}
+
+/// A file with both an enabled and a disabled local copy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DisabledConflict {
+    pub serial: String,
+    /// Repo path relative to the serial folder
+    pub path: String,
+}
+
+/// The conflict policy of this install
+pub(crate) fn load_conflict_policy(app: &AppHandle) -> Result<ConflictPolicy, String> {
+    Ok(load_state(app.clone())?.conflict_policy)
+}
+
+/// Set what syncs do with files that exist both enabled and disabled
+#[tauri::command]
+pub fn set_conflict_policy(app: AppHandle, policy: ConflictPolicy) -> Result<(), String> {
+    let mut state = load_state(app.clone())?;
+    state.conflict_policy = policy;
+    save_state(app, state)
+}
+
+/// Repo files of a folder with both an enabled and a disabled local copy, resolved by the policy
+pub(crate) struct DisabledConflicts {
+    policy: ConflictPolicy,
+    paths: BTreeSet<String>,
+}
+
+impl DisabledConflicts {
+    /// Find the conflicts among the local files of a full comparison
+    pub(crate) fn find(
+        policy: ConflictPolicy,
+        local_files: &HashMap<String, String>,
+        remote_files: &HashMap<String, String>,
+    ) -> Self {
+        let paths = local_files
+            .keys()
+            // A dash-named repo file is a file of its own, not a disabled copy
+            .filter(|local_path| !remote_files.contains_key(*local_path))
+            .filter_map(|local_path| get_enabled_path(local_path))
+            .filter(|enabled_path| local_files.contains_key(enabled_path) && remote_files.contains_key(enabled_path))
+            .collect();
+        DisabledConflicts { policy, paths }
+    }
+
+    /// Whether the local copies of a repo file are left for the user to decide
+    pub(crate) fn is_unresolved(&self, repo_path: &str) -> bool {
+        self.policy == ConflictPolicy::Ask && self.paths.contains(repo_path)
+    }
+
+    /// Whether the disabled copy is the one compared against (and kept for) a repo file
+    pub(crate) fn keeps_disabled(&self, repo_path: &str) -> bool {
+        self.policy == ConflictPolicy::PreferDisabled && self.paths.contains(repo_path)
+    }
+
+    /// Whether a local file is either copy of a conflict
+    pub(crate) fn involves(&self, local_path: &str) -> bool {
+        self.paths.contains(local_path) || get_enabled_path(local_path).is_some_and(|p| self.paths.contains(&p))
+    }
+
+    /// Local copies the policy deletes
+    pub(crate) fn losing_copies(&self) -> Vec<String> {
+        match self.policy.keeps_disabled() {
+            Some(true) => self.paths.iter().cloned().collect(),
+            Some(false) => self.paths.iter().map(|path| get_disabled_path(path)).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Repo paths of the conflicts, for reporting
+    pub(crate) fn paths(&self) -> Vec<String> {
+        self.paths.iter().cloned().collect()
+    }
+}
+
+/// Resolve conflicts the sync left for the user, deleting the copy they don't keep
+/// `paths` are repo paths relative to the serial folder; returns the number of files deleted
+#[tauri::command]
+pub async fn resolve_disabled_conflicts(
+    textures_dir: String,
+    serial: Option<String>,
+    paths: Vec<String>,
+    keep_disabled: bool,
+    window: Window,
+) -> Result<u32, String> {
+    let textures_path = PathBuf::from(&textures_dir);
+    let target = resolve_target(window.app_handle(), serial)?;
+    let slus_path = textures_path.join(&target.folder);
+
+    // Only delete a copy while the other one is still there
+    let files_to_delete: Vec<String> = paths
+        .iter()
+        .filter(|path| slus_path.join(path).is_file() && slus_path.join(get_disabled_path(path)).is_file())
+        .map(|path| if keep_disabled { path.clone() } else { get_disabled_path(path) })
+        .collect();
+
+    let mut undo = SyncUndo::start(window.app_handle(), &textures_path, "conflicts")?;
+    let (_, deleted, _, _) = apply_target_changes(
+        &slus_path,
+        &target.sparse_path,
+        // Only deletes, nothing is downloaded
+        "",
+        &[],
+        &files_to_delete,
+        &[],
+        &[],
+        &None,
+        &mut undo,
+        &window,
+    )
+    .await?;
+    undo.finish(None)?;
+
+    if target.is_primary() {
+        refresh_mirror_folders(window.app_handle(), &textures_path, |message| {
+            let _ = window.emit("sync-progress", SyncProgressPayload {
+                stage: "syncing".to_string(),
+                message,
+                current: None,
+                total: None,
+            });
+        })?;
+    }
+
+    let _ = window.emit("sync-progress", SyncProgressPayload {
+        stage: "complete".to_string(),
+        message: format!("Resolved conflicts: deleted {} duplicate copies", deleted),
+        current: None,
+        total: None,
+    });
+
+    Ok(deleted)
+}
@@ -0,0 +1,200 @@
+use super::holds::HoldList;
+use super::mass_change::MassChangeGuard;
+use super::serials::{load_pack_targets, refresh_mirror_folders};
+use super::state::{load_state, save_state};
+use super::sync::{
+    apply_target_changes, FailedAction, FailedFile, SyncProgressPayload, SyncResult, TargetAnalysis,
+};
+use super::undo::SyncUndo;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tauri::{AppHandle, Emitter, Manager, Window};
+
+/// Downloads a sync could not finish, kept until they are retried
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FailedSync {
+    /// Commit the sync was applying; recorded as the last sync commit once nothing is left failing
+    pub commit_sha: String,
+    pub failed: Vec<FailedFile>,
+    /// Failed verification fixes, which leave the last sync commit as it is
+    #[serde(default)]
+    pub keeps_commit: bool,
+}
+
+/// Remember the failed downloads of a sync to `commit_sha` (none clears them)
+pub(crate) fn record_failed_sync(app: &AppHandle, commit_sha: &str, failed: &[FailedFile]) -> Result<(), String> {
+    let mut state = load_state(app.clone())?;
+    state.failed_sync = if failed.is_empty() {
+        None
+    } else {
+        Some(FailedSync { commit_sha: commit_sha.to_string(), failed: failed.to_vec(), keeps_commit: false })
+    };
+    save_state(app.clone(), state)
+}
+
+/// Remember the failed fixes of a verification at `commit_sha` so they can be retried
+/// They are added to the failed files of a sync to the same commit; the failed files of a sync to another
+/// commit are kept instead, since retrying those records their commit
+pub(crate) fn record_failed_fixes(app: &AppHandle, commit_sha: &str, failed: &[FailedFile]) -> Result<(), String> {
+    if failed.is_empty() {
+        return Ok(());
+    }
+    let mut state = load_state(app.clone())?;
+    match &mut state.failed_sync {
+        Some(pending) if pending.commit_sha == commit_sha => {
+            pending.failed.retain(|file| {
+                !failed.iter().any(|f| f.folder == file.folder && f.path == file.path && f.to_disabled == file.to_disabled)
+            });
+            pending.failed.extend(failed.iter().cloned());
+        }
+        Some(_) => return Ok(()),
+        None => {
+            state.failed_sync =
+                Some(FailedSync { commit_sha: commit_sha.to_string(), failed: failed.to_vec(), keeps_commit: true });
+        }
+    }
+    save_state(app.clone(), state)
+}
+
+/// Set whether syncs go on past files that fail to download
+#[tauri::command]
+pub fn set_continue_on_errors(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut state = load_state(app.clone())?;
+    state.continue_on_errors = enabled;
+    save_state(app, state)
+}
+
+/// Download (or delete) the files the last sync failed to, as they are at that sync's commit
+/// Held files are left alone and dropped from the failed files; once none are left failing,
+/// the commit is recorded as the last sync commit (unless they were verification fixes)
+/// `allow_mass_changes` confirms changes above the mass-change limit
+#[tauri::command]
+pub async fn retry_failed_files(
+    textures_dir: String,
+    github_token: Option<String>,
+    allow_mass_changes: Option<bool>,
+    window: Window,
+) -> Result<SyncResult, String> {
+    let app = window.app_handle();
+    let textures_path = PathBuf::from(&textures_dir);
+    let pending = load_state(app.clone())?.failed_sync.ok_or("No failed files to retry")?;
+
+    // Files of serial folders that were removed since are no longer synced
+    let mut targets = Vec::new();
+    let mut held = Vec::new();
+    for target in load_pack_targets(app)? {
+        let files = pending.failed.iter().filter(|file| file.folder == target.folder);
+        let mut analysis = TargetAnalysis {
+            serial: target.folder.clone(),
+            files_to_add: Vec::new(),
+            files_to_replace: files
+                .clone()
+                .filter(|file| file.action == FailedAction::Download)
+                .map(FailedFile::to_sync_file)
+                .collect(),
+            files_to_delete: files
+                .filter(|file| file.action == FailedAction::Delete)
+                .map(|file| file.path.clone())
+                .collect(),
+            files_to_move: Vec::new(),
+            files_to_rescue: Vec::new(),
+            conflicts: Vec::new(),
+            held: Vec::new(),
+        };
+        // Held files are left alone
+        let target_held = HoldList::load(app, &target.folder)?.take_held(&mut analysis);
+        held.extend(target_held.iter().map(|path| format!("{}/{}", target.folder, path)));
+        if !analysis.files_to_replace.is_empty() || !analysis.files_to_delete.is_empty() {
+            targets.push((target, analysis));
+        }
+    }
+
+    let mut guard = MassChangeGuard::load(app, "retry", allow_mass_changes.unwrap_or(false)).await?;
+    for (target, analysis) in &targets {
+        guard.check(
+            &textures_path.join(&target.folder),
+            &analysis.files_to_replace,
+            &analysis.files_to_delete,
+            &[],
+        )?;
+    }
+    let mut undo = SyncUndo::start(app, &textures_path, "retry")?;
+
+    let _ = window.emit("sync-progress", SyncProgressPayload {
+        stage: "syncing".to_string(),
+        message: format!("Retrying {} failed file(s)...", pending.failed.len()),
+        current: None,
+        total: None,
+    });
+
+    let mut downloaded: u32 = 0;
+    let mut deleted: u32 = 0;
+    let mut failed = Vec::new();
+    for (target, analysis) in targets {
+        let (target_downloaded, target_deleted, _, target_failed) = apply_target_changes(
+            &textures_path.join(&target.folder),
+            &target.sparse_path,
+            &pending.commit_sha,
+            &analysis.files_to_replace,
+            &analysis.files_to_delete,
+            &[],
+            &[],
+            &github_token,
+            &mut undo,
+            &window,
+        )
+        .await?;
+        downloaded += target_downloaded;
+        deleted += target_deleted;
+        failed.extend(target_failed);
+    }
+    undo.finish(Some(&pending.commit_sha))?;
+    guard.record_completed(Some(&pending.commit_sha), downloaded, deleted, 0);
+
+    let mut state = load_state(app.clone())?;
+    if failed.is_empty() {
+        if !pending.keeps_commit {
+            state.last_sync_commit = Some(pending.commit_sha.clone());
+            state.last_sync_timestamp = Some(Utc::now().to_rfc3339());
+        }
+        state.failed_sync = None;
+    } else {
+        state.failed_sync = Some(FailedSync { failed: failed.clone(), ..pending.clone() });
+    }
+    save_state(app.clone(), state)?;
+
+    refresh_mirror_folders(app, &textures_path, |message| {
+        let _ = window.emit("sync-progress", SyncProgressPayload {
+            stage: "syncing".to_string(),
+            message,
+            current: None,
+            total: None,
+        });
+    })?;
+
+    let _ = window.emit("sync-progress", SyncProgressPayload {
+        stage: "complete".to_string(),
+        message: format!(
+            "Retry complete! Downloaded: {}, Deleted: {}, Still failing: {}",
+            downloaded,
+            deleted,
+            failed.len()
+        ),
+        current: None,
+        total: None,
+    });
+
+    Ok(SyncResult {
+        files_downloaded: downloaded,
+        files_deleted: deleted,
+        files_renamed: 0,
+        files_skipped: 0,
+        new_commit_sha: pending.commit_sha,
+        skipped: Vec::new(),
+        rescued: Vec::new(),
+        conflicts: Vec::new(),
+        held,
+        failed,
+    })
+}
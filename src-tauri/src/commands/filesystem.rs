@@ -1,46 +1,62 @@
+use super::serials::load_pack_targets;
 use crate::config::SLUS_FOLDER;
 use chrono::Local;
+use sha1::{Digest, Sha1};
 use std::fs;
-use std::path::PathBuf;
+use std::io;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
 
-/// Check if the SLUS folder already exists in the textures directory
+/// Existing folders the installation would create (SLUS folder and serial folders with their own pack)
+fn existing_install_folders(app: &AppHandle, textures_dir: &str) -> Result<Vec<String>, String> {
+    Ok(load_pack_targets(app)?
+        .into_iter()
+        .map(|t| t.folder)
+        .filter(|folder| PathBuf::from(textures_dir).join(folder).exists())
+        .collect())
+}
+
+/// Check if the SLUS folder (or a serial folder with its own pack) already exists in the textures directory
 #[tauri::command]
-pub fn check_existing_folder(textures_dir: String) -> Result<bool, String> {
-    let path = PathBuf::from(&textures_dir).join(SLUS_FOLDER);
-    Ok(path.exists())
+pub fn check_existing_folder(app: AppHandle, textures_dir: String) -> Result<bool, String> {
+    Ok(!existing_install_folders(&app, &textures_dir)?.is_empty())
 }
 
-/// Backup the existing SLUS folder by renaming it with a timestamp
+/// Backup the existing SLUS folder (and serial folders with their own pack) by renaming with a timestamp
+/// Returns the backup folder names, comma separated
 #[tauri::command]
-pub fn backup_existing_folder(textures_dir: String) -> Result<String, String> {
-    let source = PathBuf::from(&textures_dir).join(SLUS_FOLDER);
+pub fn backup_existing_folder(app: AppHandle, textures_dir: String) -> Result<String, String> {
+    let folders = existing_install_folders(&app, &textures_dir)?;
 
-    if !source.exists() {
+    if folders.is_empty() {
         return Err(format!("Folder {} does not exist", SLUS_FOLDER));
     }
 
     let timestamp = Local::now().format("%Y%m%d_%H%M%S");
-    let backup_name = format!("{}_backup_{}", SLUS_FOLDER, timestamp);
-    let dest = PathBuf::from(&textures_dir).join(&backup_name);
+    let mut backup_names = Vec::new();
 
-    fs::rename(&source, &dest)
-        .map_err(|e| format!("Failed to backup folder: {}", e))?;
+    for folder in folders {
+        let backup_name = format!("{}_backup_{}", folder, timestamp);
+        let source = PathBuf::from(&textures_dir).join(&folder);
+        let dest = PathBuf::from(&textures_dir).join(&backup_name);
+
+        fs::rename(&source, &dest)
+            .map_err(|e| format!("Failed to backup folder: {}", e))?;
+        backup_names.push(backup_name);
+    }
 
-    Ok(backup_name)
+    Ok(backup_names.join(", "))
 }
 
-/// Delete the existing SLUS folder
+/// Delete the existing SLUS folder (and serial folders with their own pack)
 #[tauri::command]
-pub fn delete_existing_folder(textures_dir: String) -> Result<(), String> {
-    let path = PathBuf::from(&textures_dir).join(SLUS_FOLDER);
-
-    if !path.exists() {
-        return Ok(());
+pub fn delete_existing_folder(app: AppHandle, textures_dir: String) -> Result<(), String> {
+    for folder in existing_install_folders(&app, &textures_dir)? {
+        let path = PathBuf::from(&textures_dir).join(folder);
+        fs::remove_dir_all(&path)
+            .map_err(|e| format!("Failed to delete folder: {}", e))?;
     }
 
-    fs::remove_dir_all(&path)
-        .map_err(|e| format!("Failed to delete folder: {}", e))?;
-
     Ok(())
 }
 
@@ -69,3 +85,196 @@ pub fn validate_directory(path: String) -> Result<bool, String> {
         Err(e) => Err(format!("Cannot access directory: {}", e)),
     }
 }
+
+/// Check if an io error means source and destination are on different devices/volumes
+/// (EXDEV on macOS/Linux, ERROR_NOT_SAME_DEVICE on Windows)
+fn is_cross_device_error(e: &io::Error) -> bool {
+    let raw_code = if cfg!(target_os = "windows") { 17 } else { 18 };
+    e.kind() == io::ErrorKind::CrossesDevices || e.raw_os_error() == Some(raw_code)
+}
+
+/// Collect all files below `current` as (path relative to `root`, size)
+fn collect_files(root: &Path, current: &Path, files: &mut Vec<(PathBuf, u64)>) -> Result<(), String> {
+    let entries = fs::read_dir(current)
+        .map_err(|e| format!("Failed to read directory {}: {}", current.display(), e))?;
+
+    for entry in entries {
+        let entry = entry.map_err(|e| format!("Failed to read entry: {}", e))?;
+        let path = entry.path();
+
+        if path.is_dir() {
+            collect_files(root, &path, files)?;
+        } else {
+            let size = entry
+                .metadata()
+                .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?
+                .len();
+            let relative = path
+                .strip_prefix(root)
+                .map_err(|e| format!("Failed to get relative path: {}", e))?
+                .to_path_buf();
+            files.push((relative, size));
+        }
+    }
+
+    Ok(())
+}
+
+/// SHA-1 of a file's content
+fn file_sha1(path: &Path) -> io::Result<Vec<u8>> {
+    let mut hasher = Sha1::new();
+    io::copy(&mut fs::File::open(path)?, &mut hasher)?;
+    Ok(hasher.finalize().to_vec())
+}
+
+/// Copy every file into `staging` and check each copy has the source's content
+fn copy_and_verify(
+    source: &Path,
+    staging: &Path,
+    on_progress: &mut impl FnMut(u64, u64),
+) -> Result<(), String> {
+    let mut files = Vec::new();
+    collect_files(source, source, &mut files)?;
+
+    let total_bytes: u64 = files.iter().map(|(_, size)| size).sum();
+    let mut copied_bytes: u64 = 0;
+    on_progress(0, total_bytes);
+
+    fs::create_dir_all(staging)
+        .map_err(|e| format!("Failed to create {}: {}", staging.display(), e))?;
+
+    for (relative, size) in &files {
+        let from = source.join(relative);
+        let to = staging.join(relative);
+
+        if let Some(parent) = to.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create directory: {}", e))?;
+        }
+
+        fs::copy(&from, &to)
+            .map_err(|e| format!("Failed to copy {}: {}", relative.display(), e))?;
+
+        let verify_error = |e: io::Error| format!("Failed to verify {}: {}", relative.display(), e);
+        if file_sha1(&to).map_err(verify_error)? != file_sha1(&from).map_err(verify_error)? {
+            return Err(format!("Verification failed for {}: the copy differs from the original", relative.display()));
+        }
+
+        copied_bytes += size;
+        on_progress(copied_bytes, total_bytes);
+    }
+
+    Ok(())
+}
+
+/// Move a directory to `dest`
+/// Uses a plain rename when possible. When `dest` is on another device (e.g. a junctioned or
+/// symlinked textures folder), copies into a staging folder next to `dest`, verifies every file,
+/// renames the staging folder into place and only then deletes the source. `dest` is therefore
+/// either absent or complete, never half-populated.
+/// `on_progress` receives (bytes copied, total bytes) during the copy fallback.
+pub(crate) fn move_dir_across_devices(
+    source: &Path,
+    dest: &Path,
+    on_progress: impl FnMut(u64, u64),
+) -> Result<(), String> {
+    match fs::rename(source, dest) {
+        Ok(()) => Ok(()),
+        Err(e) if is_cross_device_error(&e) => copy_dir_into_place(source, dest, on_progress),
+        Err(e) => Err(format!("Failed to move folder to final location: {}", e)),
+    }
+}
+
+/// Copy fallback of `move_dir_across_devices`
+fn copy_dir_into_place(source: &Path, dest: &Path, mut on_progress: impl FnMut(u64, u64)) -> Result<(), String> {
+    let dest_name = dest
+        .file_name()
+        .ok_or_else(|| format!("Invalid destination {}", dest.display()))?
+        .to_string_lossy();
+    let staging = dest.with_file_name(format!("{}.incoming", dest_name));
+
+    // Leftover from an earlier interrupted copy
+    if staging.exists() {
+        fs::remove_dir_all(&staging)
+            .map_err(|e| format!("Failed to clean {}: {}", staging.display(), e))?;
+    }
+
+    if let Err(e) = copy_and_verify(source, &staging, &mut on_progress) {
+        let _ = fs::remove_dir_all(&staging);
+        return Err(e);
+    }
+
+    // Same device as dest, so this rename is atomic
+    if let Err(e) = fs::rename(&staging, dest) {
+        let _ = fs::remove_dir_all(&staging);
+        return Err(format!("Failed to move folder to final location: {}", e));
+    }
+
+    fs::remove_dir_all(source)
+        .map_err(|e| format!("Failed to remove {} after copying: {}", source.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::TempFolder;
+
+    /// A pack folder holding a file at the top and one in a subfolder
+    fn pack_folder(temp: &TempFolder) -> PathBuf {
+        let source = temp.0.join("source").join("SLUS-21770");
+        fs::create_dir_all(source.join("textures/sub")).unwrap();
+        fs::write(source.join("top.png"), b"top").unwrap();
+        fs::write(source.join("textures/sub/abc.png"), b"abc").unwrap();
+        source
+    }
+
+    #[test]
+    fn copies_the_folder_into_place_and_removes_the_source() {
+        let temp = TempFolder::new("filesystem-copy");
+        let source = pack_folder(&temp);
+        let dest = temp.0.join("SLUS-21770");
+
+        let mut progress = Vec::new();
+        copy_dir_into_place(&source, &dest, |copied, total| progress.push((copied, total))).unwrap();
+
+        assert_eq!(fs::read(dest.join("top.png")).unwrap(), b"top");
+        assert_eq!(fs::read(dest.join("textures/sub/abc.png")).unwrap(), b"abc");
+        assert!(!source.exists());
+        assert!(!temp.0.join("SLUS-21770.incoming").exists());
+        assert_eq!(progress.last(), Some(&(6, 6)));
+    }
+
+    #[test]
+    fn replaces_a_leftover_incoming_folder() {
+        let temp = TempFolder::new("filesystem-leftover");
+        let source = pack_folder(&temp);
+        let dest = temp.0.join("SLUS-21770");
+        let staging = temp.0.join("SLUS-21770.incoming");
+        fs::create_dir_all(&staging).unwrap();
+        fs::write(staging.join("stale.png"), b"stale").unwrap();
+
+        copy_dir_into_place(&source, &dest, |_, _| {}).unwrap();
+
+        assert!(!dest.join("stale.png").exists());
+        assert_eq!(fs::read(dest.join("textures/sub/abc.png")).unwrap(), b"abc");
+        assert!(!staging.exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn failed_copy_keeps_the_source_and_leaves_no_destination() {
+        let temp = TempFolder::new("filesystem-failed");
+        let source = pack_folder(&temp);
+        let dest = temp.0.join("SLUS-21770");
+        // Can't be copied: the link points nowhere
+        std::os::unix::fs::symlink(temp.0.join("missing.png"), source.join("textures/broken.png")).unwrap();
+
+        assert!(copy_dir_into_place(&source, &dest, |_, _| {}).is_err());
+
+        assert_eq!(fs::read(source.join("top.png")).unwrap(), b"top");
+        assert_eq!(fs::read(source.join("textures/sub/abc.png")).unwrap(), b"abc");
+        assert!(fs::symlink_metadata(source.join("textures/broken.png")).is_ok());
+        assert!(!dest.exists());
+        assert!(!temp.0.join("SLUS-21770.incoming").exists());
+    }
+}
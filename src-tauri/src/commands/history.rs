@@ -0,0 +1,94 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager};
+
+/// Entries kept in the sync history
+const MAX_HISTORY_ENTRIES: usize = 200;
+
+/// A folder where a sync would delete or replace more files than the safety limit allows
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MassChangeTrip {
+    pub folder: String,
+    /// Files the sync would delete or replace
+    pub affected: u32,
+    /// Files among them the sync would move to user-customs/rescued
+    #[serde(default)]
+    pub rescued: u32,
+    /// Tracked files in the folder before the sync
+    pub tracked: u32,
+    pub limit_percent: f64,
+    /// Whether the user confirmed and the sync went ahead
+    pub confirmed: bool,
+}
+
+/// One sync, as shown in the sync history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncHistoryEntry {
+    pub timestamp: String,
+    /// What ran ("sync", "verification" or "undo")
+    pub kind: String,
+    /// "completed" or "blocked" (stopped by the mass-change limit)
+    pub outcome: String,
+    #[serde(default)]
+    pub commit_sha: Option<String>,
+    #[serde(default)]
+    pub files_downloaded: u32,
+    #[serde(default)]
+    pub files_deleted: u32,
+    #[serde(default)]
+    pub files_renamed: u32,
+    #[serde(default)]
+    pub mass_changes: Vec<MassChangeTrip>,
+}
+
+impl SyncHistoryEntry {
+    pub(crate) fn new(kind: &str, outcome: &str) -> Self {
+        SyncHistoryEntry {
+            timestamp: Utc::now().to_rfc3339(),
+            kind: kind.to_string(),
+            outcome: outcome.to_string(),
+            commit_sha: None,
+            files_downloaded: 0,
+            files_deleted: 0,
+            files_renamed: 0,
+            mass_changes: Vec::new(),
+        }
+    }
+}
+
+fn get_history_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+    fs::create_dir_all(&app_data_dir)
+        .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+    Ok(app_data_dir.join("sync-history.json"))
+}
+
+/// Append an entry to the sync history, dropping the oldest beyond the limit
+pub(crate) fn record_sync_history(app: &AppHandle, entry: SyncHistoryEntry) -> Result<(), String> {
+    let mut entries = get_sync_history(app.clone())?;
+    entries.push(entry);
+    if entries.len() > MAX_HISTORY_ENTRIES {
+        entries.drain(..entries.len() - MAX_HISTORY_ENTRIES);
+    }
+
+    let content = serde_json::to_string_pretty(&entries)
+        .map_err(|e| format!("Failed to serialize sync history: {}", e))?;
+    fs::write(get_history_path(app)?, content).map_err(|e| format!("Failed to write sync history: {}", e))
+}
+
+/// Past syncs, oldest first
+#[tauri::command]
+pub fn get_sync_history(app: AppHandle) -> Result<Vec<SyncHistoryEntry>, String> {
+    let path = get_history_path(&app)?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&path).map_err(|e| format!("Failed to read sync history: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse sync history: {}", e))
+}
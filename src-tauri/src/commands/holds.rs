@@ -0,0 +1,227 @@
+use super::serials::load_pack_targets;
+use super::state::{load_state, save_state, AppState};
+use super::sync::{fetch_github_tree_at, get_enabled_path, resolve_sync_commit, TargetAnalysis};
+use chrono::Utc;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::AppHandle;
+
+/// A path or glob that syncs leave alone, keeping the local file at the version it was held at
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeldPath {
+    /// Path relative to the serial folder, or a glob ("*" within a folder, "**" across folders)
+    /// A folder path holds everything inside it
+    pub pattern: String,
+    /// Serial folder the hold applies to (None for every folder)
+    #[serde(default)]
+    pub serial: Option<String>,
+    pub held_at: String,
+    /// Last synced commit when the hold was added, to find upstream changes since
+    #[serde(default)]
+    pub commit_sha: Option<String>,
+}
+
+/// A held file whose repository version changed since it was held
+#[derive(Debug, Clone, Serialize)]
+pub struct HeldChange {
+    pub serial: String,
+    pub path: String,
+    pub pattern: String,
+    /// Blob SHA when held (None if the file didn't exist upstream yet)
+    pub held_sha: Option<String>,
+    /// Current blob SHA (None if the file was removed upstream)
+    pub latest_sha: Option<String>,
+}
+
+/// Turn a hold pattern into a regex matching repo paths
+fn pattern_regex(pattern: &str) -> Result<Regex, String> {
+    let pattern = pattern.trim_matches('/');
+    let mut regex = String::from("^");
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                // "**/" also matches no folder at all
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    regex.push_str("(?:.*/)?");
+                } else {
+                    regex.push_str(".*");
+                }
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push_str("(?:/.*)?$");
+    Regex::new(&regex).map_err(|e| format!("Invalid hold pattern \"{}\": {}", pattern, e))
+}
+
+/// Holds that apply to one serial folder
+pub(crate) struct HoldList {
+    patterns: Vec<Regex>,
+}
+
+impl HoldList {
+    pub(crate) fn load(app: &AppHandle, folder: &str) -> Result<Self, String> {
+        let patterns = load_state(app.clone())?
+            .held_paths
+            .iter()
+            .filter(|hold| hold.serial.as_deref().is_none_or(|serial| serial == folder))
+            .map(|hold| pattern_regex(&hold.pattern))
+            .collect::<Result<_, _>>()?;
+        Ok(HoldList { patterns })
+    }
+
+    /// Whether a repo or local path (either copy of a dash-disabled file) is held
+    pub(crate) fn is_held(&self, path: &str) -> bool {
+        let enabled = get_enabled_path(path);
+        self.patterns
+            .iter()
+            .any(|re| re.is_match(path) || enabled.as_deref().is_some_and(|p| re.is_match(p)))
+    }
+
+    /// Remove the items on held paths, adding the paths to `held`
+    pub(crate) fn retain_unheld<T>(&self, items: &mut Vec<T>, path_of: impl Fn(&T) -> &str, held: &mut Vec<String>) {
+        if self.patterns.is_empty() {
+            return;
+        }
+        items.retain(|item| {
+            let path = path_of(item);
+            if self.is_held(path) {
+                held.push(path.to_string());
+                return false;
+            }
+            true
+        });
+    }
+
+    /// Take every change to a held file out of an analysis; returns the held paths
+    pub(crate) fn take_held(&self, analysis: &mut TargetAnalysis) -> Vec<String> {
+        let mut held = Vec::new();
+        self.retain_unheld(&mut analysis.files_to_add, |f| &f.path, &mut held);
+        self.retain_unheld(&mut analysis.files_to_replace, |f| &f.path, &mut held);
+        self.retain_unheld(&mut analysis.files_to_delete, |p| p, &mut held);
+        self.retain_unheld(&mut analysis.files_to_move, |m| &m.from, &mut held);
+        self.retain_unheld(&mut analysis.files_to_move, |m| &m.to, &mut held);
+        self.retain_unheld(&mut analysis.files_to_rescue, |m| &m.from, &mut held);
+        held.sort();
+        held.dedup();
+        held
+    }
+}
+
+/// Held paths and globs
+#[tauri::command]
+pub fn get_held_paths(app: AppHandle) -> Result<Vec<HeldPath>, String> {
+    Ok(load_state(app)?.held_paths)
+}
+
+/// Add a hold unless the same one exists; `commit_sha` is the version the held files are at
+pub(crate) fn add_hold(state: &mut AppState, pattern: &str, serial: Option<String>, commit_sha: Option<String>) -> Result<(), String> {
+    let pattern = pattern.trim().trim_matches('/').to_string();
+    if pattern.is_empty() {
+        return Ok(());
+    }
+    pattern_regex(&pattern)?;
+    if state.held_paths.iter().any(|hold| hold.pattern == pattern && hold.serial == serial) {
+        return Ok(());
+    }
+    state.held_paths.push(HeldPath {
+        pattern,
+        serial,
+        held_at: Utc::now().to_rfc3339(),
+        commit_sha,
+    });
+    Ok(())
+}
+
+/// Hold paths or globs so syncs and verification leave them alone
+/// `serial` limits the hold to one serial folder
+#[tauri::command]
+pub fn hold_paths(app: AppHandle, patterns: Vec<String>, serial: Option<String>) -> Result<Vec<HeldPath>, String> {
+    let mut state = load_state(app.clone())?;
+    let commit_sha = state.last_sync_commit.clone();
+    for pattern in patterns {
+        add_hold(&mut state, &pattern, serial.clone(), commit_sha.clone())?;
+    }
+    let held_paths = state.held_paths.clone();
+    save_state(app, state)?;
+    Ok(held_paths)
+}
+
+/// Release held paths so syncs update them again
+#[tauri::command]
+pub fn release_held_paths(app: AppHandle, patterns: Vec<String>, serial: Option<String>) -> Result<Vec<HeldPath>, String> {
+    let mut state = load_state(app.clone())?;
+    state
+        .held_paths
+        .retain(|hold| !(patterns.contains(&hold.pattern) && hold.serial == serial));
+    let held_paths = state.held_paths.clone();
+    save_state(app, state)?;
+    Ok(held_paths)
+}
+
+/// Held files whose repository version changed since they were held
+#[tauri::command]
+pub async fn get_held_upstream_changes(
+    app: AppHandle,
+    github_token: Option<String>,
+) -> Result<Vec<HeldChange>, String> {
+    let holds = load_state(app.clone())?.held_paths;
+    if holds.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let latest_commit = resolve_sync_commit(&app, &github_token).await?;
+    let mut changes = Vec::new();
+    for target in load_pack_targets(&app)? {
+        let target_holds: Vec<&HeldPath> = holds
+            .iter()
+            .filter(|hold| hold.serial.as_deref().is_none_or(|serial| serial == target.folder))
+            .collect();
+        if target_holds.is_empty() {
+            continue;
+        }
+
+        let latest_files = fetch_github_tree_at(&target.sparse_path, &latest_commit, &github_token).await?;
+        let mut held_trees: HashMap<String, HashMap<String, String>> = HashMap::new();
+
+        for hold in target_holds {
+            // Holds added before the first sync have no version to compare against
+            let Some(commit) = &hold.commit_sha else {
+                continue;
+            };
+            let re = pattern_regex(&hold.pattern)?;
+            if !held_trees.contains_key(commit) {
+                let files = fetch_github_tree_at(&target.sparse_path, commit, &github_token).await?;
+                held_trees.insert(commit.clone(), files);
+            }
+            let held_files = &held_trees[commit];
+
+            let mut paths: Vec<&String> = latest_files.keys().filter(|p| re.is_match(p)).collect();
+            paths.extend(held_files.keys().filter(|p| re.is_match(p) && !latest_files.contains_key(*p)));
+            paths.sort();
+
+            for path in paths {
+                let held_sha = held_files.get(path).cloned();
+                let latest_sha = latest_files.get(path).cloned();
+                if held_sha == latest_sha {
+                    continue;
+                }
+                changes.push(HeldChange {
+                    serial: target.folder.clone(),
+                    path: path.clone(),
+                    pattern: hold.pattern.clone(),
+                    held_sha,
+                    latest_sha,
+                });
+            }
+        }
+    }
+
+    Ok(changes)
+}
@@ -1,21 +1,37 @@
+use super::cleanup::resolve_temp_root;
+use super::filesystem::move_dir_across_devices;
+use super::install_error::InstallError;
+use super::modules::{load_module_filter, ModuleFilter};
+use super::serials::{load_pack_targets, refresh_mirror_folders, PackTarget};
+use super::state::load_state;
+use super::sync::{download_file_at, fetch_github_tree_at, load_sync_ref, resolve_sync_commit, DEFAULT_SYNC_REF};
 use crate::config::{REPO_URL, SLUS_FOLDER, SPARSE_PATH, TEMP_DIR_NAME};
 use regex::Regex;
-use serde::Serialize;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::ffi::OsString;
 use std::io::{BufReader, Read as IoRead};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 #[cfg(not(target_os = "windows"))]
 use std::process::{Command, Stdio};
 #[cfg(target_os = "windows")]
 use std::process::Command;
 use std::fs;
 use std::sync::{Arc, Mutex};
-use tauri::{Emitter, Window};
+use tauri::{Emitter, Manager, Window};
 
 // Track running process PIDs so we can kill them on app exit
 static RUNNING_PIDS: Mutex<Vec<u32>> = Mutex::new(Vec::new());
 
+// Interrupt flag for the in-process clone (it has no PID to kill)
+#[cfg(feature = "native-git")]
+static NATIVE_CLONE_INTERRUPT: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
 /// Kill all tracked processes (called on app exit)
 pub fn cleanup_processes() {
+    #[cfg(feature = "native-git")]
+    NATIVE_CLONE_INTERRUPT.store(true, std::sync::atomic::Ordering::Relaxed);
+
     if let Ok(pids) = RUNNING_PIDS.lock() {
         for pid in pids.iter() {
             #[cfg(target_os = "windows")]
@@ -43,14 +59,138 @@ pub struct ProgressPayload {
     pub percent: Option<u32>,
 }
 
-/// Get the path to git executable
-/// On Windows x64, use bundled MinGit if available
+/// Progress payload with typed transfer counters (emitted by the in-process backend)
+/// Shares the "install-progress" event and the stage/message/percent fields with ProgressPayload
+#[cfg(feature = "native-git")]
+#[derive(Clone, Serialize)]
+pub struct CloneProgressPayload {
+    pub stage: String,
+    pub message: String,
+    pub percent: Option<u32>,
+    pub objects_received: u64,
+    pub objects_total: Option<u64>,
+    pub bytes_received: u64,
+    pub bytes_per_second: u64,
+}
+
+/// Which implementation performs the clone during installation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InstallBackend {
+    /// Bundled MinGit or system git, driven through a PTY
+    #[default]
+    Git,
+    /// In-process partial clone using gix (only available with the `native-git` feature)
+    /// Fetches the trees first, then only the files of the sparse paths
+    Native,
+    /// File-by-file download through the GitHub API and raw URLs (no git at all)
+    Http,
+}
+
+/// Pick a backend when the frontend doesn't ask for one:
+/// a git that supports sparse checkout, then plain HTTP (the in-process clone is only used when picked)
+fn choose_install_backend(git: &Result<GitCapabilities, String>) -> InstallBackend {
+    match git {
+        Ok(caps) if caps.is_usable() => InstallBackend::Git,
+        _ => InstallBackend::Http,
+    }
+}
+
+/// Minimum git version for `clone --filter=blob:none --sparse` and `sparse-checkout set`
+const MIN_SPARSE_CHECKOUT_VERSION: (u32, u32, u32) = (2, 25, 0);
+
+/// First git version with dependable partial clone (`--filter=blob:none`) support
+const MIN_PARTIAL_CLONE_VERSION: (u32, u32, u32) = (2, 22, 0);
+
+/// First git version with `sparse-checkout set --no-cone` (needed to leave out optional modules)
+const MIN_SPARSE_PATTERNS_VERSION: (u32, u32, u32) = (2, 35, 0);
+
+/// Where a git executable came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GitSource {
+    /// MinGit shipped with the app (Windows x64)
+    #[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+    Bundled,
+    /// git found on the system PATH
+    System,
+}
+
+/// Probed git executable and what it can do
+#[derive(Debug, Clone, Serialize)]
+pub struct GitCapabilities {
+    pub path: String,
+    pub version: String,
+    pub source: GitSource,
+    pub supports_partial_clone: bool,
+    pub supports_sparse_checkout: bool,
+    pub supports_sparse_patterns: bool,
+}
+
+impl GitCapabilities {
+    /// Whether this git can run the sparse clone the installer needs
+    pub fn is_usable(&self) -> bool {
+        self.supports_partial_clone && self.supports_sparse_checkout
+    }
+
+    /// Explanation shown when is_usable() is false
+    fn unusable_reason(&self) -> String {
+        let (major, minor, patch) = MIN_SPARSE_CHECKOUT_VERSION;
+        format!(
+            "Git {} ({}) is too old for sparse checkout. Git {}.{}.{} or newer is required.",
+            self.version, self.path, major, minor, patch
+        )
+    }
+}
+
+/// Parse "git version 2.39.3 (Apple Git-146)" or "git version 2.45.1.windows.1" into (2, 39, 3)
+fn parse_git_version(output: &str) -> Option<(u32, u32, u32)> {
+    let version = output.trim().strip_prefix("git version ")?;
+    let mut parts = version
+        .split(|c: char| !c.is_ascii_digit())
+        .take(3)
+        .map(|p| p.parse::<u32>().ok());
+
+    let major = parts.next()??;
+    let minor = parts.next().flatten().unwrap_or(0);
+    let patch = parts.next().flatten().unwrap_or(0);
+    Some((major, minor, patch))
+}
+
+/// Run `git --version` and derive the capability record, or None if it doesn't run
+fn probe_git(path: &str, source: GitSource) -> Option<GitCapabilities> {
+    let output = Command::new(path).arg("--version").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let text = String::from_utf8_lossy(&output.stdout).to_string();
+    let parsed = parse_git_version(&text);
+    let version = match parsed {
+        Some((major, minor, patch)) => format!("{}.{}.{}", major, minor, patch),
+        None => text.trim().to_string(),
+    };
+
+    Some(GitCapabilities {
+        path: path.to_string(),
+        version,
+        source,
+        supports_partial_clone: parsed.is_some_and(|v| v >= MIN_PARTIAL_CLONE_VERSION),
+        supports_sparse_checkout: parsed.is_some_and(|v| v >= MIN_SPARSE_CHECKOUT_VERSION),
+        supports_sparse_patterns: parsed.is_some_and(|v| v >= MIN_SPARSE_PATTERNS_VERSION),
+    })
+}
+
+/// Find and probe the git executable
+/// On Windows x64, prefer bundled MinGit if available
 /// On Windows ARM, require system git
 /// On macOS, use system git
-fn get_git_path() -> Result<String, String> {
+/// A usable git wins over an outdated one; an outdated one is still returned so callers can explain why
+fn get_git_path() -> Result<GitCapabilities, String> {
     #[cfg(target_os = "windows")]
     {
         let is_arm = cfg!(target_arch = "aarch64");
+        let mut found: Vec<GitCapabilities> = Vec::new();
 
         // On x64, check for bundled MinGit first
         if !is_arm {
@@ -68,7 +208,10 @@ fn get_git_path() -> Result<String, String> {
 
                     for mingit_path in &paths_to_try {
                         if mingit_path.exists() {
-                            return Ok(mingit_path.to_string_lossy().to_string());
+                            if let Some(caps) = probe_git(&mingit_path.to_string_lossy(), GitSource::Bundled) {
+                                found.push(caps);
+                                break;
+                            }
                         }
                     }
                 }
@@ -76,8 +219,15 @@ fn get_git_path() -> Result<String, String> {
         }
 
         // Fall back to system git
-        if Command::new("git").arg("--version").output().is_ok() {
-            return Ok("git".to_string());
+        if let Some(caps) = probe_git("git", GitSource::System) {
+            found.push(caps);
+        }
+
+        if let Some(index) = found.iter().position(|caps| caps.is_usable()) {
+            return Ok(found.swap_remove(index));
+        }
+        if let Some(caps) = found.into_iter().next() {
+            return Ok(caps);
         }
 
         // Build error message based on architecture
@@ -101,19 +251,19 @@ fn get_git_path() -> Result<String, String> {
     #[cfg(not(target_os = "windows"))]
     {
         // On macOS/Linux, check for system git
-        if Command::new("git").arg("--version").output().is_ok() {
-            return Ok("git".to_string());
+        if let Some(caps) = probe_git("git", GitSource::System) {
+            return Ok(caps);
         }
 
         Err("Git not found. Please install Xcode Command Line Tools by running: xcode-select --install".to_string())
     }
 }
 
-/// Check if git is available
+/// Check if a git that supports sparse checkout is available
 #[tauri::command]
 pub fn check_git_installed() -> Result<bool, String> {
     match get_git_path() {
-        Ok(_) => Ok(true),
+        Ok(caps) => Ok(caps.is_usable()),
         Err(_) => Ok(false),
     }
 }
@@ -122,11 +272,158 @@ pub fn check_git_installed() -> Result<bool, String> {
 #[tauri::command]
 pub fn get_git_error() -> String {
     match get_git_path() {
-        Ok(_) => String::new(),
+        Ok(caps) if caps.is_usable() => String::new(),
+        Ok(caps) => caps.unusable_reason(),
         Err(e) => e,
     }
 }
 
+/// Get the probed git capability record
+#[tauri::command]
+pub fn get_git_capabilities() -> Result<GitCapabilities, String> {
+    get_git_path()
+}
+
+/// Null device used to point git at an empty global config and hooks directory
+#[cfg(target_os = "windows")]
+const NULL_DEVICE: &str = "NUL";
+#[cfg(not(target_os = "windows"))]
+const NULL_DEVICE: &str = "/dev/null";
+
+/// Environment variables that isolate git from the user's machine setup
+/// - No system or global config (credential helpers, proxies, hooks, autocrlf...)
+/// - Never prompt for credentials (the repo is public, a prompt means something is wrong)
+/// - English output, since detect_git_stage parses git's progress lines
+fn hermetic_env_overrides() -> Vec<(&'static str, &'static str)> {
+    vec![
+        ("GIT_CONFIG_NOSYSTEM", "1"),
+        ("GIT_CONFIG_GLOBAL", NULL_DEVICE),
+        ("GIT_TERMINAL_PROMPT", "0"),
+        ("GIT_ASKPASS", ""),
+        ("SSH_ASKPASS", ""),
+        ("GCM_INTERACTIVE", "never"),
+        ("LC_ALL", "C"),
+    ]
+}
+
+/// Full environment for a git child process: the inherited environment minus any
+/// GIT_* / GCM_* variables (which can redirect git to other configs, repos or askpass
+/// helpers), plus the hermetic overrides. Proxy variables like HTTPS_PROXY are kept.
+fn hermetic_git_env() -> Vec<(OsString, OsString)> {
+    let mut env: Vec<(OsString, OsString)> = std::env::vars_os()
+        .filter(|(key, _)| {
+            let key = key.to_string_lossy().to_ascii_uppercase();
+            !key.starts_with("GIT_") && !key.starts_with("GCM_")
+        })
+        .collect();
+
+    for (key, value) in hermetic_env_overrides() {
+        env.retain(|(k, _)| !k.to_string_lossy().eq_ignore_ascii_case(key));
+        env.push((key.into(), value.into()));
+    }
+
+    env
+}
+
+/// The only config values git sees besides its built-in defaults
+/// Passed as top-level `-c` options so they apply to this invocation only
+fn hermetic_config(working_dir: &Path) -> Vec<(&'static str, String)> {
+    vec![
+        ("core.autocrlf", "false".to_string()),
+        ("core.longpaths", "true".to_string()),
+        ("core.hooksPath", NULL_DEVICE.to_string()),
+        ("core.askPass", String::new()),
+        // An empty value resets the credential helper list
+        ("credential.helper", String::new()),
+        // The temp clone is ours even if the drive reports a different owner (FAT, network shares)
+        ("safe.directory", working_dir.to_string_lossy().replace('\\', "/")),
+    ]
+}
+
+/// Build the `-c key=value` arguments for hermetic_config
+fn hermetic_config_args(working_dir: &Path) -> Vec<String> {
+    hermetic_config(working_dir)
+        .into_iter()
+        .flat_map(|(key, value)| ["-c".to_string(), format!("{}={}", key, value)])
+        .collect()
+}
+
+/// A single effective git config entry
+#[derive(Debug, Clone, Serialize)]
+pub struct GitConfigEntry {
+    pub scope: String,
+    pub origin: String,
+    pub key: String,
+    pub value: String,
+}
+
+/// Effective git setup as the installer runs it (for troubleshooting)
+#[derive(Debug, Clone, Serialize)]
+pub struct GitDiagnostics {
+    pub git_path: String,
+    pub environment: Vec<(String, String)>,
+    pub config: Vec<GitConfigEntry>,
+}
+
+/// Show the environment and effective config the installer's git invocations use
+#[tauri::command]
+pub fn get_git_diagnostics() -> Result<GitDiagnostics, String> {
+    let git_path = get_git_path()?.path;
+    let working_dir = std::env::temp_dir();
+
+    let mut args = hermetic_config_args(&working_dir);
+    args.extend(["config", "--list", "--show-scope", "--show-origin"].map(String::from));
+
+    let output = Command::new(&git_path)
+        .args(&args)
+        .current_dir(&working_dir)
+        .env_clear()
+        .envs(hermetic_git_env())
+        .output()
+        .map_err(|e| format!("Failed to run git config: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "git config failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    // Each line is "<scope>\t<origin>\t<key>=<value>"
+    let config = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.splitn(3, '\t');
+            let scope = parts.next()?.to_string();
+            let origin = parts.next()?.to_string();
+            let (key, value) = parts.next()?.split_once('=').unwrap_or((line, ""));
+            Some(GitConfigEntry {
+                scope,
+                origin,
+                key: key.to_string(),
+                value: value.to_string(),
+            })
+        })
+        .collect();
+
+    let environment = hermetic_env_overrides()
+        .into_iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect();
+
+    Ok(GitDiagnostics {
+        git_path,
+        environment,
+        config,
+    })
+}
+
+/// Check if this build includes the in-process git backend
+#[tauri::command]
+pub fn check_native_git_available() -> bool {
+    cfg!(feature = "native-git")
+}
+
 /// Strip ANSI escape codes from a string
 fn strip_ansi_codes(s: &str) -> String {
     let ansi_re = Regex::new(r"\x1b\[[0-9;]*[a-zA-Z]").unwrap();
@@ -278,12 +575,16 @@ fn run_git_with_pty(
     // Use 'caffeinate' to prevent sleep, 'script' to create a PTY for git
     // caffeinate -d: prevent display sleep (also prevents screensaver)
     // script -q /dev/null: create PTY without saving typescript
+    let config_args = hermetic_config_args(working_dir);
     let mut cmd_args: Vec<&str> = vec!["-d", "script", "-q", "/dev/null", git_path];
+    cmd_args.extend(config_args.iter().map(String::as_str));
     cmd_args.extend(args);
 
     let mut cmd = Command::new("caffeinate")
         .args(&cmd_args)
         .current_dir(working_dir)
+        .env_clear()
+        .envs(hermetic_git_env())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .spawn()
@@ -322,7 +623,7 @@ fn run_git_with_pty(
     default_stage: &str,
     detect_stages: bool,
 ) -> Result<(bool, String), String> {
-    use conpty::spawn;
+    use conpty::Process;
     use std::io::Read as _;
     use windows::Win32::System::Power::{SetThreadExecutionState, ES_CONTINUOUS, ES_SYSTEM_REQUIRED, ES_DISPLAY_REQUIRED};
 
@@ -338,9 +639,15 @@ fn run_git_with_pty(
     // For other commands, use -C flag to set working directory
     let is_clone = args.first() == Some(&"clone");
 
-    let full_args: Vec<String> = if is_clone {
+    // Hermetic config goes first so it applies to the whole invocation
+    let mut full_args: Vec<String> = hermetic_config_args(working_dir)
+        .into_iter()
+        .map(|arg| if arg.contains(' ') { format!("\"{}\"", arg) } else { arg })
+        .collect();
+
+    if is_clone {
         // For clone, replace "." with the full path
-        args.iter().map(|arg| {
+        full_args.extend(args.iter().map(|arg| {
             if *arg == "." {
                 format!("\"{}\"", working_dir_str)
             } else if arg.contains(' ') {
@@ -348,34 +655,37 @@ fn run_git_with_pty(
             } else {
                 arg.to_string()
             }
-        }).collect()
+        }));
     } else {
         // For other commands, use -C flag
-        let mut v: Vec<String> = vec![
-            "-C".to_string(),
-            format!("\"{}\"", working_dir_str),
-        ];
+        full_args.push("-C".to_string());
+        full_args.push(format!("\"{}\"", working_dir_str));
         for arg in args {
             if arg.contains(' ') {
-                v.push(format!("\"{}\"", arg));
+                full_args.push(format!("\"{}\"", arg));
             } else {
-                v.push(arg.to_string());
+                full_args.push(arg.to_string());
             }
         }
-        v
-    };
+    }
 
-    // Build command line - use cmd.exe /c wrapper when path has spaces
-    // ConPTY doesn't handle quoted executable paths correctly
+    // Everything after "cmd.exe /c"; a git path with spaces is quoted and the whole line quoted again,
+    // since ConPTY doesn't handle quoted executable paths correctly
     let command_line = if git_path.contains(' ') {
-        format!("cmd.exe /c \"\"{}\" {}\"", git_path, full_args.join(" "))
+        format!("\"\"{}\" {}\"", git_path, full_args.join(" "))
     } else {
         format!("{} {}", git_path, full_args.join(" "))
     };
 
     // Spawn process using ConPTY (Windows Pseudo Console)
     // This makes git think it's connected to a real terminal
-    let mut proc = spawn(&command_line)
+    // ConPTY joins the program and its arguments with spaces, unquoted, so this runs
+    // `cmd.exe /c <command_line>` with a single cmd wrapper. Only explicitly set variables
+    // reach the child, which is why hermetic_git_env includes the inherited ones.
+    let mut command = Command::new("cmd.exe");
+    command.arg("/c").arg(&command_line);
+    command.envs(hermetic_git_env());
+    let mut proc = Process::spawn(command)
         .map_err(|e| {
             unsafe { SetThreadExecutionState(ES_CONTINUOUS); }
             format!("Failed to spawn process with ConPTY: {}", e)
@@ -494,33 +804,18 @@ fn run_git_with_pty(
     Ok((exit_code == 0, error_context))
 }
 
-/// Run the git sparse checkout installation
-#[tauri::command]
-pub async fn start_installation(textures_dir: String, window: Window) -> Result<(), String> {
-    let git_path = get_git_path()?;
-    let textures_path = PathBuf::from(&textures_dir);
-    let temp_path = textures_path.join(TEMP_DIR_NAME);
-    let final_path = textures_path.join(SLUS_FOLDER);
-
-    // Emit initial progress
-    let _ = window.emit(
-        "install-progress",
-        ProgressPayload {
-            stage: "preparing".to_string(),
-            message: "Preparing installation...".to_string(),
-            percent: Some(0),
-        },
-    );
-
-    // Clean up any existing temp directory
-    if temp_path.exists() {
-        fs::remove_dir_all(&temp_path)
-            .map_err(|e| format!("Failed to clean temp directory: {}", e))?;
-    }
-
+/// Run the sparse clone with git (stages 1 and 2 of the installation)
+/// Leaves the checked out textures at temp_path/<sparse path> for every target
+fn clone_with_git(
+    git_path: &str,
+    temp_path: &PathBuf,
+    targets: &[PackTarget],
+    modules: &ModuleFilter,
+    window: &Window,
+) -> Result<String, InstallError> {
     // Create temp directory (only on macOS - on Windows, git clone will create it)
     #[cfg(not(target_os = "windows"))]
-    fs::create_dir_all(&temp_path)
+    fs::create_dir_all(temp_path)
         .map_err(|e| format!("Failed to create temp directory: {}", e))?;
 
     // Stage 1: Clone with sparse checkout (this is quick - just metadata)
@@ -534,7 +829,7 @@ pub async fn start_installation(textures_dir: String, window: Window) -> Result<
     );
 
     let (clone_success, clone_output) = run_git_with_pty(
-        &git_path,
+        git_path,
         &[
             "clone",
             "--depth=1",
@@ -544,20 +839,15 @@ pub async fn start_installation(textures_dir: String, window: Window) -> Result<
             REPO_URL,
             ".",
         ],
-        &temp_path,
-        &window,
+        temp_path,
+        window,
         "cloning",
         false, // Don't detect stages - keep showing "Initializing repository..."
     )?;
 
     if !clone_success {
-        let _ = fs::remove_dir_all(&temp_path);
-        let error_msg = if clone_output.is_empty() {
-            "Git clone has failed. Please check your internet connection.".to_string()
-        } else {
-            format!("Git clone has failed:\n{}", clone_output)
-        };
-        return Err(error_msg);
+        let _ = fs::remove_dir_all(temp_path);
+        return Err(InstallError::classify("Git clone has failed.", clone_output));
     }
 
     // Stage 2: Set sparse checkout path - THIS IS THE MAIN DOWNLOAD
@@ -570,48 +860,730 @@ pub async fn start_installation(textures_dir: String, window: Window) -> Result<
         },
     );
 
+    // Cone mode can't leave out subfolders, so unselected modules need pattern mode
+    let mut sparse_args = vec!["sparse-checkout".to_string(), "set".to_string()];
+    if modules.is_empty() {
+        sparse_args.extend(targets.iter().map(|t| t.sparse_path.clone()));
+    } else {
+        sparse_args.push("--no-cone".to_string());
+        sparse_args.extend(targets.iter().map(|t| format!("/{}/", t.sparse_path)));
+        for path in modules.excluded_paths() {
+            sparse_args.push(format!("!/{}/{}/", SPARSE_PATH, path));
+        }
+    }
+    let sparse_args: Vec<&str> = sparse_args.iter().map(String::as_str).collect();
+
     let (checkout_success, checkout_output) = run_git_with_pty(
-        &git_path,
-        &["sparse-checkout", "set", SPARSE_PATH],
-        &temp_path,
-        &window,
+        git_path,
+        &sparse_args,
+        temp_path,
+        window,
         "downloading",
         true, // Detect stages - show compressing/downloading/extracting
     )?;
 
     if !checkout_success {
-        let _ = fs::remove_dir_all(&temp_path);
-        let error_msg = if checkout_output.is_empty() {
-            "Sparse checkout failed.".to_string()
-        } else {
-            format!("Sparse checkout failed:\n{}", checkout_output)
+        let _ = fs::remove_dir_all(temp_path);
+        return Err(InstallError::classify("Sparse checkout failed.", checkout_output));
+    }
+
+    match git_head_sha(git_path, temp_path) {
+        Ok(sha) => Ok(sha),
+        Err(e) => {
+            let _ = fs::remove_dir_all(temp_path);
+            Err(e.into())
+        }
+    }
+}
+
+/// Commit SHA the clone at repo_path checked out
+fn git_head_sha(git_path: &str, repo_path: &Path) -> Result<String, String> {
+    let mut args = hermetic_config_args(repo_path);
+    args.extend(["rev-parse", "HEAD"].map(String::from));
+
+    let output = Command::new(git_path)
+        .args(&args)
+        .current_dir(repo_path)
+        .env_clear()
+        .envs(hermetic_git_env())
+        .output()
+        .map_err(|e| format!("Failed to run git rev-parse: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "git rev-parse failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Format a byte count the way git prints it in progress lines
+#[cfg(feature = "native-git")]
+fn format_bytes(bytes: u64) -> String {
+    const KIB: f64 = 1024.0;
+    const MIB: f64 = KIB * 1024.0;
+    const GIB: f64 = MIB * 1024.0;
+
+    let b = bytes as f64;
+    if b >= GIB {
+        format!("{:.2} GiB", b / GIB)
+    } else if b >= MIB {
+        format!("{:.2} MiB", b / MIB)
+    } else if b >= KIB {
+        format!("{:.2} KiB", b / KIB)
+    } else {
+        format!("{} bytes", bytes)
+    }
+}
+
+/// Poll the gix progress tree and emit typed progress events until `done` is set
+/// gix reports pack bytes under the "BWRB" id and indexed objects under "IWIO"
+#[cfg(feature = "native-git")]
+fn spawn_gix_progress_reporter(
+    root: Arc<gix::progress::tree::Root>,
+    done: Arc<std::sync::atomic::AtomicBool>,
+    window: Window,
+) -> std::thread::JoinHandle<()> {
+    use std::sync::atomic::Ordering;
+    use std::time::{Duration, Instant};
+
+    std::thread::spawn(move || {
+        let mut snapshot = Vec::new();
+        let mut last_bytes: u64 = 0;
+        let mut last_tick = Instant::now();
+        let mut bytes_per_second: u64 = 0;
+
+        while !done.load(Ordering::Relaxed) {
+            std::thread::sleep(Duration::from_millis(250));
+            root.sorted_snapshot(&mut snapshot);
+
+            let mut bytes_received: Option<u64> = None;
+            let mut objects: Option<(u64, Option<u64>)> = None;
+
+            for (_, task) in &snapshot {
+                let Some(value) = &task.progress else { continue };
+                let step = value.step.load(Ordering::Relaxed) as u64;
+                match &task.id {
+                    b"BWRB" => bytes_received = Some(step),
+                    b"IWIO" => objects = Some((step, value.done_at.map(|d| d as u64))),
+                    _ => {}
+                }
+            }
+
+            // Nothing is transferring yet (still negotiating with the remote)
+            let Some(bytes_received) = bytes_received else { continue };
+
+            let elapsed = last_tick.elapsed().as_secs_f64();
+            if elapsed >= 1.0 {
+                bytes_per_second = ((bytes_received.saturating_sub(last_bytes)) as f64 / elapsed) as u64;
+                last_bytes = bytes_received;
+                last_tick = Instant::now();
+            }
+
+            let (objects_received, objects_total) = objects.unwrap_or((0, None));
+            let percent = objects_total
+                .filter(|total| *total > 0)
+                .map(|total| ((objects_received * 100) / total).min(100) as u32);
+
+            let message = match objects_total {
+                Some(total) => format!(
+                    "Receiving objects: {}% ({}/{}), {} | {}/s",
+                    percent.unwrap_or(0),
+                    objects_received,
+                    total,
+                    format_bytes(bytes_received),
+                    format_bytes(bytes_per_second)
+                ),
+                None => format!(
+                    "Receiving objects: {}, {} | {}/s",
+                    objects_received,
+                    format_bytes(bytes_received),
+                    format_bytes(bytes_per_second)
+                ),
+            };
+
+            let _ = window.emit(
+                "install-progress",
+                CloneProgressPayload {
+                    stage: "downloading".to_string(),
+                    message,
+                    percent,
+                    objects_received,
+                    objects_total,
+                    bytes_received,
+                    bytes_per_second,
+                },
+            );
+        }
+    })
+}
+
+/// A file of the sparse paths, written once its blob is fetched
+#[cfg(feature = "native-git")]
+struct GixFile {
+    id: gix::ObjectId,
+    dest: PathBuf,
+}
+
+/// Collect every file of a tree below dest_dir, recursing into subtrees (only trees are local yet)
+#[cfg(feature = "native-git")]
+fn collect_gix_tree(
+    repo: &gix::Repository,
+    tree: &gix::Tree<'_>,
+    dest_dir: &Path,
+    relative_dir: &str,
+    modules: &ModuleFilter,
+    files: &mut Vec<GixFile>,
+) -> Result<(), String> {
+    use gix::bstr::ByteSlice;
+    use gix::objs::tree::EntryKind;
+
+    for entry in tree.iter() {
+        let entry = entry.map_err(|e| format!("Failed to read tree entry: {}", e))?;
+        let name = entry
+            .filename()
+            .to_str()
+            .map_err(|_| format!("Non UTF-8 file name in repository: {}", entry.filename()))?;
+        // A crafted tree could name an entry ".." or "C:"
+        let name = super::path_guard::validate_relative_path(name).map_err(String::from)?;
+        let dest = dest_dir.join(&name);
+        let relative = format!("{}{}", relative_dir, name);
+
+        match entry.kind() {
+            EntryKind::Tree => {
+                let relative = format!("{}/", relative);
+                if modules.excludes(&relative) {
+                    continue;
+                }
+                let subtree = repo
+                    .find_tree(entry.object_id())
+                    .map_err(|e| format!("Failed to read tree {}: {}", name, e))?;
+                collect_gix_tree(repo, &subtree, &dest, &relative, modules, files)?;
+            }
+            // Symlinks are written as plain files holding the link target, like git does where it can't make links
+            EntryKind::Blob | EntryKind::BlobExecutable | EntryKind::Link => {
+                files.push(GixFile { id: entry.object_id(), dest });
+            }
+            // Submodules are left as empty folders, like git does
+            EntryKind::Commit => {
+                fs::create_dir_all(&dest).map_err(|e| format!("Failed to create directory: {}", e))?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Fetch negotiation that asks for fixed objects and sends no haves (the repository starts out empty)
+#[cfg(feature = "native-git")]
+struct WantObjects<'a> {
+    wants: &'a [gix::ObjectId],
+    /// `--filter` spec, e.g. "blob:none"
+    filter: Option<&'a str>,
+    filter_unsupported: bool,
+}
+
+#[cfg(feature = "native-git")]
+impl gix::protocol::fetch::Negotiate for WantObjects<'_> {
+    fn mark_complete_and_common_ref(&mut self) -> gix::error::Result<gix::protocol::fetch::negotiate::Action> {
+        Ok(gix::protocol::fetch::negotiate::Action::MustNegotiate {
+            remote_ref_target_known: Vec::new(),
+        })
+    }
+
+    fn add_wants(&mut self, arguments: &mut gix::protocol::fetch::Arguments, _: &[bool]) -> bool {
+        if let Some(filter) = self.filter {
+            if !arguments.can_use_filter() {
+                self.filter_unsupported = true;
+                return false;
+            }
+            arguments.filter(filter);
+        }
+        for id in self.wants {
+            arguments.want(id);
+        }
+        !self.wants.is_empty()
+    }
+
+    fn one_round(
+        &mut self,
+        _: &mut gix::protocol::fetch::negotiate::one_round::State,
+        _: &mut gix::protocol::fetch::Arguments,
+        _: Option<&gix::protocol::fetch::Response>,
+    ) -> gix::error::Result<(gix::protocol::fetch::negotiate::Round, bool)> {
+        let round = gix::protocol::fetch::negotiate::Round {
+            haves_sent: 0,
+            in_vain: 0,
+            haves_to_send: 0,
+            previous_response_had_at_least_one_in_common: false,
+        };
+        Ok((round, true))
+    }
+}
+
+/// Connection to the pack repository over git's protocol
+#[cfg(feature = "native-git")]
+struct GixRemote {
+    transport: Box<dyn gix::protocol::transport::client::blocking_io::Transport + Send>,
+    handshake: gix::protocol::Handshake,
+}
+
+#[cfg(feature = "native-git")]
+impl GixRemote {
+    fn connect(url: &str) -> Result<Self, String> {
+        use gix::protocol::transport::client::blocking_io::connect;
+
+        let mut transport = connect::connect(url, connect::Options::default())
+            .map_err(|e| format!("Failed to connect to the repository: {}", e))?;
+        let handshake = gix::protocol::handshake(
+            &mut transport,
+            gix::protocol::transport::Service::UploadPack,
+            // The repository is public, so there is nothing to authenticate
+            |_| Ok(None),
+            Vec::new(),
+            &mut gix::progress::Discard,
+        )
+        .map_err(|e| format!("Failed to connect to the repository: {}", e))?;
+        Ok(GixRemote { transport, handshake })
+    }
+
+    fn user_agent() -> gix::protocol::command::Feature {
+        ("agent", Some(gix::protocol::agent(gix::env::agent())))
+    }
+
+    /// Commit the remote HEAD points at
+    fn head_commit(&mut self) -> Result<gix::ObjectId, String> {
+        use gix::protocol::handshake::Ref;
+
+        let refs = match self.handshake.refs.take() {
+            Some(refs) => refs,
+            None => {
+                let mut prefixes = gix::protocol::ls_refs::RefPrefixes::new();
+                prefixes.extend(["HEAD".into()]);
+                gix::protocol::LsRefsCommand::new(Some(prefixes), &self.handshake.capabilities, Self::user_agent())
+                    .invoke_blocking(&mut self.transport, &mut gix::progress::Discard, false)
+                    .map_err(|e| format!("Failed to list the repository's branches: {}", e))?
+            }
         };
-        return Err(error_msg);
+        refs.into_iter()
+            .find_map(|r| match r {
+                Ref::Symbolic { full_ref_name, object, .. } | Ref::Direct { full_ref_name, object }
+                    if full_ref_name == "HEAD" =>
+                {
+                    Some(object)
+                }
+                _ => None,
+            })
+            .ok_or_else(|| "The repository has no HEAD commit".to_string())
     }
 
-    // Stage 3: Move folder to final location
+    /// Fetch `wants` into the repository's object database as one pack
+    fn fetch(
+        &mut self,
+        repo: &gix::Repository,
+        wants: &[gix::ObjectId],
+        filter: Option<&str>,
+        shallow: &gix::protocol::fetch::Shallow,
+        progress: impl gix::NestedProgress + 'static,
+    ) -> Result<(), String> {
+        use gix::error::ResultExt;
+        use std::sync::atomic::Ordering;
+
+        let mut negotiate = WantObjects { wants, filter, filter_unsupported: false };
+        let pack_dir = repo.objects.store_ref().path().join("pack");
+        let shallow_file = repo.shallow_file().map_err(|e| format!("Failed to read repository: {}", e))?;
+        let context = gix::protocol::fetch::Context {
+            handshake: &mut self.handshake,
+            transport: &mut self.transport,
+            user_agent: Self::user_agent(),
+            trace_packetlines: false,
+        };
+        let options = gix::protocol::fetch::Options {
+            shallow_file,
+            shallow,
+            tags: gix::protocol::fetch::Tags::None,
+            reject_shallow_remote: false,
+        };
+
+        let outcome = gix::protocol::fetch(
+            &mut negotiate,
+            |reader, progress, should_interrupt| {
+                gix::odb::pack::Bundle::write_to_directory(
+                    reader,
+                    Some(&pack_dir),
+                    progress,
+                    should_interrupt,
+                    Some(repo.objects.clone()),
+                    repo.object_hash(),
+                    Default::default(),
+                )
+                .or_raise(|| gix::error::message("Failed to write the received pack"))?;
+                Ok(true)
+            },
+            progress,
+            &NATIVE_CLONE_INTERRUPT,
+            context,
+            options,
+        );
+
+        if NATIVE_CLONE_INTERRUPT.load(Ordering::Relaxed) {
+            return Err("Installation was interrupted".to_string());
+        }
+        if negotiate.filter_unsupported {
+            return Err("The repository server doesn't support partial clones".to_string());
+        }
+        match outcome {
+            Ok(Some(_)) => Ok(()),
+            Ok(None) => Err("The repository server sent nothing".to_string()),
+            Err(e) => Err(format!("Clone failed: {}", e)),
+        }
+    }
+
+    fn close(mut self) {
+        if self.handshake.server_protocol_version == gix::protocol::transport::Protocol::V2 {
+            let _ = gix::protocol::indicate_end_of_interaction(&mut self.transport, false);
+        }
+    }
+}
+
+/// Run the clone in-process with gix (stages 1 and 2 of the installation)
+/// Leaves the checked out textures at temp_path/<sparse path> for every target, same as clone_with_git
+///
+/// Like the git backend's `--filter=blob:none --depth 1` clone, this first fetches only the HEAD commit
+/// and its trees, then fetches the blobs of the sparse paths (minus deselected modules) in one pack.
+#[cfg(feature = "native-git")]
+fn clone_with_gix(
+    temp_path: &Path,
+    targets: &[PackTarget],
+    modules: &ModuleFilter,
+    window: &Window,
+) -> Result<String, String> {
+    use std::num::NonZeroU32;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    NATIVE_CLONE_INTERRUPT.store(false, Ordering::Relaxed);
+
     let _ = window.emit(
         "install-progress",
         ProgressPayload {
-            stage: "moving".to_string(),
-            message: format!("Moving {} to final location...", SLUS_FOLDER),
+            stage: "cloning".to_string(),
+            message: "Initializing repository...".to_string(),
             percent: Some(0),
         },
     );
 
-    let source_path = temp_path.join("textures").join(SLUS_FOLDER);
+    let root = gix::progress::tree::Root::new();
+    let done = Arc::new(AtomicBool::new(false));
+    let reporter = spawn_gix_progress_reporter(root.clone(), done.clone(), window.clone());
+
+    let clone = || -> Result<(u32, String), String> {
+        let git_dir = temp_path.join(".git");
+        fs::create_dir_all(&git_dir).map_err(|e| format!("Failed to create directory: {}", e))?;
+        let repo = gix::init_bare(&git_dir).map_err(|e| format!("Failed to initialize repository: {}", e))?;
+        let mut remote = GixRemote::connect(REPO_URL)?;
+        let commit_id = remote.head_commit()?;
+
+        // The commit and its trees, without any file contents
+        let depth = NonZeroU32::new(1).expect("non-zero depth");
+        remote.fetch(
+            &repo,
+            &[commit_id],
+            Some("blob:none"),
+            &gix::protocol::fetch::Shallow::DepthAtRemote(depth),
+            root.add_child("trees"),
+        )?;
+
+        let repo = gix::open(repo.git_dir()).map_err(|e| format!("Failed to open repository: {}", e))?;
+        let tree = repo
+            .find_commit(commit_id)
+            .and_then(|commit| commit.tree())
+            .map_err(|e| format!("Failed to read HEAD tree: {}", e))?;
+
+        let no_modules = ModuleFilter::default();
+        let mut files = Vec::new();
+        for target in targets {
+            let sparse_path = target.sparse_path.as_str();
+            let entry = tree
+                .lookup_entry_by_path(sparse_path)
+                .map_err(|e| format!("Failed to look up {}: {}", sparse_path, e))?
+                .ok_or_else(|| format!("Expected folder {} not found in repository", sparse_path))?;
+            let subtree = repo
+                .find_tree(entry.object_id())
+                .map_err(|e| format!("Failed to read {}: {}", sparse_path, e))?;
+
+            // Optional modules only exist in the primary pack
+            let target_modules = if target.is_primary() { modules } else { &no_modules };
+            fs::create_dir_all(temp_path.join(sparse_path))
+                .map_err(|e| format!("Failed to create directory: {}", e))?;
+            collect_gix_tree(&repo, &subtree, &temp_path.join(sparse_path), "", target_modules, &mut files)?;
+        }
 
-    if !source_path.exists() {
-        let _ = fs::remove_dir_all(&temp_path);
-        return Err(format!(
-            "Expected folder {} not found in repository",
-            SPARSE_PATH
-        ));
+        // Then only the blobs of those files
+        let mut blob_ids: Vec<gix::ObjectId> = files.iter().map(|f| f.id).collect();
+        blob_ids.sort();
+        blob_ids.dedup();
+        if !blob_ids.is_empty() {
+            remote.fetch(
+                &repo,
+                &blob_ids,
+                None,
+                &gix::protocol::fetch::Shallow::NoChange,
+                root.add_child("files"),
+            )?;
+        }
+        remote.close();
+
+        let repo = gix::open(repo.git_dir()).map_err(|e| format!("Failed to open repository: {}", e))?;
+        write_gix_files(&repo, &files, window)?;
+        Ok((files.len() as u32, commit_id.to_string()))
+    };
+    let result = clone();
+
+    done.store(true, Ordering::Relaxed);
+    let _ = reporter.join();
+
+    match result {
+        Ok((written, commit_sha)) => {
+            let _ = window.emit(
+                "install-progress",
+                ProgressPayload {
+                    stage: "extracting".to_string(),
+                    message: format!("Updating files: 100% ({}/{}), done.", written, written),
+                    percent: Some(100),
+                },
+            );
+            Ok(commit_sha)
+        }
+        Err(e) => {
+            let _ = fs::remove_dir_all(temp_path);
+            Err(e)
+        }
     }
+}
 
-    // Move the folder
-    fs::rename(&source_path, &final_path)
-        .map_err(|e| format!("Failed to move folder to final location: {}", e))?;
+/// Write the fetched blobs of `files` to their destinations
+#[cfg(feature = "native-git")]
+fn write_gix_files(repo: &gix::Repository, files: &[GixFile], window: &Window) -> Result<(), String> {
+    use std::sync::atomic::Ordering;
+
+    for (i, file) in files.iter().enumerate() {
+        if NATIVE_CLONE_INTERRUPT.load(Ordering::Relaxed) {
+            return Err("Installation was interrupted".to_string());
+        }
+
+        let blob = repo
+            .find_blob(file.id)
+            .map_err(|e| format!("Failed to read {}: {}", file.dest.display(), e))?;
+        if let Some(parent) = file.dest.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
+        }
+        fs::write(&file.dest, &blob.data)
+            .map_err(|e| format!("Failed to write {}: {}", file.dest.display(), e))?;
+
+        let written = i + 1;
+        if written.is_multiple_of(500) {
+            let _ = window.emit(
+                "install-progress",
+                ProgressPayload {
+                    stage: "extracting".to_string(),
+                    message: format!("Updating files: {}% ({}/{})", written * 100 / files.len(), written, files.len()),
+                    percent: Some((written * 100 / files.len()) as u32),
+                },
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(not(feature = "native-git"))]
+fn clone_with_gix(
+    _temp_path: &Path,
+    _targets: &[PackTarget],
+    _modules: &ModuleFilter,
+    _window: &Window,
+) -> Result<String, String> {
+    Err("This build does not include the in-process git backend".to_string())
+}
+
+/// Download every target's sparse path file by file (stages 1 and 2 of the installation, without git)
+/// Leaves the textures at temp_path/<sparse path>, same as clone_with_git
+async fn download_with_http(
+    temp_path: &Path,
+    targets: &[PackTarget],
+    token: &Option<String>,
+    modules: &ModuleFilter,
+    window: &Window,
+) -> Result<String, String> {
+    let _ = window.emit(
+        "install-progress",
+        ProgressPayload {
+            stage: "cloning".to_string(),
+            message: "Fetching repository file list...".to_string(),
+            percent: Some(0),
+        },
+    );
+
+    // (sparse path, file path) for every file of every target, all at the same commit
+    let commit_sha = resolve_sync_commit(window.app_handle(), token).await?;
+    let mut files: Vec<(&str, String)> = Vec::new();
+    for target in targets {
+        let remote_files = fetch_github_tree_at(&target.sparse_path, &commit_sha, token).await?;
+        files.extend(
+            remote_files
+                .into_keys()
+                .filter(|p| !target.is_primary() || !modules.excludes(p))
+                .map(|p| (target.sparse_path.as_str(), p)),
+        );
+    }
+    files.sort();
+
+    let client = Client::new();
+    let total = files.len();
+
+    for (i, (sparse_path, path)) in files.iter().enumerate() {
+        let dest = temp_path.join(sparse_path).join(path);
+        if let Err(e) = download_file_at(&client, &commit_sha, sparse_path, path, &dest, token).await {
+            let _ = fs::remove_dir_all(temp_path);
+            return Err(e);
+        }
+
+        let _ = window.emit(
+            "install-progress",
+            ProgressPayload {
+                stage: "downloading".to_string(),
+                message: format!("Downloading files: ({}/{}) {}", i + 1, total, path),
+                percent: Some((((i + 1) * 100) / total.max(1)) as u32),
+            },
+        );
+    }
+
+    Ok(commit_sha)
+}
+
+/// Run the git sparse checkout installation
+/// `backend` is the user's pick (None chooses git or HTTP)
+/// Returns the commit SHA that was installed, to record as the last sync commit
+#[tauri::command]
+pub async fn start_installation(
+    textures_dir: String,
+    backend: Option<InstallBackend>,
+    window: Window,
+) -> Result<String, InstallError> {
+    let modules = load_module_filter(window.app_handle()).await?;
+    // Probed once, each probe runs `git --version`
+    let git = get_git_path();
+    // Clones only fetch main, so another sync ref is installed over HTTP
+    let backend = if load_sync_ref(window.app_handle()).await? != DEFAULT_SYNC_REF {
+        InstallBackend::Http
+    } else {
+        backend.unwrap_or_else(|| choose_install_backend(&git))
+    };
+    let mut git_path = String::new();
+    if backend == InstallBackend::Git {
+        let caps = git?;
+        if !caps.is_usable() {
+            return Err(caps.unusable_reason().into());
+        }
+        if !modules.is_empty() && !caps.supports_sparse_patterns {
+            let (major, minor, patch) = MIN_SPARSE_PATTERNS_VERSION;
+            return Err(format!(
+                "Git {} cannot leave out optional modules. Git {}.{}.{} or newer is required, or select all modules.",
+                caps.version, major, minor, patch
+            )
+            .into());
+        }
+        git_path = caps.path;
+    }
+    let targets = load_pack_targets(window.app_handle())?;
+    let textures_path = PathBuf::from(&textures_dir);
+    let temp_path = resolve_temp_root(window.app_handle(), &textures_path).join(TEMP_DIR_NAME);
+    let final_path = textures_path.join(SLUS_FOLDER);
+
+    // Emit initial progress
+    let _ = window.emit(
+        "install-progress",
+        ProgressPayload {
+            stage: "preparing".to_string(),
+            message: "Preparing installation...".to_string(),
+            percent: Some(0),
+        },
+    );
+
+    // Clean up any existing temp directory
+    if temp_path.exists() {
+        fs::remove_dir_all(&temp_path)
+            .map_err(|e| format!("Failed to clean temp directory: {}", e))?;
+    }
+
+    // Stages 1 and 2: clone and download the sparse path, at the commit that gets recorded
+    let commit_sha = match backend {
+        InstallBackend::Git => clone_with_git(&git_path, &temp_path, &targets, &modules, &window)?,
+        InstallBackend::Native => clone_with_gix(&temp_path, &targets, &modules, &window)?,
+        InstallBackend::Http => {
+            let token = load_state(window.app_handle().clone())?.github_token;
+            download_with_http(&temp_path, &targets, &token, &modules, &window).await?
+        }
+    };
+
+    // Stage 3: Move each folder to its final location
+    for target in &targets {
+        let folder = target.folder.as_str();
+        let _ = window.emit(
+            "install-progress",
+            ProgressPayload {
+                stage: "moving".to_string(),
+                message: format!("Moving {} to final location...", folder),
+                percent: Some(0),
+            },
+        );
+
+        let source_path = temp_path.join(&target.sparse_path);
+
+        if !source_path.exists() {
+            let _ = fs::remove_dir_all(&temp_path);
+            return Err(format!(
+                "Expected folder {} not found in repository",
+                target.sparse_path
+            )
+            .into());
+        }
+
+        // Move the folder (copies with progress if the final location is on another drive)
+        let mut last_percent = None;
+        move_dir_across_devices(&source_path, &textures_path.join(folder), |copied, total| {
+            let percent = (copied * 100).checked_div(total).unwrap_or(100) as u32;
+            if last_percent == Some(percent) {
+                return;
+            }
+            last_percent = Some(percent);
+
+            let _ = window.emit(
+                "install-progress",
+                ProgressPayload {
+                    stage: "moving".to_string(),
+                    message: format!(
+                        "Copying {} to final location (different drive): {}%",
+                        folder, percent
+                    ),
+                    percent: Some(percent),
+                },
+            );
+        })?;
+    }
+
+    // Fill mirror serial folders from the freshly installed SLUS folder
+    refresh_mirror_folders(window.app_handle(), &textures_path, |message| {
+        let _ = window.emit(
+            "install-progress",
+            ProgressPayload {
+                stage: "moving".to_string(),
+                message,
+                percent: None,
+            },
+        );
+    })?;
 
     // Stage 4: Cleanup
     let _ = window.emit(
@@ -639,5 +1611,5 @@ pub async fn start_installation(textures_dir: String, window: Window) -> Result<
         },
     );
 
-    Ok(())
+    Ok(commit_sha)
 }
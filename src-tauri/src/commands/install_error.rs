@@ -0,0 +1,312 @@
+use regex::Regex;
+use serde::Serialize;
+use std::sync::LazyLock;
+
+/// Known installation failure kinds
+/// Serialized as stable codes (e.g. "DISK_FULL") that the frontend can branch on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum InstallErrorKind {
+    /// DNS failure, no connectivity, or the connection dropped mid-transfer
+    Network,
+    /// TLS handshake or certificate problem, usually a proxy or HTTPS-scanning antivirus
+    TlsProxy,
+    /// GitHub answered with a 5xx status
+    ServerError,
+    /// The drive ran out of space
+    DiskFull,
+    /// A path exceeded the Windows MAX_PATH limit
+    PathTooLong,
+    /// Writing to the textures folder was not allowed
+    PermissionDenied,
+    /// A file was held open by another program (typically antivirus or a cloud sync client)
+    FileLocked,
+    /// The texture repository doesn't exist or isn't public
+    RepoNotFound,
+    /// Anything we don't recognize
+    Unknown,
+}
+
+/// Installation error returned to the frontend
+#[derive(Debug, Clone, Serialize)]
+pub struct InstallError {
+    pub code: InstallErrorKind,
+    /// Short description of what went wrong
+    pub message: String,
+    /// What the user can do about it
+    pub hint: String,
+    /// Raw output (last lines of git output, OS error text) for "show details"
+    pub details: String,
+}
+
+/// Output patterns for each kind, checked in order (first match wins)
+/// More specific kinds come first: a 404 also mentions "unable to access",
+/// and a locked file on Windows can also report "Permission denied"
+/// OS errors are matched by their text, not their `os error N` code: the same code means
+/// different things on Windows and unix (5 is "Access is denied" on Windows but EIO on unix)
+/// A 404 for a single file (HTTP backend) isn't a missing repository, so only git's and the
+/// GitHub API's 404s count as RepoNotFound
+fn failure_patterns() -> Vec<(InstallErrorKind, &'static str)> {
+    vec![
+        (
+            InstallErrorKind::RepoNotFound,
+            r"(?i)repository not found|returned error: 404|GitHub API error: 404|does not appear to be a git repository",
+        ),
+        (
+            InstallErrorKind::ServerError,
+            r"(?i)returned error: 5\d\d|HTTP 5\d\d|RPC failed; HTTP 5\d\d|bad gateway|service unavailable|internal server error",
+        ),
+        (
+            InstallErrorKind::TlsProxy,
+            r"(?i)ssl certificate|certificate verif|local issuer certificate|self.signed certificate|schannel|gnutls_handshake|ssl_connect|tls handshake|invalid peer certificate|proxy|returned error: 407|CONNECT tunnel failed",
+        ),
+        (
+            InstallErrorKind::Network,
+            r"(?i)could not resolve host|failed to connect|couldn't connect|connection timed out|timed out|connection refused|connection reset|network is unreachable|early eof|remote end hung up|RPC failed|error sending request|dns error",
+        ),
+        (
+            InstallErrorKind::DiskFull,
+            r"(?i)no space left|not enough space|disk full|ENOSPC",
+        ),
+        (
+            InstallErrorKind::PathTooLong,
+            r"(?i)filename too long|file name too long|path too long|filename or extension is too long|ENAMETOOLONG",
+        ),
+        (
+            InstallErrorKind::FileLocked,
+            r"(?i)being used by another process|device or resource busy|unlink of file .* failed|deletion of directory .* failed|locked a portion of the file",
+        ),
+        (
+            InstallErrorKind::PermissionDenied,
+            r"(?i)permission denied|access is denied|operation not permitted|read-only file system",
+        ),
+    ]
+}
+
+/// The failure patterns, compiled once (a pattern that doesn't compile is a bug, not a miss)
+static FAILURE_REGEXES: LazyLock<Vec<(InstallErrorKind, Regex)>> = LazyLock::new(|| {
+    failure_patterns()
+        .into_iter()
+        .map(|(kind, pattern)| (kind, Regex::new(pattern).expect("invalid failure pattern")))
+        .collect()
+});
+
+/// Classify raw output into a failure kind
+pub fn classify_failure(output: &str) -> InstallErrorKind {
+    FAILURE_REGEXES
+        .iter()
+        .find(|(_, re)| re.is_match(output))
+        .map(|(kind, _)| *kind)
+        .unwrap_or(InstallErrorKind::Unknown)
+}
+
+impl InstallErrorKind {
+    /// User-facing message and remediation hint
+    fn message_and_hint(self) -> (&'static str, &'static str) {
+        match self {
+            InstallErrorKind::Network => (
+                "Could not reach GitHub, or the connection dropped during the download.",
+                "Check your internet connection and try again. If you use a VPN or firewall, make sure github.com is allowed.",
+            ),
+            InstallErrorKind::TlsProxy => (
+                "A secure connection to GitHub could not be established.",
+                "This is usually caused by a proxy, VPN or antivirus that inspects HTTPS traffic, or by a wrong system clock. Check your date and time settings, or turn off HTTPS scanning in your antivirus and try again.",
+            ),
+            InstallErrorKind::ServerError => (
+                "GitHub returned a server error.",
+                "This is a problem on GitHub's side and is usually temporary. Wait a few minutes and try again (see https://www.githubstatus.com).",
+            ),
+            InstallErrorKind::DiskFull => (
+                "The drive ran out of space.",
+                "Free up space on the drive that holds your textures folder (the full pack needs several GB) and try again.",
+            ),
+            InstallErrorKind::PathTooLong => (
+                "A file path was too long for Windows.",
+                "Move your PCSX2 textures folder to a shorter path (for example C:\\PCSX2\\textures) and try again.",
+            ),
+            InstallErrorKind::PermissionDenied => (
+                "The installer was not allowed to write to the textures folder.",
+                "Make sure the folder is not read-only and not inside a protected location such as Program Files, or choose a different folder.",
+            ),
+            InstallErrorKind::FileLocked => (
+                "A file was locked by another program.",
+                "Antivirus software or a cloud sync client (OneDrive, Dropbox) may be scanning the new files. Add the textures folder to your antivirus exclusions or pause syncing, then try again.",
+            ),
+            InstallErrorKind::RepoNotFound => (
+                "The texture repository could not be found.",
+                "The repository may have moved or been made private. Check for a newer version of this app.",
+            ),
+            InstallErrorKind::Unknown => (
+                "Installation failed.",
+                "Check your internet connection and try again. If it keeps failing, share the details below with the mod team.",
+            ),
+        }
+    }
+}
+
+impl InstallError {
+    /// Build an error from a summary (used when the failure is unrecognized) and raw output
+    pub fn classify(summary: &str, details: String) -> Self {
+        let code = classify_failure(&format!("{}\n{}", summary, details));
+        let (message, hint) = code.message_and_hint();
+        let message = if code == InstallErrorKind::Unknown {
+            summary.to_string()
+        } else {
+            message.to_string()
+        };
+
+        InstallError {
+            code,
+            message,
+            hint: hint.to_string(),
+            details,
+        }
+    }
+}
+
+/// Plain string errors (fs and network helpers) are classified from their text
+impl From<String> for InstallError {
+    fn from(error: String) -> Self {
+        InstallError::classify(&error, error.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_kind(kind: InstallErrorKind, outputs: &[&str]) {
+        for output in outputs {
+            assert_eq!(classify_failure(output), kind, "{}", output);
+        }
+    }
+
+    #[test]
+    fn all_patterns_compile() {
+        assert_eq!(FAILURE_REGEXES.len(), failure_patterns().len());
+    }
+
+    #[test]
+    fn classifies_repo_not_found() {
+        assert_kind(
+            InstallErrorKind::RepoNotFound,
+            &[
+                "remote: Repository not found.\nfatal: repository 'https://github.com/x/y.git/' not found",
+                "fatal: unable to access 'https://github.com/x/y.git/': The requested URL returned error: 404",
+                "GitHub API error: 404 Not Found - {\"message\":\"Not Found\"}",
+            ],
+        );
+    }
+
+    #[test]
+    fn classifies_server_errors() {
+        assert_kind(
+            InstallErrorKind::ServerError,
+            &[
+                "fatal: unable to access 'https://github.com/x/y.git/': The requested URL returned error: 503",
+                "error: RPC failed; HTTP 502 curl 22 The requested URL returned error: 502",
+                "GitHub API error: 500 Internal Server Error",
+            ],
+        );
+    }
+
+    #[test]
+    fn classifies_tls_and_proxy() {
+        assert_kind(
+            InstallErrorKind::TlsProxy,
+            &[
+                "fatal: unable to access 'https://github.com/': SSL certificate problem: unable to get local issuer certificate",
+                "schannel: next InitializeSecurityContext failed: SEC_E_UNTRUSTED_ROOT",
+                "Received HTTP code 407 from proxy after CONNECT",
+            ],
+        );
+    }
+
+    #[test]
+    fn classifies_network() {
+        assert_kind(
+            InstallErrorKind::Network,
+            &[
+                "fatal: unable to access 'https://github.com/': Could not resolve host: github.com",
+                "fetch-pack: unexpected disconnect while reading sideband packet\nfatal: early EOF",
+                "Failed to download file: error sending request for url",
+                "Failed to connect to github.com port 443: Connection timed out",
+            ],
+        );
+    }
+
+    #[test]
+    fn classifies_disk_full() {
+        assert_kind(
+            InstallErrorKind::DiskFull,
+            &[
+                "fatal: write error: No space left on device",
+                "Failed to write file: There is not enough space on the disk. (os error 112)",
+            ],
+        );
+    }
+
+    #[test]
+    fn classifies_path_too_long() {
+        assert_kind(
+            InstallErrorKind::PathTooLong,
+            &[
+                "error: unable to create file textures/a.png: Filename too long",
+                "Failed to move folder: The filename or extension is too long. (os error 206)",
+            ],
+        );
+    }
+
+    #[test]
+    fn classifies_file_locked_before_permission_denied() {
+        assert_kind(
+            InstallErrorKind::FileLocked,
+            &[
+                "The process cannot access the file because it is being used by another process. (os error 32)",
+                "The process cannot access the file because another process has locked a portion of the file. (os error 33)",
+                "Unlink of file 'textures/a.png' failed. Should I try again? Permission denied",
+            ],
+        );
+    }
+
+    #[test]
+    fn classifies_permission_denied() {
+        assert_kind(
+            InstallErrorKind::PermissionDenied,
+            &[
+                "Failed to create temp directory: Access is denied. (os error 5)",
+                "fatal: could not create work tree dir 'x': Permission denied",
+                "Read-only file system (os error 30)",
+            ],
+        );
+    }
+
+    #[test]
+    fn unrecognized_output_is_unknown() {
+        assert_kind(InstallErrorKind::Unknown, &["Expected folder textures/SLUS-21770 not found in repository", ""]);
+    }
+
+    #[test]
+    fn a_single_missing_file_is_not_a_missing_repository() {
+        assert_kind(InstallErrorKind::Unknown, &["Failed to download a.png: HTTP 404 Not Found"]);
+    }
+
+    #[test]
+    fn unix_error_codes_are_not_read_as_windows_ones() {
+        assert_kind(
+            InstallErrorKind::Unknown,
+            &["Failed to read a.png: Input/output error (os error 5)", "Failed to write a.png: Broken pipe (os error 32)"],
+        );
+    }
+
+    #[test]
+    fn classify_keeps_the_summary_only_for_unknown_failures() {
+        let error = InstallError::classify("Git clone has failed.", "Could not resolve host: github.com".to_string());
+        assert_eq!(error.code, InstallErrorKind::Network);
+        assert_ne!(error.message, "Git clone has failed.");
+
+        let error = InstallError::classify("Git clone has failed.", "something odd".to_string());
+        assert_eq!(error.code, InstallErrorKind::Unknown);
+        assert_eq!(error.message, "Git clone has failed.");
+    }
+}
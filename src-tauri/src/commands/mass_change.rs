@@ -0,0 +1,128 @@
+use super::app_info::fetch_installer_data;
+use super::history::{record_sync_history, MassChangeTrip, SyncHistoryEntry};
+use super::modules::ModuleFilter;
+use super::state::load_state;
+use super::sync::{count_local_files, get_disabled_path, SyncFile, SyncMove};
+use std::path::Path;
+use tauri::AppHandle;
+
+/// Share of a folder's tracked files a sync may delete or replace without confirmation
+pub const DEFAULT_MASS_CHANGE_LIMIT_PERCENT: f64 = 25.0;
+
+/// Files a sync may rescue from one folder without confirmation
+/// (many usually means the app doesn't know the versions that were installed, not that they were edited)
+pub const MAX_UNCONFIRMED_RESCUES: usize = 100;
+
+/// Stops syncs that would delete or replace a large part of a folder
+/// (e.g. after the pack was accidentally emptied or restructured upstream)
+pub(crate) struct MassChangeGuard {
+    app: AppHandle,
+    kind: String,
+    limit_percent: f64,
+    /// The user confirmed the changes after an earlier attempt was stopped
+    confirmed: bool,
+    trips: Vec<MassChangeTrip>,
+}
+
+impl MassChangeGuard {
+    /// Use the user's limit, else the one from installer-data.json, else the default
+    pub(crate) async fn load(app: &AppHandle, kind: &str, confirmed: bool) -> Result<Self, String> {
+        let limit_percent = match load_state(app.clone())?.mass_change_limit_percent {
+            Some(percent) => percent,
+            None => fetch_installer_data()
+                .await
+                .data
+                .and_then(|data| data.mass_change_limit_percent)
+                .unwrap_or(DEFAULT_MASS_CHANGE_LIMIT_PERCENT),
+        };
+
+        Ok(MassChangeGuard {
+            app: app.clone(),
+            kind: kind.to_string(),
+            limit_percent,
+            confirmed,
+            trips: Vec::new(),
+        })
+    }
+
+    /// Check the changes planned for one folder before anything is applied
+    /// Rescued files leave the folder too, so they count like deletions
+    /// Fails with a "MASS_CHANGE:" error (and records it in the sync history) unless confirmed
+    pub(crate) fn check(
+        &mut self,
+        slus_path: &Path,
+        files_to_download: &[SyncFile],
+        files_to_delete: &[String],
+        files_to_rescue: &[SyncMove],
+    ) -> Result<(), String> {
+        let Ok(tracked) = count_local_files(slus_path, &ModuleFilter::default()) else {
+            return Ok(());
+        };
+        if tracked == 0 {
+            return Ok(());
+        }
+
+        let replaced = files_to_download
+            .iter()
+            .filter(|file| {
+                let local_path = if file.to_disabled { get_disabled_path(&file.path) } else { file.path.clone() };
+                slus_path.join(local_path).exists()
+            })
+            .count();
+        let rescued = files_to_rescue.len();
+        let affected = replaced + files_to_delete.len() + rescued;
+        let percent = affected as f64 * 100.0 / tracked as f64;
+        if percent <= self.limit_percent && rescued <= MAX_UNCONFIRMED_RESCUES {
+            return Ok(());
+        }
+
+        let folder = slus_path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        let trip = MassChangeTrip {
+            folder: folder.clone(),
+            affected: affected as u32,
+            rescued: rescued as u32,
+            tracked: tracked as u32,
+            limit_percent: self.limit_percent,
+            confirmed: self.confirmed,
+        };
+
+        if self.confirmed {
+            self.trips.push(trip);
+            return Ok(());
+        }
+
+        let mut entry = SyncHistoryEntry::new(&self.kind, "blocked");
+        entry.mass_changes.push(trip);
+        let _ = record_sync_history(&self.app, entry);
+
+        if percent <= self.limit_percent {
+            return Err(format!(
+                "MASS_CHANGE: This sync would move {} files in {} that don't match any version it installed to \
+                 user-customs/rescued, more than the {} allowed without asking. Confirm to continue.",
+                rescued, folder, MAX_UNCONFIRMED_RESCUES
+            ));
+        }
+        Err(format!(
+            "MASS_CHANGE: This sync would delete or replace {} of {} files ({:.0}%) in {}, more than the {}% safety limit. \
+             If the texture pack really changed this much, confirm to continue.",
+            affected, tracked, percent, folder, self.limit_percent
+        ))
+    }
+
+    /// Record a finished sync in the history, with the limits the user confirmed past
+    pub(crate) fn record_completed(
+        self,
+        commit_sha: Option<&str>,
+        files_downloaded: u32,
+        files_deleted: u32,
+        files_renamed: u32,
+    ) {
+        let mut entry = SyncHistoryEntry::new(&self.kind, "completed");
+        entry.commit_sha = commit_sha.map(str::to_string);
+        entry.files_downloaded = files_downloaded;
+        entry.files_deleted = files_deleted;
+        entry.files_renamed = files_renamed;
+        entry.mass_changes = self.trips;
+        let _ = record_sync_history(&self.app, entry);
+    }
+}
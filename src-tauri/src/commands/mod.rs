@@ -1,11 +1,43 @@
 pub mod app_info;
+pub mod channels;
+pub mod cleanup;
+pub mod conflicts;
+pub mod failures;
 pub mod filesystem;
+pub mod history;
+pub mod holds;
 pub mod install;
+pub mod install_error;
+pub mod mass_change;
+pub mod modules;
+pub mod path_guard;
+pub mod preview;
+pub mod rescue;
+pub mod serials;
 pub mod state;
 pub mod sync;
+pub mod sync_plan;
+#[cfg(test)]
+pub(crate) mod test_support;
+pub mod texture_history;
+pub mod transaction;
+pub mod undo;
 
 pub use app_info::*;
+pub use channels::*;
+pub use cleanup::*;
+pub use conflicts::*;
+pub use failures::*;
 pub use filesystem::*;
+pub use history::*;
+pub use holds::*;
 pub use install::*;
+pub use modules::*;
+pub use preview::*;
+pub use serials::*;
 pub use state::*;
 pub use sync::*;
+pub use sync_plan::*;
+pub use texture_history::*;
+pub use transaction::*;
+pub use undo::*;
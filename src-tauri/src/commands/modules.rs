@@ -0,0 +1,274 @@
+use super::app_info::{fetch_installer_data, ContentModule};
+use super::failures::record_failed_fixes;
+use super::holds::HoldList;
+use super::mass_change::MassChangeGuard;
+use super::rescue::{load_known_blobs, plan_rescues};
+use super::state::{load_state, save_state, AppState};
+use super::sync::{
+    apply_target_changes, cleanup_empty_directories, fetch_github_tree, find_local_file, get_disabled_path,
+    FailedFile, SyncFile, SyncProgressPayload, TargetAnalysis,
+};
+use super::serials::{refresh_mirror_folders, PackTarget};
+use super::undo::SyncUndo;
+use crate::config::SLUS_FOLDER;
+use serde::Serialize;
+use std::path::PathBuf;
+use tauri::{AppHandle, Emitter, Manager, Window};
+
+/// Folders of optional modules the user has not selected
+/// Paths are relative to the SLUS folder and end with '/'
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ModuleFilter {
+    excluded: Vec<String>,
+}
+
+impl ModuleFilter {
+    pub(crate) fn new(modules: &[ContentModule], selected: &[String]) -> Self {
+        let excluded = modules
+            .iter()
+            .filter(|m| !selected.contains(&m.id))
+            .map(module_prefix)
+            .collect();
+        ModuleFilter { excluded }
+    }
+
+    /// Check if a path (relative to the SLUS folder) belongs to an unselected module
+    pub(crate) fn excludes(&self, relative_path: &str) -> bool {
+        self.excluded.iter().any(|prefix| relative_path.starts_with(prefix.as_str()))
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.excluded.is_empty()
+    }
+
+    /// Excluded folders without the trailing '/'
+    pub(crate) fn excluded_paths(&self) -> impl Iterator<Item = &str> {
+        self.excluded.iter().map(|p| p.trim_end_matches('/'))
+    }
+}
+
+/// Module path normalized to "folder/sub/"
+fn module_prefix(module: &ContentModule) -> String {
+    format!("{}/", module.path.replace('\\', "/").trim_matches('/'))
+}
+
+/// IDs of the selected modules, falling back to the modules enabled by default
+fn selected_module_ids(state: &AppState, modules: &[ContentModule]) -> Vec<String> {
+    match &state.selected_modules {
+        Some(ids) => ids.clone(),
+        None => modules
+            .iter()
+            .filter(|m| m.default_enabled)
+            .map(|m| m.id.clone())
+            .collect(),
+    }
+}
+
+/// Fetch the optional module declarations from installer-data.json
+async fn fetch_content_modules() -> Result<Vec<ContentModule>, String> {
+    let result = fetch_installer_data().await;
+    match result.data {
+        Some(data) => Ok(data.modules),
+        None => Err(result
+            .error
+            .unwrap_or_else(|| "Failed to fetch installer data".to_string())),
+    }
+}
+
+/// Module declarations, falling back to the last fetched ones when installer-data.json can't be reached
+/// (none if it never could, which excludes nothing)
+async fn load_content_modules(app: &AppHandle) -> Result<Vec<ContentModule>, String> {
+    let mut state = load_state(app.clone())?;
+    match fetch_content_modules().await {
+        Ok(modules) => {
+            if state.content_modules != modules {
+                state.content_modules = modules.clone();
+                save_state(app.clone(), state)?;
+            }
+            Ok(modules)
+        }
+        Err(_) => Ok(state.content_modules),
+    }
+}
+
+/// Build the filter for the user's current module selection
+/// Works offline with the last known modules, so installs and syncs don't depend on installer-data.json
+pub(crate) async fn load_module_filter(app: &AppHandle) -> Result<ModuleFilter, String> {
+    let modules = load_content_modules(app).await?;
+    if modules.is_empty() {
+        return Ok(ModuleFilter::default());
+    }
+
+    let state = load_state(app.clone())?;
+    Ok(ModuleFilter::new(&modules, &selected_module_ids(&state, &modules)))
+}
+
+/// Result of changing the module selection
+#[derive(Debug, Clone, Serialize)]
+pub struct ModuleSelectionResult {
+    pub files_downloaded: u32,
+    pub files_deleted: u32,
+    /// User files of deselected modules moved to user-customs/rescued/ instead of deleted
+    pub rescued: Vec<String>,
+    /// Files left alone because the user holds them
+    pub held: Vec<String>,
+    /// Changes that failed and are kept for `retry_failed_files`
+    pub failed: Vec<FailedFile>,
+}
+
+/// Get the IDs of the selected optional modules
+#[tauri::command]
+pub async fn get_selected_modules(app: AppHandle) -> Result<Vec<String>, String> {
+    let modules = load_content_modules(&app).await?;
+    let state = load_state(app)?;
+    Ok(selected_module_ids(&state, &modules))
+}
+
+/// Change the module selection of an installed pack
+/// Downloads the files of newly selected modules and deletes the files of deselected ones
+/// through the sync transaction (undo, holds, rescues and the mass-change guard);
+/// everything else in the SLUS folder is left alone
+/// `allow_mass_changes` confirms changes above the mass-change limit
+#[tauri::command]
+pub async fn apply_module_selection(
+    textures_dir: String,
+    module_ids: Vec<String>,
+    github_token: Option<String>,
+    allow_mass_changes: Option<bool>,
+    window: Window,
+) -> Result<ModuleSelectionResult, String> {
+    let app = window.app_handle().clone();
+    let textures_path = PathBuf::from(&textures_dir);
+    let target = PackTarget::primary();
+    let slus_path = textures_path.join(&target.folder);
+    if !slus_path.exists() {
+        return Err(format!("{} folder not found", SLUS_FOLDER));
+    }
+
+    let modules = fetch_content_modules().await?;
+    let previous = selected_module_ids(&load_state(app.clone())?, &modules);
+
+    let added: Vec<String> = modules
+        .iter()
+        .filter(|m| module_ids.contains(&m.id) && !previous.contains(&m.id))
+        .map(module_prefix)
+        .collect();
+    let removed: Vec<String> = modules
+        .iter()
+        .filter(|m| !module_ids.contains(&m.id) && previous.contains(&m.id))
+        .map(module_prefix)
+        .collect();
+
+    let mut result = ModuleSelectionResult {
+        files_downloaded: 0,
+        files_deleted: 0,
+        rescued: Vec::new(),
+        held: Vec::new(),
+        failed: Vec::new(),
+    };
+
+    if !added.is_empty() || !removed.is_empty() {
+        let _ = window.emit("sync-progress", SyncProgressPayload {
+            stage: "fetching".to_string(),
+            message: "Fetching repository file list...".to_string(),
+            current: None,
+            total: None,
+        });
+
+        let (remote_files, commit_sha) = fetch_github_tree(&app, &target.sparse_path, &github_token).await?;
+        let in_any = |path: &str, prefixes: &[String]| prefixes.iter().any(|p| path.starts_with(p.as_str()));
+
+        // Repo files of deselected modules, enabled or disabled copies
+        let mut files_to_delete: Vec<String> = remote_files
+            .keys()
+            .filter(|p| in_any(p, &removed))
+            .flat_map(|p| [p.clone(), get_disabled_path(p)])
+            .filter(|local_path| slus_path.join(local_path).is_file())
+            .collect();
+        files_to_delete.sort();
+
+        // Files of newly selected modules that aren't there yet
+        let mut files_to_add: Vec<SyncFile> = remote_files
+            .iter()
+            .filter(|(p, _)| in_any(p, &added) && !find_local_file(&slus_path, p).0)
+            .map(|(p, sha)| SyncFile { path: p.clone(), to_disabled: false, sha: Some(sha.clone()) })
+            .collect();
+        files_to_add.sort_by(|a, b| a.path.cmp(&b.path));
+
+        let known = load_known_blobs(&app, &target, &remote_files, &commit_sha, &github_token).await;
+        let files_to_rescue = plan_rescues(&slus_path, &known, &[], &mut files_to_delete);
+        let mut analysis = TargetAnalysis {
+            serial: target.folder.clone(),
+            files_to_add,
+            files_to_replace: Vec::new(),
+            files_to_delete,
+            files_to_move: Vec::new(),
+            files_to_rescue,
+            conflicts: Vec::new(),
+            held: Vec::new(),
+        };
+        let held = HoldList::load(&app, &target.folder)?.take_held(&mut analysis);
+
+        let mut guard = MassChangeGuard::load(&app, "modules", allow_mass_changes.unwrap_or(false)).await?;
+        guard.check(&slus_path, &analysis.files_to_add, &analysis.files_to_delete, &analysis.files_to_rescue)?;
+
+        let mut undo = SyncUndo::start(&app, &textures_path, "modules")?;
+        let (downloaded, deleted, _, failed) = apply_target_changes(
+            &slus_path,
+            &target.sparse_path,
+            &commit_sha,
+            &analysis.files_to_add,
+            &analysis.files_to_delete,
+            &[],
+            &analysis.files_to_rescue,
+            &github_token,
+            &mut undo,
+            &window,
+        )
+        .await?;
+        undo.finish(None)?;
+        guard.record_completed(None, downloaded, deleted, 0);
+        record_failed_fixes(&app, &commit_sha, &failed)?;
+
+        cleanup_empty_directories(&slus_path, &window);
+        refresh_mirror_folders(&app, &textures_path, |message| {
+            let _ = window.emit("sync-progress", SyncProgressPayload {
+                stage: "syncing".to_string(),
+                message,
+                current: None,
+                total: None,
+            });
+        })?;
+
+        result = ModuleSelectionResult {
+            files_downloaded: downloaded,
+            files_deleted: deleted,
+            rescued: analysis
+                .files_to_rescue
+                .iter()
+                .map(|rescue| format!("{}/{}", target.folder, rescue.to))
+                .collect(),
+            held: held.iter().map(|path| format!("{}/{}", target.folder, path)).collect(),
+            failed,
+        };
+    }
+
+    // Only saved once the changes are committed, so a failed update keeps the previous selection
+    let mut state = load_state(app.clone())?;
+    state.selected_modules = Some(module_ids);
+    save_state(app, state)?;
+
+    let _ = window.emit("sync-progress", SyncProgressPayload {
+        stage: "complete".to_string(),
+        message: format!(
+            "Modules updated! Downloaded: {}, Deleted: {}, Failed: {}",
+            result.files_downloaded,
+            result.files_deleted,
+            result.failed.len()
+        ),
+        current: None,
+        total: None,
+    });
+
+    Ok(result)
+}
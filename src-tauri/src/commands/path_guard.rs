@@ -0,0 +1,315 @@
+use serde::Serialize;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// Folder inside a serial folder that syncs never write to or delete from (except rescues)
+const PROTECTED_DIR: &str = "user-customs";
+/// The only part of the protected folder rescues may move files into
+const RESCUE_PREFIX: &str = "user-customs/rescued/";
+
+/// Windows device names, which open the device instead of a file (with any extension)
+const RESERVED_NAMES: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL", "CONIN$", "CONOUT$", "COM0", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6",
+    "COM7", "COM8", "COM9", "LPT0", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// Characters Windows doesn't allow in file names (':' also opens alternate data streams)
+const INVALID_CHARACTERS: &[char] = &['<', '>', ':', '"', '|', '?', '*'];
+
+/// Why a path was refused
+/// Serialized as stable codes (e.g. "PARENT_TRAVERSAL")
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PathViolation {
+    /// Nothing left after normalizing
+    Empty,
+    /// Starts at a root ("/textures", "\\server\share")
+    Absolute,
+    /// Starts with a Windows drive ("C:", "C:\Windows")
+    DrivePrefix,
+    /// Contains a ".." segment
+    ParentTraversal,
+    /// A segment is a Windows device name ("CON", "nul.png")
+    ReservedName,
+    /// Control characters, characters Windows refuses, or a trailing dot or space that Windows strips
+    InvalidCharacter,
+    /// Inside user-customs or a hidden folder, which syncs leave alone
+    Protected,
+    /// A rescue that doesn't go to user-customs/rescued
+    OutsideRescue,
+    /// A folder on the way is a symlink or junction that leads out of the serial folder
+    LinkEscape,
+}
+
+/// A path from the repository or the frontend that could reach outside the serial folder
+/// or into a protected area
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PathSecurityError {
+    pub violation: PathViolation,
+    pub path: String,
+}
+
+impl PathSecurityError {
+    fn new(violation: PathViolation, path: &str) -> Self {
+        PathSecurityError { violation, path: path.to_string() }
+    }
+
+    /// Why the path was refused, e.g. "it is absolute"
+    pub(crate) fn reason(&self) -> &'static str {
+        match self.violation {
+            PathViolation::Empty => "it is empty",
+            PathViolation::Absolute => "it is absolute",
+            PathViolation::DrivePrefix => "it starts with a drive",
+            PathViolation::ParentTraversal => "it leaves its folder with \"..\"",
+            PathViolation::ReservedName => "it uses a reserved device name",
+            PathViolation::InvalidCharacter => "it contains characters Windows can't store",
+            PathViolation::Protected => "it is in user-customs or a hidden folder",
+            PathViolation::OutsideRescue => "rescued files can only go to user-customs/rescued",
+            PathViolation::LinkEscape => "it leads out of the folder through a symlink or junction",
+        }
+    }
+}
+
+impl fmt::Display for PathSecurityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason = self.reason();
+        // The marker lets the frontend tell these apart from ordinary failures
+        write!(f, "UNSAFE_PATH: Refused {:?} because {}; nothing was changed", self.path, reason)
+    }
+}
+
+impl From<PathSecurityError> for String {
+    fn from(error: PathSecurityError) -> Self {
+        error.to_string()
+    }
+}
+
+fn is_reserved_name(segment: &str) -> bool {
+    // "nul.png" and "NUL .txt" are the device too
+    let stem = segment.split('.').next().unwrap_or(segment).trim_end_matches(' ');
+    RESERVED_NAMES.iter().any(|name| name.eq_ignore_ascii_case(stem))
+}
+
+fn has_drive_prefix(path: &str) -> bool {
+    let bytes = path.as_bytes();
+    bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':'
+}
+
+/// Check a path relative to a serial folder and normalize it
+/// Backslashes count as separators (they are on Windows); empty and "." segments are dropped
+pub(crate) fn validate_relative_path(path: &str) -> Result<String, PathSecurityError> {
+    let error = |violation| Err(PathSecurityError::new(violation, path));
+
+    if path.starts_with('/') || path.starts_with('\\') {
+        return error(PathViolation::Absolute);
+    }
+    if has_drive_prefix(path) {
+        return error(PathViolation::DrivePrefix);
+    }
+
+    let mut segments = Vec::new();
+    for segment in path.split(['/', '\\']) {
+        match segment {
+            "" | "." => continue,
+            ".." => return error(PathViolation::ParentTraversal),
+            _ => {}
+        }
+        if segment.chars().any(|c| c.is_control() || INVALID_CHARACTERS.contains(&c))
+            || segment.ends_with('.')
+            || segment.ends_with(' ')
+        {
+            return error(PathViolation::InvalidCharacter);
+        }
+        if is_reserved_name(segment) {
+            return error(PathViolation::ReservedName);
+        }
+        segments.push(segment);
+    }
+
+    if segments.is_empty() {
+        return error(PathViolation::Empty);
+    }
+    Ok(segments.join("/"))
+}
+
+/// Whether a normalized path is in user-customs or a hidden folder (Windows ignores case)
+fn is_protected(path: &str) -> bool {
+    path.split('/')
+        .any(|segment| segment.eq_ignore_ascii_case(PROTECTED_DIR) || segment.starts_with('.'))
+}
+
+/// Check and normalize a path a sync downloads, deletes or moves
+pub(crate) fn validate_sync_path(path: &str) -> Result<String, PathSecurityError> {
+    let normalized = validate_relative_path(path)?;
+    if is_protected(&normalized) {
+        return Err(PathSecurityError::new(PathViolation::Protected, path));
+    }
+    Ok(normalized)
+}
+
+/// Check and normalize where a rescued file is moved to
+pub(crate) fn validate_rescue_path(path: &str) -> Result<String, PathSecurityError> {
+    let normalized = validate_relative_path(path)?;
+    let inside_rescue = normalized
+        .get(..RESCUE_PREFIX.len())
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case(RESCUE_PREFIX));
+    let rest = &normalized[if inside_rescue { RESCUE_PREFIX.len() } else { 0 }..];
+    if !inside_rescue || is_protected(rest) {
+        return Err(PathSecurityError::new(PathViolation::OutsideRescue, path));
+    }
+    Ok(normalized)
+}
+
+/// Check a path from a repository tree, which must already be in normal form
+/// (a backslash would be a separator on Windows and alias another path)
+pub(crate) fn validate_repo_path(path: &str) -> Result<(), PathSecurityError> {
+    if validate_relative_path(path)? != path {
+        return Err(PathSecurityError::new(PathViolation::InvalidCharacter, path));
+    }
+    Ok(())
+}
+
+/// Check that a validated path's folder doesn't lead out of `root` through a symlink or junction
+/// The deepest folder of the path that exists is resolved, since the rest is only created later
+pub(crate) fn check_contained(root: &Path, path: &str) -> Result<(), PathSecurityError> {
+    let escape = || PathSecurityError::new(PathViolation::LinkEscape, path);
+    let root = root.canonicalize().map_err(|_| escape())?;
+
+    let mut folder = root.join(path);
+    folder.pop();
+    while fs::symlink_metadata(&folder).is_err() {
+        if !folder.pop() {
+            return Err(escape());
+        }
+    }
+    match folder.canonicalize() {
+        Ok(resolved) if resolved.starts_with(&root) => Ok(()),
+        _ => Err(escape()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::TempFolder;
+
+    fn violation(result: Result<String, PathSecurityError>) -> PathViolation {
+        result.expect_err("path should be refused").violation
+    }
+
+    #[test]
+    fn accepts_and_normalizes_ordinary_paths() {
+        assert_eq!(validate_sync_path("textures/abc.png").unwrap(), "textures/abc.png");
+        assert_eq!(validate_sync_path("textures//./abc.png").unwrap(), "textures/abc.png");
+        assert_eq!(validate_sync_path("textures\\sub\\-abc.png").unwrap(), "textures/sub/-abc.png");
+        assert_eq!(validate_sync_path("replacements/console.png").unwrap(), "replacements/console.png");
+        assert_eq!(validate_sync_path("a..b/file..png").unwrap(), "a..b/file..png");
+    }
+
+    #[test]
+    fn refuses_parent_traversal() {
+        for path in ["../outside.png", "textures/../../outside.png", "textures\\..\\..\\evil.dll", "a/.."] {
+            assert_eq!(violation(validate_sync_path(path)), PathViolation::ParentTraversal, "{}", path);
+        }
+    }
+
+    #[test]
+    fn refuses_absolute_and_drive_paths() {
+        assert_eq!(violation(validate_sync_path("/etc/passwd")), PathViolation::Absolute);
+        assert_eq!(violation(validate_sync_path("\\\\server\\share\\x.png")), PathViolation::Absolute);
+        assert_eq!(violation(validate_sync_path("\\Windows\\x.png")), PathViolation::Absolute);
+        assert_eq!(violation(validate_sync_path("C:\\Windows\\x.png")), PathViolation::DrivePrefix);
+        assert_eq!(violation(validate_sync_path("c:relative.png")), PathViolation::DrivePrefix);
+    }
+
+    #[test]
+    fn refuses_reserved_device_names() {
+        for path in ["CON", "textures/nul.png", "textures/Com1.dds", "lpt9", "aux .txt", "textures/CONOUT$"] {
+            assert_eq!(violation(validate_sync_path(path)), PathViolation::ReservedName, "{}", path);
+        }
+    }
+
+    #[test]
+    fn refuses_invalid_characters() {
+        for path in ["textures/a:stream.png", "a|b.png", "a?.png", "tex\u{0}.png", "textures/trailing.", "dir /x.png"] {
+            assert_eq!(violation(validate_sync_path(path)), PathViolation::InvalidCharacter, "{}", path);
+        }
+    }
+
+    #[test]
+    fn refuses_empty_paths() {
+        for path in ["", ".", "./", "//"] {
+            let expected = if path.starts_with('/') { PathViolation::Absolute } else { PathViolation::Empty };
+            assert_eq!(violation(validate_sync_path(path)), expected, "{:?}", path);
+        }
+    }
+
+    #[test]
+    fn refuses_protected_areas() {
+        for path in ["user-customs/mine.png", "textures/User-Customs/mine.png", ".git/config", "textures/.hidden/x.png"] {
+            assert_eq!(violation(validate_sync_path(path)), PathViolation::Protected, "{}", path);
+        }
+        // Windows strips the trailing dot, so this would alias user-customs
+        assert_eq!(violation(validate_sync_path("user-customs./x.png")), PathViolation::InvalidCharacter);
+        // Structural checks alone allow them (e.g. for the source of a rescue)
+        assert!(validate_relative_path("user-customs/mine.png").is_ok());
+    }
+
+    #[test]
+    fn rescues_only_go_to_the_rescue_folder() {
+        assert_eq!(
+            validate_rescue_path("user-customs/rescued/2024-01-01/textures/a.png").unwrap(),
+            "user-customs/rescued/2024-01-01/textures/a.png"
+        );
+        for path in ["textures/a.png", "user-customs/mine.png", "user-customs/rescued", "user-customs/rescued/.git/x"] {
+            assert_eq!(violation(validate_rescue_path(path)), PathViolation::OutsideRescue, "{}", path);
+        }
+        assert_eq!(
+            violation(validate_rescue_path("user-customs/rescued/../../x.png")),
+            PathViolation::ParentTraversal
+        );
+    }
+
+    #[test]
+    fn repo_paths_must_be_in_normal_form() {
+        assert!(validate_repo_path("textures/abc.png").is_ok());
+        assert_eq!(validate_repo_path("textures\\abc.png").unwrap_err().violation, PathViolation::InvalidCharacter);
+        assert_eq!(validate_repo_path("textures//abc.png").unwrap_err().violation, PathViolation::InvalidCharacter);
+        assert_eq!(validate_repo_path("../abc.png").unwrap_err().violation, PathViolation::ParentTraversal);
+    }
+
+    #[test]
+    fn contained_paths_may_have_missing_folders() {
+        let temp = TempFolder::new("path-guard-contained");
+        let root = temp.0.join("SLUS-21770");
+        fs::create_dir_all(root.join("textures")).unwrap();
+
+        assert!(check_contained(&root, "textures/abc.png").is_ok());
+        assert!(check_contained(&root, "textures/new/deeper/abc.png").is_ok());
+        assert!(check_contained(&root, "abc.png").is_ok());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn refuses_folders_linked_out_of_the_root() {
+        let temp = TempFolder::new("path-guard-linked");
+        let root = temp.0.join("SLUS-21770");
+        let outside = temp.0.join("outside");
+        fs::create_dir_all(&root).unwrap();
+        fs::create_dir_all(&outside).unwrap();
+        std::os::unix::fs::symlink(&outside, root.join("textures")).unwrap();
+
+        for path in ["textures/abc.png", "textures/new/abc.png"] {
+            assert_eq!(check_contained(&root, path).unwrap_err().violation, PathViolation::LinkEscape, "{}", path);
+        }
+        assert!(check_contained(&root, "other/abc.png").is_ok());
+    }
+
+    #[test]
+    fn error_carries_the_marker_and_path() {
+        let message = String::from(validate_sync_path("../x.png").unwrap_err());
+        assert!(message.starts_with("UNSAFE_PATH:"));
+        assert!(message.contains("../x.png"));
+    }
+}
@@ -0,0 +1,214 @@
+use super::mass_change::MassChangeGuard;
+use super::serials::refresh_mirror_folders;
+use super::state::{load_state, save_state};
+use super::sync::{build_request, get_commit_details_with_token, sync_folders, SyncProgressPayload, SyncResult};
+use super::undo::{find_record, reverse_record, SyncUndo, SyncUndoSummary, PREVIEW_KIND};
+use crate::config::{REPO_NAME, REPO_OWNER};
+use chrono::Utc;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tauri::{Emitter, Manager, Window};
+
+/// A branch, tag or pull request the folders are temporarily synced to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncPreview {
+    /// What the user previews (e.g. "feature/hd-crowds" or "#123")
+    pub preview_ref: String,
+    /// Commit the folders were synced to (a pull request's head when the preview started)
+    pub commit_sha: String,
+    /// Undo record of the preview sync, reversed to end it
+    pub undo_id: String,
+    /// `last_sync_commit` and timestamp before the preview
+    pub previous_commit: Option<String>,
+    pub previous_timestamp: Option<String>,
+    pub started_at: String,
+}
+
+/// Pull request response (only the head)
+#[derive(Debug, Deserialize)]
+struct PullRequestResponse {
+    head: PullRequestHead,
+}
+
+#[derive(Debug, Deserialize)]
+struct PullRequestHead {
+    sha: String,
+}
+
+/// Number of a pull request written as "#123" or "pull/123"
+fn pull_request_number(preview_ref: &str) -> Option<u64> {
+    preview_ref
+        .strip_prefix('#')
+        .or_else(|| preview_ref.strip_prefix("pull/"))
+        .and_then(|number| number.parse().ok())
+}
+
+/// Commit a preview syncs to: the head of a pull request, or the commit of a branch or tag
+async fn resolve_preview_commit(preview_ref: &str, token: &Option<String>) -> Result<String, String> {
+    let Some(number) = pull_request_number(preview_ref) else {
+        let (sha, _) = get_commit_details_with_token(preview_ref, token).await?;
+        return Ok(sha);
+    };
+
+    let client = Client::new();
+    let url = format!("https://api.github.com/repos/{}/{}/pulls/{}", REPO_OWNER, REPO_NAME, number);
+    let response = build_request(&client, &url, token)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch pull request: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!(
+            "GitHub API error: {} - {}",
+            response.status(),
+            response.text().await.unwrap_or_default()
+        ));
+    }
+
+    let pull: PullRequestResponse = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse pull request: {}", e))?;
+    Ok(pull.head.sha)
+}
+
+/// Sync every folder to a branch, tag or pull request ("#123") until the preview is ended
+/// The replaced and deleted files are kept whatever the undo retention, and other syncs are
+/// refused meanwhile, so ending the preview restores the folders exactly
+/// `allow_mass_changes` confirms changes above the mass-change limit
+#[tauri::command]
+pub async fn start_preview(
+    textures_dir: String,
+    preview_ref: String,
+    github_token: Option<String>,
+    allow_mass_changes: Option<bool>,
+    window: Window,
+) -> Result<SyncResult, String> {
+    let app = window.app_handle();
+    let textures_path = PathBuf::from(&textures_dir);
+    let preview_ref = preview_ref.trim().to_string();
+    if preview_ref.is_empty() {
+        return Err("Enter a branch, tag or pull request to preview".to_string());
+    }
+
+    let _ = window.emit("sync-progress", SyncProgressPayload {
+        stage: "fetching".to_string(),
+        message: format!("Looking up {}...", preview_ref),
+        current: None,
+        total: None,
+    });
+    let commit_sha = resolve_preview_commit(&preview_ref, &github_token).await?;
+
+    // Fails if a preview is already active
+    let mut undo = SyncUndo::start(app, &textures_path, PREVIEW_KIND)?;
+    let undo_id = undo.id().to_string();
+    let mut guard = MassChangeGuard::load(app, PREVIEW_KIND, allow_mass_changes.unwrap_or(false)).await?;
+    let state = load_state(app.clone())?;
+
+    let synced = sync_folders(
+        &textures_dir,
+        &state.last_sync_commit,
+        &commit_sha,
+        false,
+        &github_token,
+        &mut undo,
+        &mut guard,
+        &window,
+    )
+    .await
+    // A preview that left files out couldn't be ended cleanly, so it is undone instead
+    .and_then(|result| match result.failed.first() {
+        Some(file) => Err(format!(
+            "{} file(s) failed to download, e.g. {}: {}",
+            result.failed.len(),
+            file.path,
+            file.reason
+        )),
+        None => Ok(result),
+    });
+    undo.finish(Some(&commit_sha))?;
+
+    let result = match synced {
+        Ok(result) => result,
+        Err(e) => {
+            // Put back the folders the preview already changed
+            if let Some((dir, record)) = find_record(&textures_path, &undo_id) {
+                if let Err(undo_error) = reverse_record(&textures_path, &dir, record, "preview", &window) {
+                    return Err(format!("{}\nThe preview's changes so far could not be undone: {}", e, undo_error));
+                }
+            }
+            return Err(e);
+        }
+    };
+    guard.record_completed(
+        Some(&commit_sha),
+        result.files_downloaded,
+        result.files_deleted,
+        result.files_renamed,
+    );
+
+    let mut state = load_state(app.clone())?;
+    state.preview = Some(SyncPreview {
+        preview_ref: preview_ref.clone(),
+        commit_sha: commit_sha.clone(),
+        undo_id,
+        previous_commit: state.last_sync_commit.clone(),
+        previous_timestamp: state.last_sync_timestamp.clone(),
+        started_at: Utc::now().to_rfc3339(),
+    });
+    state.last_sync_commit = Some(commit_sha);
+    state.last_sync_timestamp = Some(Utc::now().to_rfc3339());
+    save_state(app.clone(), state)?;
+
+    refresh_mirror_folders(app, &textures_path, |message| {
+        let _ = window.emit("sync-progress", SyncProgressPayload {
+            stage: "sync_complete".to_string(),
+            message,
+            current: None,
+            total: None,
+        });
+    })?;
+
+    let _ = window.emit("sync-progress", SyncProgressPayload {
+        stage: "complete".to_string(),
+        message: format!(
+            "Previewing {}! Downloaded: {}, Deleted: {}, Renamed: {}",
+            preview_ref, result.files_downloaded, result.files_deleted, result.files_renamed
+        ),
+        current: None,
+        total: None,
+    });
+
+    Ok(result)
+}
+
+/// End the preview: put back every file it added, replaced, deleted or moved, and the commit before it
+/// Returns None when the preview changed no files
+#[tauri::command]
+pub fn end_preview(textures_dir: String, window: Window) -> Result<Option<SyncUndoSummary>, String> {
+    let app = window.app_handle();
+    let textures_path = PathBuf::from(&textures_dir);
+    let preview = load_state(app.clone())?.preview.ok_or("No preview is active")?;
+
+    let summary = match find_record(&textures_path, &preview.undo_id) {
+        Some((dir, record)) => Some(reverse_record(&textures_path, &dir, record, "preview", &window)?),
+        None => {
+            let _ = window.emit("sync-progress", SyncProgressPayload {
+                stage: "complete".to_string(),
+                message: "Preview ended!".to_string(),
+                current: None,
+                total: None,
+            });
+            None
+        }
+    };
+
+    let mut state = load_state(app.clone())?;
+    state.last_sync_commit = preview.previous_commit;
+    state.last_sync_timestamp = preview.previous_timestamp;
+    state.preview = None;
+    save_state(app.clone(), state)?;
+
+    Ok(summary)
+}
@@ -0,0 +1,184 @@
+use super::history::get_sync_history;
+use super::serials::PackTarget;
+use super::state::load_state;
+use super::sync::{compute_git_blob_sha_variants, fetch_github_tree_at, get_disabled_path, SyncFile, SyncMove};
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+
+/// Folder inside a serial folder's user-customs that rescued files are moved to
+const RESCUE_DIR: &str = "user-customs/rescued";
+
+/// Earlier synced commits whose trees are indexed, newest first (one tree request each)
+const MAX_HISTORY_TREES: usize = 10;
+
+/// Blob SHAs of every file version the app has installed
+/// A local file whose content is not among them was most likely made or edited by the user
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct KnownBlobs {
+    /// "<commit>:<sparse path>" trees whose blobs were added
+    #[serde(default)]
+    trees: HashSet<String>,
+    #[serde(default)]
+    blobs: HashSet<String>,
+}
+
+fn get_known_blobs_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+    fs::create_dir_all(&app_data_dir)
+        .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+    Ok(app_data_dir.join("known-blobs.json"))
+}
+
+impl KnownBlobs {
+    /// Load the index (empty if missing or unreadable)
+    pub(crate) fn load(app: &AppHandle) -> Self {
+        get_known_blobs_path(app)
+            .and_then(|path| fs::read_to_string(path).map_err(|e| e.to_string()))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn save(&self, app: &AppHandle) -> Result<(), String> {
+        let content = serde_json::to_string(self).map_err(|e| format!("Failed to serialize known files: {}", e))?;
+        fs::write(get_known_blobs_path(app)?, content).map_err(|e| format!("Failed to write known files: {}", e))
+    }
+
+    fn contains_any(&self, shas: &[String]) -> bool {
+        shas.iter().any(|sha| self.blobs.contains(sha))
+    }
+
+    /// Add the blobs of a commit's tree for one sparse path
+    fn add_tree<'a>(&mut self, commit_sha: &str, sparse_path: &str, shas: impl IntoIterator<Item = &'a String>) {
+        self.trees.insert(format!("{}:{}", commit_sha, sparse_path));
+        self.blobs.extend(shas.into_iter().cloned());
+    }
+
+    fn has_tree(&self, commit_sha: &str, sparse_path: &str) -> bool {
+        self.trees.contains(&format!("{}:{}", commit_sha, sparse_path))
+    }
+}
+
+/// Remember the blobs a sync just installed
+pub(crate) fn remember_installed_blobs(app: &AppHandle, shas: impl IntoIterator<Item = String>) {
+    let mut known = KnownBlobs::load(app);
+    let before = known.blobs.len();
+    known.blobs.extend(shas);
+    if known.blobs.len() != before {
+        let _ = known.save(app);
+    }
+}
+
+/// Known blobs for a Full Sync of `target` to `commit_sha`
+/// Adds the synced tree, and the trees of the last synced (installed) commit and of earlier syncs
+/// in the sync history that were never indexed
+pub(crate) async fn load_known_blobs(
+    app: &AppHandle,
+    target: &PackTarget,
+    remote_files: &HashMap<String, String>,
+    commit_sha: &str,
+    token: &Option<String>,
+) -> KnownBlobs {
+    let mut known = KnownBlobs::load(app);
+
+    let mut installed_commits: Vec<String> = load_state(app.clone())
+        .ok()
+        .and_then(|state| state.last_sync_commit)
+        .into_iter()
+        .collect();
+    let history = get_sync_history(app.clone()).unwrap_or_default();
+    let synced_commits = history
+        .into_iter()
+        .rev()
+        .filter(|entry| entry.outcome == "completed")
+        .filter_map(|entry| entry.commit_sha);
+    for commit in synced_commits {
+        if installed_commits.len() >= MAX_HISTORY_TREES {
+            break;
+        }
+        if !installed_commits.contains(&commit) {
+            installed_commits.push(commit);
+        }
+    }
+
+    for installed_commit in installed_commits {
+        if !known.has_tree(&installed_commit, &target.sparse_path) {
+            // An unreachable commit (e.g. after a force push) just can't be indexed
+            if let Ok(installed_files) = fetch_github_tree_at(&target.sparse_path, &installed_commit, token).await {
+                known.add_tree(&installed_commit, &target.sparse_path, installed_files.values());
+            }
+        }
+    }
+
+    if !known.has_tree(commit_sha, &target.sparse_path) {
+        known.add_tree(commit_sha, &target.sparse_path, remote_files.values());
+    }
+    let _ = known.save(app);
+    known
+}
+
+/// Folder (relative to the serial folder) this sync's rescued files go to
+/// A new folder is used when one for today already exists
+fn rescue_folder(slus_path: &Path) -> String {
+    let date = Local::now().format("%Y-%m-%d").to_string();
+    let mut folder = format!("{}/{}", RESCUE_DIR, date);
+    let mut n = 2;
+    while slus_path.join(&folder).exists() {
+        folder = format!("{}/{}-{}", RESCUE_DIR, date, n);
+        n += 1;
+    }
+    folder
+}
+
+/// Find replaced and deleted local files whose content the app never installed
+/// They are moved to user-customs/rescued/<date>/ (keeping their path) instead of being lost;
+/// rescued deletions are taken out of `files_to_delete`
+pub(crate) fn plan_rescues(
+    slus_path: &Path,
+    known: &KnownBlobs,
+    files_to_replace: &[SyncFile],
+    files_to_delete: &mut Vec<String>,
+) -> Vec<SyncMove> {
+    let is_user_file = |local_path: &str| {
+        compute_git_blob_sha_variants(&slus_path.join(local_path))
+            .map(|shas| !known.contains_any(&shas))
+            .unwrap_or(false)
+    };
+
+    let mut rescued: Vec<String> = files_to_replace
+        .iter()
+        .map(|file| if file.to_disabled { get_disabled_path(&file.path) } else { file.path.clone() })
+        .filter(|local_path| is_user_file(local_path))
+        .collect();
+
+    let mut kept_deletes = Vec::new();
+    for path in files_to_delete.drain(..) {
+        if is_user_file(&path) {
+            rescued.push(path);
+        } else {
+            kept_deletes.push(path);
+        }
+    }
+    *files_to_delete = kept_deletes;
+
+    if rescued.is_empty() {
+        return Vec::new();
+    }
+
+    let folder = rescue_folder(slus_path);
+    rescued
+        .into_iter()
+        .map(|path| SyncMove {
+            to: format!("{}/{}", folder, path),
+            from: path,
+            to_disabled: false,
+        })
+        .collect()
+}
@@ -0,0 +1,310 @@
+use super::state::{load_state, save_state, AppState};
+use super::sync::should_skip_path;
+use crate::config::{SLUS_FOLDER, SPARSE_PATH};
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+/// Marks a folder as a mirror this app manages (hidden, so syncs and mirroring skip it)
+const MIRROR_MARKER: &str = ".mirror";
+
+/// How a mirror folder is kept in sync with the primary folder
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LinkMode {
+    /// Hardlink each file (no extra disk space; falls back to copying where unsupported)
+    /// Linked files are the same file, so editing one in either folder edits both
+    Hardlink,
+    /// Copy each file
+    #[default]
+    Copy,
+}
+
+/// Where an additional serial folder gets its textures from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum SerialSource {
+    /// Same pack as the primary folder, kept identical to it
+    /// A read-only copy: files disabled, added or removed in it are reset on the next refresh
+    Mirror {
+        #[serde(default)]
+        link: LinkMode,
+    },
+    /// Its own pack at a different path in the repository (e.g. "textures/SLES-55123")
+    Subpath { path: String },
+}
+
+/// Additional game-serial folder (another region or revision of the game)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SerialFolder {
+    /// Folder name PCSX2 looks up, e.g. "SLES-55123"
+    pub serial: String,
+    pub source: SerialSource,
+}
+
+/// A folder in the textures directory that is synced against a path in the repository
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PackTarget {
+    pub folder: String,
+    pub sparse_path: String,
+}
+
+impl PackTarget {
+    pub(crate) fn primary() -> Self {
+        PackTarget {
+            folder: SLUS_FOLDER.to_string(),
+            sparse_path: SPARSE_PATH.to_string(),
+        }
+    }
+
+    pub(crate) fn is_primary(&self) -> bool {
+        self.folder == SLUS_FOLDER
+    }
+}
+
+/// The primary target followed by every subpath serial folder
+pub(crate) fn pack_targets(state: &AppState) -> Vec<PackTarget> {
+    let mut targets = vec![PackTarget::primary()];
+    for folder in &state.serial_folders {
+        if let SerialSource::Subpath { path } = &folder.source {
+            targets.push(PackTarget {
+                folder: folder.serial.clone(),
+                sparse_path: path.clone(),
+            });
+        }
+    }
+    targets
+}
+
+/// Load all pack targets from the saved state
+pub(crate) fn load_pack_targets(app: &AppHandle) -> Result<Vec<PackTarget>, String> {
+    Ok(pack_targets(&load_state(app.clone())?))
+}
+
+/// Find the target for a serial folder (None means the primary folder)
+pub(crate) fn resolve_target(app: &AppHandle, serial: Option<String>) -> Result<PackTarget, String> {
+    let serial = match serial {
+        Some(s) if s != SLUS_FOLDER => s,
+        _ => return Ok(PackTarget::primary()),
+    };
+
+    load_pack_targets(app)?
+        .into_iter()
+        .find(|t| t.folder == serial)
+        .ok_or_else(|| format!("{} is not a configured serial folder with its own pack", serial))
+}
+
+/// Check that a serial is a plain folder name
+fn validate_serial(serial: &str) -> Result<(), String> {
+    let valid_chars = serial
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+
+    if serial.is_empty() || !valid_chars {
+        return Err(format!(
+            "Invalid serial \"{}\": use letters, digits, '-' and '_' only (e.g. SLES-55123)",
+            serial
+        ));
+    }
+    if serial.eq_ignore_ascii_case(SLUS_FOLDER) {
+        return Err(format!("{} is already the main folder", SLUS_FOLDER));
+    }
+    Ok(())
+}
+
+/// Check that a repository subpath is relative and stays inside the repository
+fn validate_subpath(path: &str) -> Result<(), String> {
+    let invalid = path.is_empty()
+        || path.starts_with('/')
+        || path.contains('\\')
+        || path.contains(':')
+        || path.split('/').any(|c| c.is_empty() || c == "." || c == "..");
+
+    if invalid {
+        return Err(format!(
+            "Invalid repository path \"{}\": use a relative path like textures/SLES-55123",
+            path
+        ));
+    }
+    Ok(())
+}
+
+/// Save the additional serial folders
+#[tauri::command]
+pub fn set_serial_folders(app: AppHandle, folders: Vec<SerialFolder>) -> Result<(), String> {
+    let mut seen = HashSet::new();
+    for folder in &folders {
+        validate_serial(&folder.serial)?;
+        if let SerialSource::Subpath { path } = &folder.source {
+            validate_subpath(path)?;
+        }
+        if !seen.insert(folder.serial.to_ascii_uppercase()) {
+            return Err(format!("Serial {} is listed more than once", folder.serial));
+        }
+    }
+
+    let mut state = load_state(app.clone())?;
+    state.serial_folders = folders;
+    save_state(app, state)
+}
+
+/// Collect files below a folder as forward-slash relative paths, skipping user-customs and hidden files
+fn collect_relative_files(root: &Path, current: &Path, files: &mut Vec<String>) -> Result<(), String> {
+    let entries = fs::read_dir(current)
+        .map_err(|e| format!("Failed to read directory {}: {}", current.display(), e))?;
+
+    for entry in entries {
+        let entry = entry.map_err(|e| format!("Failed to read entry: {}", e))?;
+        let path = entry.path();
+        let relative = path
+            .strip_prefix(root)
+            .map_err(|e| format!("Failed to get relative path: {}", e))?
+            .to_string_lossy()
+            .replace('\\', "/");
+
+        if should_skip_path(&relative) {
+            continue;
+        }
+
+        if path.is_dir() {
+            collect_relative_files(root, &path, files)?;
+        } else if path.is_file() {
+            files.push(relative);
+        }
+    }
+
+    Ok(())
+}
+
+/// Hardlinks share metadata and copies get the source's modification time,
+/// so matching size and mtime means the mirror file is current
+fn is_mirror_current(source: &Path, dest: &Path) -> bool {
+    match (fs::metadata(source), fs::metadata(dest)) {
+        (Ok(s), Ok(d)) => {
+            s.len() == d.len()
+                && matches!((s.modified(), d.modified()), (Ok(a), Ok(b)) if a == b)
+        }
+        _ => false,
+    }
+}
+
+/// Copy a file and give the copy the source's modification time
+fn copy_with_mtime(source: &Path, dest: &Path) -> Result<(), String> {
+    fs::copy(source, dest).map_err(|e| format!("Failed to copy {}: {}", source.display(), e))?;
+
+    let modified = fs::metadata(source)
+        .and_then(|m| m.modified())
+        .map_err(|e| format!("Failed to read {}: {}", source.display(), e))?;
+    fs::File::options()
+        .write(true)
+        .open(dest)
+        .and_then(|f| f.set_modified(modified))
+        .map_err(|e| format!("Failed to update {}: {}", dest.display(), e))
+}
+
+/// Move a folder that isn't a mirror yet out of the way before it becomes one
+/// Returns the backup folder name, if there was anything to back up
+fn claim_mirror_folder(textures_path: &Path, serial: &str) -> Result<Option<String>, String> {
+    let mirror = textures_path.join(serial);
+    let mut backup_name = None;
+
+    if mirror.exists() && !mirror.join(MIRROR_MARKER).exists() {
+        let name = format!("{}_backup_{}", serial, Local::now().format("%Y%m%d_%H%M%S"));
+        fs::rename(&mirror, textures_path.join(&name))
+            .map_err(|e| format!("Failed to back up {} before mirroring: {}", serial, e))?;
+        backup_name = Some(name);
+    }
+
+    if !mirror.join(MIRROR_MARKER).exists() {
+        fs::create_dir_all(&mirror).map_err(|e| format!("Failed to create {}: {}", serial, e))?;
+        fs::write(mirror.join(MIRROR_MARKER), "")
+            .map_err(|e| format!("Failed to mark {} as a mirror: {}", serial, e))?;
+    }
+    Ok(backup_name)
+}
+
+/// Make a mirror folder identical to the primary folder (user-customs and hidden files excepted)
+/// Returns (files updated, files removed)
+fn mirror_folder(primary: &Path, mirror: &Path, link: LinkMode) -> Result<(u32, u32), String> {
+    let mut source_files = Vec::new();
+    collect_relative_files(primary, primary, &mut source_files)?;
+
+    let mut updated = 0;
+    for relative in &source_files {
+        let source = primary.join(relative);
+        let dest = mirror.join(relative);
+
+        if is_mirror_current(&source, &dest) {
+            continue;
+        }
+
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create directory: {}", e))?;
+        }
+        if dest.exists() {
+            fs::remove_file(&dest)
+                .map_err(|e| format!("Failed to replace {}: {}", dest.display(), e))?;
+        }
+
+        // Hardlinks fail across drives and on some file systems (e.g. FAT32)
+        if link == LinkMode::Copy || fs::hard_link(&source, &dest).is_err() {
+            copy_with_mtime(&source, &dest)?;
+        }
+        updated += 1;
+    }
+
+    let mut removed = 0;
+    let mut mirror_files = Vec::new();
+    collect_relative_files(mirror, mirror, &mut mirror_files)?;
+    let source_set: HashSet<&String> = source_files.iter().collect();
+
+    for relative in mirror_files.iter().filter(|r| !source_set.contains(r)) {
+        let path = mirror.join(relative);
+        fs::remove_file(&path)
+            .map_err(|e| format!("Failed to delete {}: {}", path.display(), e))?;
+        removed += 1;
+
+        if let Some(parent) = path.parent() {
+            let _ = fs::remove_dir(parent);
+        }
+    }
+
+    Ok((updated, removed))
+}
+
+/// Bring every mirror serial folder in line with the primary folder
+/// `on_progress` receives a status line per mirror folder
+pub(crate) fn refresh_mirror_folders(
+    app: &AppHandle,
+    textures_path: &Path,
+    mut on_progress: impl FnMut(String),
+) -> Result<(), String> {
+    let state = load_state(app.clone())?;
+    let primary = textures_path.join(SLUS_FOLDER);
+    if !primary.exists() {
+        return Ok(());
+    }
+
+    for folder in &state.serial_folders {
+        let SerialSource::Mirror { link } = folder.source else {
+            continue;
+        };
+
+        on_progress(format!("Updating mirror folder {}...", folder.serial));
+        if let Some(backup_name) = claim_mirror_folder(textures_path, &folder.serial)? {
+            on_progress(format!("{} was not a mirror yet; moved it to {}", folder.serial, backup_name));
+        }
+        let mirror: PathBuf = textures_path.join(&folder.serial);
+        let (updated, removed) = mirror_folder(&primary, &mirror, link)?;
+        on_progress(format!(
+            "Mirror folder {}: {} updated, {} removed",
+            folder.serial, updated, removed
+        ));
+    }
+
+    Ok(())
+}
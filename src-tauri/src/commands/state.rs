@@ -1,3 +1,9 @@
+use super::app_info::ContentModule;
+use super::conflicts::ConflictPolicy;
+use super::failures::FailedSync;
+use super::holds::HeldPath;
+use super::preview::SyncPreview;
+use super::serials::SerialFolder;
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use std::fs;
@@ -20,6 +26,45 @@ pub struct AppState {
     /// Whether the user has acknowledged the sync disclaimer
     #[serde(default)]
     pub sync_disclaimer_acknowledged: bool,
+    /// Scratch directory for installation clones (defaults to the textures directory)
+    #[serde(default)]
+    pub temp_path: Option<String>,
+    /// IDs of the selected optional content modules (None until the user picks)
+    #[serde(default)]
+    pub selected_modules: Option<Vec<String>>,
+    /// Module declarations from the last installer-data.json that could be fetched
+    #[serde(default)]
+    pub content_modules: Vec<ContentModule>,
+    /// Additional game-serial folders (other regions/revisions) installed next to the SLUS folder
+    #[serde(default)]
+    pub serial_folders: Vec<SerialFolder>,
+    /// Days to keep undo data of past syncs (None uses the default, 0 keeps none)
+    #[serde(default)]
+    pub undo_retention_days: Option<u32>,
+    /// Mass-change limit in percent chosen by the user (None uses installer-data.json or the default)
+    #[serde(default)]
+    pub mass_change_limit_percent: Option<f64>,
+    /// What syncs do when a file exists both enabled and dash-disabled
+    #[serde(default)]
+    pub conflict_policy: ConflictPolicy,
+    /// Paths and globs syncs leave alone
+    #[serde(default)]
+    pub held_paths: Vec<HeldPath>,
+    /// Branch, tag or commit SHA syncs install (None follows the release channel)
+    #[serde(default)]
+    pub sync_ref: Option<String>,
+    /// ID of the release channel from installer-data.json syncs follow (None for main)
+    #[serde(default)]
+    pub release_channel: Option<String>,
+    /// Branch, tag or pull request the folders are temporarily synced to
+    #[serde(default)]
+    pub preview: Option<SyncPreview>,
+    /// Whether syncs go on past files that fail to download instead of stopping
+    #[serde(default)]
+    pub continue_on_errors: bool,
+    /// Files the last sync failed to download; its commit is recorded once they are retried
+    #[serde(default)]
+    pub failed_sync: Option<FailedSync>,
 }
 
 /// Get the path to the state file
@@ -116,3 +161,41 @@ pub fn set_sync_disclaimer_acknowledged(app: AppHandle, acknowledged: bool) -> R
     state.sync_disclaimer_acknowledged = acknowledged;
     save_state(app, state)
 }
+
+/// Set the scratch directory for installation clones (empty resets to the textures directory)
+#[tauri::command]
+pub fn set_temp_path(app: AppHandle, path: String) -> Result<(), String> {
+    let mut state = load_state(app.clone())?;
+    state.temp_path = if path.is_empty() { None } else { Some(path) };
+    save_state(app, state)
+}
+
+/// Save the optional content module selection without touching installed files
+#[tauri::command]
+pub fn set_selected_modules(app: AppHandle, module_ids: Vec<String>) -> Result<(), String> {
+    let mut state = load_state(app.clone())?;
+    state.selected_modules = Some(module_ids);
+    save_state(app, state)
+}
+
+/// Set how many days undo data of past syncs is kept (0 keeps none)
+#[tauri::command]
+pub fn set_undo_retention_days(app: AppHandle, days: u32) -> Result<(), String> {
+    let mut state = load_state(app.clone())?;
+    state.undo_retention_days = Some(days);
+    save_state(app, state)
+}
+
+/// Set the share of tracked files (percent) a sync may delete or replace without confirmation
+/// None goes back to the limit from installer-data.json
+#[tauri::command]
+pub fn set_mass_change_limit(app: AppHandle, percent: Option<f64>) -> Result<(), String> {
+    if let Some(percent) = percent {
+        if !(0.0..=100.0).contains(&percent) {
+            return Err("The limit must be between 0 and 100 percent".to_string());
+        }
+    }
+    let mut state = load_state(app.clone())?;
+    state.mass_change_limit_percent = percent;
+    save_state(app, state)
+}
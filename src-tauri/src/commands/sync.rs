@@ -1,11 +1,27 @@
-use crate::config::{REPO_NAME, REPO_OWNER, SLUS_FOLDER, SPARSE_PATH};
+use super::channels::load_channel_ref;
+use super::cleanup::PARTIAL_SUFFIX;
+use super::conflicts::{load_conflict_policy, ConflictPolicy, DisabledConflict, DisabledConflicts};
+use super::failures::{record_failed_fixes, record_failed_sync};
+use super::holds::HoldList;
+use super::mass_change::MassChangeGuard;
+use super::modules::{load_module_filter, ModuleFilter};
+use super::path_guard::{
+    check_contained, validate_repo_path, validate_rescue_path, validate_sync_path, PathSecurityError, PathViolation,
+};
+use super::rescue::{load_known_blobs, plan_rescues, remember_installed_blobs};
+use super::serials::{load_pack_targets, refresh_mirror_folders, resolve_target, PackTarget};
+use super::state::{load_state, save_state};
+use super::sync_plan::{build_plan_target, PlanTarget, SyncPlan};
+use super::transaction::{SyncTransaction, TransactionOp};
+use super::undo::SyncUndo;
+use crate::config::{REPO_NAME, REPO_OWNER, SLUS_FOLDER};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use sha1::{Digest, Sha1};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
-use tauri::{Emitter, Window};
+use tauri::{AppHandle, Emitter, Manager, Window};
 
 /// GitHub tree entry from API response
 #[derive(Debug, Deserialize, Clone)]
@@ -14,6 +30,9 @@ struct TreeEntry {
     #[serde(rename = "type")]
     entry_type: String,
     sha: String,
+    /// Blob size in bytes (not set for trees)
+    #[serde(default)]
+    size: Option<u64>,
 }
 
 /// GitHub tree response
@@ -27,34 +46,41 @@ struct TreeResponse {
 
 /// GitHub commit response (for getting latest commit)
 #[derive(Debug, Deserialize)]
-struct CommitResponse {
-    sha: String,
-    commit: CommitDetails,
+pub(crate) struct CommitResponse {
+    pub sha: String,
+    pub commit: CommitDetails,
 }
 
 #[derive(Debug, Deserialize)]
-struct CommitDetails {
-    committer: CommitAuthor,
+pub(crate) struct CommitDetails {
+    pub committer: CommitAuthor,
+    #[serde(default)]
+    pub message: String,
 }
 
 #[derive(Debug, Deserialize)]
-struct CommitAuthor {
-    date: String,
+pub(crate) struct CommitAuthor {
+    pub date: String,
+    #[serde(default)]
+    pub name: String,
 }
 
 /// GitHub compare response
 #[derive(Debug, Deserialize)]
 struct CompareResponse {
+    /// "ahead", "behind", "diverged" or "identical" (head relative to base)
+    #[serde(default)]
+    status: Option<String>,
     files: Option<Vec<CompareFile>>,
 }
 
 /// File entry in compare response
 #[derive(Debug, Deserialize, Clone)]
-struct CompareFile {
+pub(crate) struct CompareFile {
     filename: String,
-    status: String, // "added", "modified", "removed", "renamed"
+    status: String, // "added", "modified", "removed", "renamed", "copied", "changed", "unchanged"
     previous_filename: Option<String>,
-    #[allow(dead_code)]
+    /// Blob SHA of the file at the new commit
     sha: Option<String>,
 }
 
@@ -75,6 +101,93 @@ pub struct SyncResult {
     pub files_renamed: u32,
     pub files_skipped: u32,
     pub new_commit_sha: String,
+    /// Changes that were not applied, with the reason
+    pub skipped: Vec<SkippedFile>,
+    /// Local files moved to user-customs/rescued instead of being overwritten or deleted
+    /// (relative to the textures folder)
+    pub rescued: Vec<String>,
+    /// Files with both an enabled and a disabled copy, resolved by the conflict policy or left for the user
+    pub conflicts: Vec<DisabledConflict>,
+    /// Changes to held files that were left alone (relative to the textures folder)
+    pub held: Vec<String>,
+    /// Downloads that failed while continuing on errors; the commit isn't recorded until they are retried
+    pub failed: Vec<FailedFile>,
+}
+
+/// A change from the repository that sync did not apply
+#[derive(Debug, Clone, Serialize)]
+pub struct SkippedFile {
+    /// Path in the repository
+    pub path: String,
+    pub reason: String,
+    /// Set when the path was refused as unsafe
+    pub violation: Option<PathViolation>,
+}
+
+impl SkippedFile {
+    /// A repository file whose path (relative to `sparse_path`) was refused
+    fn unsafe_path(sparse_path: &str, error: PathSecurityError) -> Self {
+        SkippedFile {
+            path: format!("{}/{}", sparse_path, error.path),
+            reason: format!("Not synced because {}", error.reason()),
+            violation: Some(error.violation),
+        }
+    }
+}
+
+/// What retrying a failed file does
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FailedAction {
+    /// Download it (also for a file that failed to be written or moved into place)
+    #[default]
+    Download,
+    /// Delete it (`path` is the local path)
+    Delete,
+}
+
+/// A change that failed while the rest of the sync went on
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FailedFile {
+    /// Serial folder the file belongs in
+    pub folder: String,
+    /// Path in the repository
+    pub path: String,
+    pub to_disabled: bool,
+    #[serde(default)]
+    pub sha: Option<String>,
+    #[serde(default)]
+    pub action: FailedAction,
+    pub reason: String,
+}
+
+impl FailedFile {
+    fn new(folder: &str, file: &SyncFile, reason: String) -> Self {
+        FailedFile {
+            folder: folder.to_string(),
+            path: file.path.clone(),
+            to_disabled: file.to_disabled,
+            sha: file.sha.clone(),
+            action: FailedAction::Download,
+            reason,
+        }
+    }
+
+    fn delete(folder: &str, local_path: &str, reason: String) -> Self {
+        FailedFile {
+            folder: folder.to_string(),
+            path: local_path.to_string(),
+            to_disabled: false,
+            sha: None,
+            action: FailedAction::Delete,
+            reason,
+        }
+    }
+
+    /// The download to try again
+    pub(crate) fn to_sync_file(&self) -> SyncFile {
+        SyncFile { path: self.path.clone(), to_disabled: self.to_disabled, sha: self.sha.clone() }
+    }
 }
 
 /// Verification scan result (discrepancies found)
@@ -82,7 +195,13 @@ pub struct SyncResult {
 pub struct VerificationResult {
     pub files_to_download: Vec<VerificationFile>,
     pub files_to_delete: Vec<String>,
+    /// Files with both an enabled and a disabled copy (the policy's losing copies are in `files_to_delete`)
+    pub conflicts: Vec<String>,
+    /// Held files that differ from the repository (left alone)
+    pub held: Vec<String>,
     pub has_discrepancies: bool,
+    /// Commit the scan compared against; fixes download the files as they are there
+    pub commit_sha: String,
 }
 
 /// Quick count check result (fast, no SHA computation)
@@ -98,6 +217,18 @@ pub struct QuickCheckResult {
 pub struct VerificationFile {
     pub path: String,
     pub to_disabled: bool,
+    /// Blob SHA at the scanned commit, checked after the download
+    #[serde(default)]
+    pub sha: Option<String>,
+}
+
+/// Result of applying verification fixes
+#[derive(Debug, Clone, Serialize)]
+pub struct VerificationFixResult {
+    pub files_downloaded: u32,
+    pub files_deleted: u32,
+    /// Fixes that failed, kept for retrying
+    pub failed: Vec<FailedFile>,
 }
 
 /// Sync analysis result - what will happen if sync proceeds
@@ -109,8 +240,73 @@ pub struct SyncAnalysis {
     pub files_to_replace: Vec<SyncFile>,
     /// Files that exist locally but not in remote (will be deleted)
     pub files_to_delete: Vec<String>,
+    /// Local files whose content moved to a new path in remote (will be moved)
+    pub files_to_move: Vec<SyncMove>,
+    /// User-made or edited files that would be replaced or deleted (will be moved to user-customs/rescued)
+    pub files_to_rescue: Vec<SyncMove>,
+    /// Files with both an enabled and a disabled copy (the policy's losing copies are in `files_to_delete`)
+    pub conflicts: Vec<String>,
+    /// Held files the sync leaves alone
+    pub held: Vec<String>,
+    /// Repository files left out because their paths aren't safe (in every folder)
+    pub skipped: Vec<SkippedFile>,
+    /// Latest commit SHA
+    pub commit_sha: String,
+    /// Same analysis for each serial folder with its own pack
+    pub serial_targets: Vec<TargetAnalysis>,
+    /// The analysis with expected SHAs, for export and stale-checked execution
+    pub plan: SyncPlan,
+}
+
+/// File in an incremental sync preview
+#[derive(Debug, Clone, Serialize)]
+pub struct PreviewFile {
+    /// Serial folder the file is in
+    pub serial: String,
+    /// Path relative to the serial folder (the new path for renames)
+    pub path: String,
+    /// Current local path of a renamed file
+    pub previous_path: Option<String>,
+    /// Size of the file that is downloaded (adds and replaces), or of the local file that is renamed or deleted
+    pub size_bytes: Option<u64>,
+    /// The local file is dash-disabled (and stays disabled)
+    pub is_disabled: bool,
+}
+
+/// Incremental sync analysis result - what "Download New Content" would do
+#[derive(Debug, Clone, Serialize)]
+pub struct IncrementalAnalysis {
+    pub files_to_add: Vec<PreviewFile>,
+    pub files_to_replace: Vec<PreviewFile>,
+    pub files_to_rename: Vec<PreviewFile>,
+    pub files_to_delete: Vec<PreviewFile>,
+    /// Changes that will not be applied, with the reason
+    pub skipped: Vec<SkippedFile>,
+    /// Files with both an enabled and a disabled copy
+    pub conflicts: Vec<DisabledConflict>,
+    /// Held files the sync leaves alone (relative to the textures folder)
+    pub held: Vec<String>,
     /// Latest commit SHA
     pub commit_sha: String,
+    /// The analysis with expected SHAs, for export and stale-checked execution
+    pub plan: SyncPlan,
+}
+
+/// Full sync analysis of one serial folder
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TargetAnalysis {
+    pub serial: String,
+    pub files_to_add: Vec<SyncFile>,
+    pub files_to_replace: Vec<SyncFile>,
+    pub files_to_delete: Vec<String>,
+    #[serde(default)]
+    pub files_to_move: Vec<SyncMove>,
+    #[serde(default)]
+    pub files_to_rescue: Vec<SyncMove>,
+    #[serde(default)]
+    pub conflicts: Vec<String>,
+    #[serde(default)]
+    pub held: Vec<String>,
 }
 
 /// File info for sync operations
@@ -118,6 +314,20 @@ pub struct SyncAnalysis {
 pub struct SyncFile {
     pub path: String,
     pub to_disabled: bool,
+    /// Expected blob SHA of the repo file, used to verify the download
+    #[serde(default)]
+    pub sha: Option<String>,
+}
+
+/// Local file that is moved instead of deleted and downloaded again
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncMove {
+    /// Current local path (may be the disabled version)
+    pub from: String,
+    /// New path in remote
+    pub to: String,
+    /// Keep the file disabled at its new path
+    pub to_disabled: bool,
 }
 
 /// Check if content is likely a text file (no null bytes in first 8KB)
@@ -161,14 +371,14 @@ fn compute_sha_for_content(content: &[u8]) -> String {
 
 /// Compute git blob SHA for a file (same format git uses)
 /// Returns both the raw SHA and normalized SHA for text files
-fn compute_git_blob_sha(path: &Path) -> Result<String, String> {
+pub(crate) fn compute_git_blob_sha(path: &Path) -> Result<String, String> {
     let content = fs::read(path).map_err(|e| format!("Failed to read file: {}", e))?;
     Ok(compute_sha_for_content(&content))
 }
 
 /// Compute git blob SHA, trying both raw and normalized versions for text files
 /// Returns the SHA that matches the expected one, or raw SHA if no expected SHA provided
-fn compute_git_blob_sha_with_normalization(path: &Path, expected_sha: Option<&str>) -> Result<String, String> {
+pub(crate) fn compute_git_blob_sha_with_normalization(path: &Path, expected_sha: Option<&str>) -> Result<String, String> {
     let content = fs::read(path).map_err(|e| format!("Failed to read file: {}", e))?;
 
     // Compute raw SHA first
@@ -193,6 +403,19 @@ fn compute_git_blob_sha_with_normalization(path: &Path, expected_sha: Option<&st
     Ok(raw_sha)
 }
 
+/// All blob SHAs a local file could have in the repository (raw, and line-ending normalized for text)
+pub(crate) fn compute_git_blob_sha_variants(path: &Path) -> Result<Vec<String>, String> {
+    let content = fs::read(path).map_err(|e| format!("Failed to read file: {}", e))?;
+    let mut shas = vec![compute_sha_for_content(&content)];
+    if is_text_content(&content) {
+        let normalized_sha = compute_sha_for_content(&normalize_line_endings(content));
+        if normalized_sha != shas[0] {
+            shas.push(normalized_sha);
+        }
+    }
+    Ok(shas)
+}
+
 /// Check if a filename is a junk file that can be safely deleted during cleanup
 fn is_junk_file(name: &str) -> bool {
     // All hidden files (starting with .)
@@ -208,7 +431,7 @@ fn is_junk_file(name: &str) -> bool {
 
 /// Recursively remove empty directories (and OS junk files)
 /// Does not remove the root directory itself, only empty subdirectories
-fn cleanup_empty_directories(root: &Path, window: &Window) -> u32 {
+pub(crate) fn cleanup_empty_directories(root: &Path, window: &Window) -> u32 {
     cleanup_empty_directories_recursive(root, true, window)
 }
 
@@ -286,7 +509,7 @@ fn cleanup_empty_directories_recursive(dir: &Path, is_root: bool, window: &Windo
 }
 
 /// Check if a path should be skipped (user-customs folder or hidden files)
-fn should_skip_path(path: &str) -> bool {
+pub(crate) fn should_skip_path(path: &str) -> bool {
     // Skip user-customs folder
     if path.contains("user-customs") {
         return true;
@@ -311,7 +534,7 @@ fn get_filename(path: &str) -> &str {
 }
 
 /// Get the disabled version path for a file
-fn get_disabled_path(path: &str) -> String {
+pub(crate) fn get_disabled_path(path: &str) -> String {
     if let Some(pos) = path.rfind('/') {
         let dir = &path[..pos + 1];
         let file = &path[pos + 1..];
@@ -322,7 +545,7 @@ fn get_disabled_path(path: &str) -> String {
 }
 
 /// Get the enabled version path for a disabled file
-fn get_enabled_path(path: &str) -> Option<String> {
+pub(crate) fn get_enabled_path(path: &str) -> Option<String> {
     let filename = get_filename(path);
     if !is_disabled_filename(filename) {
         return None;
@@ -340,7 +563,7 @@ fn get_enabled_path(path: &str) -> Option<String> {
 }
 
 /// Build request with optional auth token
-fn build_request(client: &Client, url: &str, token: &Option<String>) -> reqwest::RequestBuilder {
+pub(crate) fn build_request(client: &Client, url: &str, token: &Option<String>) -> reqwest::RequestBuilder {
     let mut req = client
         .get(url)
         .header("User-Agent", "NCAA-NEXT-Textures-Downloader")
@@ -353,19 +576,63 @@ fn build_request(client: &Client, url: &str, token: &Option<String>) -> reqwest:
     req
 }
 
-/// Get the latest commit SHA for the main branch
+/// Ref syncs install when the user hasn't picked one
+pub(crate) const DEFAULT_SYNC_REF: &str = "main";
+
+/// Get the commit SHA syncs install (the head of main unless another ref is set)
 #[tauri::command]
-pub async fn get_latest_commit() -> Result<String, String> {
-    get_latest_commit_with_token(&None).await
+pub async fn get_latest_commit(app: AppHandle) -> Result<String, String> {
+    resolve_sync_commit(&app, &None).await
+}
+
+/// Branch, tag or commit SHA syncs install: the previewed commit, else a pinned ref,
+/// else the release channel's, else main
+pub(crate) async fn load_sync_ref(app: &AppHandle) -> Result<String, String> {
+    let state = load_state(app.clone())?;
+    if let Some(preview) = state.preview {
+        return Ok(preview.commit_sha);
+    }
+    if let Some(sync_ref) = state.sync_ref {
+        return Ok(sync_ref);
+    }
+    Ok(load_channel_ref(app)
+        .await?
+        .unwrap_or_else(|| DEFAULT_SYNC_REF.to_string()))
 }
 
-async fn get_latest_commit_with_token(token: &Option<String>) -> Result<String, String> {
-    let (sha, _) = get_commit_details_with_token("main", token).await?;
+/// Commit SHA the sync ref points to; tree lookups and downloads of a sync are pinned to it
+pub(crate) async fn resolve_sync_commit(app: &AppHandle, token: &Option<String>) -> Result<String, String> {
+    let (sha, _) = get_commit_details_with_token(&load_sync_ref(app).await?, token).await?;
+    Ok(sha)
+}
+
+/// Pin the branch, tag or commit SHA syncs install (None goes back to following the release channel)
+/// Returns the commit SHA the ref points to; the next sync moves the folders to it
+#[tauri::command]
+pub async fn set_sync_ref(
+    app: AppHandle,
+    sync_ref: Option<String>,
+    github_token: Option<String>,
+) -> Result<String, String> {
+    let sync_ref = sync_ref.map(|r| r.trim().to_string()).filter(|r| !r.is_empty());
+    let commit_ref = match &sync_ref {
+        Some(sync_ref) => sync_ref.clone(),
+        None => load_channel_ref(&app)
+            .await?
+            .unwrap_or_else(|| DEFAULT_SYNC_REF.to_string()),
+    };
+    let (sha, _) = get_commit_details_with_token(&commit_ref, &github_token)
+        .await
+        .map_err(|e| format!("Unknown branch, tag or commit \"{}\": {}", commit_ref, e))?;
+
+    let mut state = load_state(app.clone())?;
+    state.sync_ref = sync_ref;
+    save_state(app, state)?;
     Ok(sha)
 }
 
 /// Fetch commit details (sha and date) for a given commit reference
-async fn get_commit_details_with_token(commit_ref: &str, token: &Option<String>) -> Result<(String, String), String> {
+pub(crate) async fn get_commit_details_with_token(commit_ref: &str, token: &Option<String>) -> Result<(String, String), String> {
     let client = Client::new();
     let url = format!(
         "https://api.github.com/repos/{}/{}/commits/{}",
@@ -449,7 +716,7 @@ async fn fetch_tree_files_recursive(
     client: &Client,
     tree_sha: &str,
     base_path: &str,
-    file_map: &mut HashMap<String, String>,
+    file_map: &mut HashMap<String, TreeEntry>,
     token: &Option<String>,
 ) -> Result<(), String> {
     let tree = fetch_tree(client, tree_sha, true, token).await?;
@@ -466,7 +733,7 @@ async fn fetch_tree_files_recursive(
             };
 
             if entry.entry_type == "blob" {
-                file_map.insert(entry_path, entry.sha);
+                file_map.insert(entry_path, entry);
             } else if entry.entry_type == "tree" {
                 // Recursively fetch this subdirectory
                 Box::pin(fetch_tree_files_recursive(client, &entry.sha, &entry_path, file_map, token)).await?;
@@ -477,11 +744,11 @@ async fn fetch_tree_files_recursive(
         for entry in tree.tree {
             if entry.entry_type == "blob" {
                 let entry_path = if base_path.is_empty() {
-                    entry.path
+                    entry.path.clone()
                 } else {
                     format!("{}/{}", base_path, entry.path)
                 };
-                file_map.insert(entry_path, entry.sha);
+                file_map.insert(entry_path, entry);
             }
         }
     }
@@ -489,21 +756,97 @@ async fn fetch_tree_files_recursive(
     Ok(())
 }
 
-/// Fetch the GitHub tree for the sparse path (used for full sync)
-async fn fetch_github_tree(token: &Option<String>) -> Result<(HashMap<String, String>, String), String> {
-    let client = Client::new();
+/// Fetch the GitHub tree for a sparse path at the sync ref
+/// Returns the files and the commit SHA they were read at
+pub(crate) async fn fetch_github_tree(
+    app: &AppHandle,
+    sparse_path: &str,
+    token: &Option<String>,
+) -> Result<(HashMap<String, String>, String), String> {
+    let commit_sha = resolve_sync_commit(app, token).await?;
+    let file_map = fetch_github_tree_at(sparse_path, &commit_sha, token).await?;
+
+    Ok((file_map, commit_sha))
+}
+
+/// Fetch the files (path -> blob SHA) of a sparse path at a given commit
+/// Files whose paths aren't safe are left out
+pub(crate) async fn fetch_github_tree_at(
+    sparse_path: &str,
+    commit_sha: &str,
+    token: &Option<String>,
+) -> Result<HashMap<String, String>, String> {
+    Ok(fetch_github_tree_checked(sparse_path, commit_sha, token).await?.0)
+}
 
-    // First get the latest commit SHA
-    let commit_sha = get_latest_commit_with_token(token).await?;
+/// Fetch the files of a sparse path at a given commit, and the files left out because their paths aren't safe
+pub(crate) async fn fetch_github_tree_checked(
+    sparse_path: &str,
+    commit_sha: &str,
+    token: &Option<String>,
+) -> Result<(HashMap<String, String>, Vec<PathSecurityError>), String> {
+    let client = Client::new();
 
-    // Navigate to the SPARSE_PATH subtree to avoid fetching the entire repo
-    let subtree_sha = get_subtree_sha(&client, &commit_sha, SPARSE_PATH, token).await?;
+    // Navigate to the sparse path subtree to avoid fetching the entire repo
+    let subtree_sha = get_subtree_sha(&client, commit_sha, sparse_path, token).await?;
 
     // Now fetch all files from this subtree
-    let mut file_map: HashMap<String, String> = HashMap::new();
-    fetch_tree_files_recursive(&client, &subtree_sha, "", &mut file_map, token).await?;
+    let mut entries = HashMap::new();
+    fetch_tree_files_recursive(&client, &subtree_sha, "", &mut entries, token).await?;
+    let mut file_map: HashMap<String, String> = entries.into_iter().map(|(path, entry)| (path, entry.sha)).collect();
+
+    // A file with a path that isn't a plain relative path can't be synced safely
+    let mut refused = Vec::new();
+    file_map.retain(|path, _| match validate_repo_path(path) {
+        Ok(()) => true,
+        Err(e) => {
+            refused.push(e);
+            false
+        }
+    });
+    refused.sort_by(|a, b| a.path.cmp(&b.path));
 
-    Ok((file_map, commit_sha))
+    Ok((file_map, refused))
+}
+
+/// Size of every file (relative to a sparse path) at a given commit
+pub(crate) async fn fetch_github_file_sizes_at(
+    sparse_path: &str,
+    commit_sha: &str,
+    token: &Option<String>,
+) -> Result<HashMap<String, u64>, String> {
+    let client = Client::new();
+    let subtree_sha = get_subtree_sha(&client, commit_sha, sparse_path, token).await?;
+    let mut entries = HashMap::new();
+    fetch_tree_files_recursive(&client, &subtree_sha, "", &mut entries, token).await?;
+    Ok(entries.into_iter().filter_map(|(path, entry)| entry.size.map(|size| (path, size))).collect())
+}
+
+/// Blob SHA of one file (relative to a sparse path) at a given commit, None if it doesn't exist there
+pub(crate) async fn fetch_blob_sha_at(
+    sparse_path: &str,
+    relative_path: &str,
+    commit_sha: &str,
+    token: &Option<String>,
+) -> Result<Option<String>, String> {
+    let client = Client::new();
+    let (dir, name) = match relative_path.rsplit_once('/') {
+        Some((dir, name)) => (format!("{}/{}", sparse_path, dir), name),
+        None => (sparse_path.to_string(), relative_path),
+    };
+
+    // A folder that doesn't exist at the commit means the file doesn't either
+    let dir_sha = match get_subtree_sha(&client, commit_sha, &dir, token).await {
+        Ok(sha) => sha,
+        Err(e) if e.starts_with("Path component") => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    let tree = fetch_tree(&client, &dir_sha, false, token).await?;
+    Ok(tree
+        .tree
+        .into_iter()
+        .find(|e| e.path == name && e.entry_type == "blob")
+        .map(|e| e.sha))
 }
 
 /// GitHub Compare API file limit
@@ -511,7 +854,7 @@ const GITHUB_COMPARE_FILE_LIMIT: usize = 300;
 
 /// Fetch changed files between two commits using compare API
 /// Returns (files, is_truncated) - truncated if exactly 300 files returned
-async fn fetch_changed_files(
+pub(crate) async fn fetch_changed_files(
     base_sha: &str,
     head_sha: &str,
     token: &Option<String>,
@@ -540,39 +883,104 @@ async fn fetch_changed_files(
         .await
         .map_err(|e| format!("Failed to parse compare response: {}", e))?;
 
+    // Changes since the base only lead to the head when the head is ahead of it
+    if matches!(compare.status.as_deref(), Some("behind") | Some("diverged")) {
+        return Err(format!("NOT_AHEAD: {} is not ahead of {}", head_sha, base_sha));
+    }
+
     let files = compare.files.unwrap_or_default();
     let is_truncated = files.len() >= GITHUB_COMPARE_FILE_LIMIT;
 
     Ok((files, is_truncated))
 }
 
+/// Changes to the files of a sparse path between two commits, from their trees
+/// Works whatever the commits' relation (e.g. after switching channels) but finds no renames
+pub(crate) async fn diff_trees(
+    sparse_path: &str,
+    base_sha: &str,
+    head_sha: &str,
+    token: &Option<String>,
+) -> Result<Vec<CompareFile>, String> {
+    let base_files = fetch_github_tree_at(sparse_path, base_sha, token).await?;
+    let head_files = fetch_github_tree_at(sparse_path, head_sha, token).await?;
+    let change = |path: &str, status: &str, sha: Option<&String>| CompareFile {
+        filename: format!("{}/{}", sparse_path, path),
+        status: status.to_string(),
+        previous_filename: None,
+        sha: sha.cloned(),
+    };
+
+    let mut changes: Vec<CompareFile> = head_files
+        .iter()
+        .filter_map(|(path, sha)| match base_files.get(path) {
+            None => Some(change(path, "added", Some(sha))),
+            Some(base_sha) if base_sha != sha => Some(change(path, "modified", Some(sha))),
+            Some(_) => None,
+        })
+        .collect();
+    changes.extend(
+        base_files
+            .keys()
+            .filter(|path| !head_files.contains_key(*path))
+            .map(|path| change(path, "removed", None)),
+    );
+    changes.sort_by(|a, b| a.filename.cmp(&b.filename));
+    Ok(changes)
+}
+
+/// Folder name of a serial folder path, for messages
+fn folder_name(slus_path: &Path) -> String {
+    slus_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| SLUS_FOLDER.to_string())
+}
+
+/// Conflicts of one target folder, for the sync result
+fn target_conflicts(target: &PackTarget, conflicts: &[String]) -> Vec<DisabledConflict> {
+    conflicts
+        .iter()
+        .map(|path| DisabledConflict { serial: target.folder.clone(), path: path.clone() })
+        .collect()
+}
+
+/// Paths of one target folder relative to the textures folder, for the sync result
+fn target_paths(target: &PackTarget, paths: &[String]) -> Vec<String> {
+    paths.iter().map(|path| format!("{}/{}", target.folder, path)).collect()
+}
+
+/// Rescued paths relative to the textures folder, for the sync result
+fn rescued_paths(target: &PackTarget, files_to_rescue: &[SyncMove]) -> Vec<String> {
+    files_to_rescue.iter().map(|rescue| format!("{}/{}", target.folder, rescue.to)).collect()
+}
+
 /// Build a map of local files (relative_path -> sha)
-fn build_local_file_map(textures_dir: &Path) -> Result<HashMap<String, String>, String> {
-    let slus_path = textures_dir.join(SLUS_FOLDER);
+fn build_local_file_map(slus_path: &Path, modules: &ModuleFilter) -> Result<HashMap<String, String>, String> {
     if !slus_path.exists() {
-        return Err(format!("{} folder not found", SLUS_FOLDER));
+        return Err(format!("{} folder not found", folder_name(slus_path)));
     }
 
     let mut file_map: HashMap<String, String> = HashMap::new();
-    build_local_file_map_recursive(&slus_path, &slus_path, &mut file_map)?;
+    build_local_file_map_recursive(slus_path, slus_path, modules, &mut file_map)?;
     Ok(file_map)
 }
 
 /// Count local files quickly (no SHA computation)
-fn count_local_files(textures_dir: &Path) -> Result<usize, String> {
-    let slus_path = textures_dir.join(SLUS_FOLDER);
+pub(crate) fn count_local_files(slus_path: &Path, modules: &ModuleFilter) -> Result<usize, String> {
     if !slus_path.exists() {
-        return Err(format!("{} folder not found", SLUS_FOLDER));
+        return Err(format!("{} folder not found", folder_name(slus_path)));
     }
 
     let mut count = 0;
-    count_local_files_recursive(&slus_path, &slus_path, &mut count)?;
+    count_local_files_recursive(slus_path, slus_path, modules, &mut count)?;
     Ok(count)
 }
 
 fn count_local_files_recursive(
     base_path: &Path,
     current_path: &Path,
+    modules: &ModuleFilter,
     count: &mut usize,
 ) -> Result<(), String> {
     let entries = fs::read_dir(current_path)
@@ -590,7 +998,7 @@ fn count_local_files_recursive(
         }
 
         if path.is_dir() {
-            count_local_files_recursive(base_path, &path, count)?;
+            count_local_files_recursive(base_path, &path, modules, count)?;
         } else if path.is_file() {
             let relative_path = path
                 .strip_prefix(base_path)
@@ -600,8 +1008,8 @@ fn count_local_files_recursive(
 
             let relative_path = relative_path.replace('\\', "/");
 
-            // Skip user-customs
-            if should_skip_path(&relative_path) {
+            // Skip user-customs and unselected modules
+            if should_skip_path(&relative_path) || modules.excludes(&relative_path) {
                 continue;
             }
 
@@ -615,6 +1023,7 @@ fn count_local_files_recursive(
 fn build_local_file_map_recursive(
     base_path: &Path,
     current_path: &Path,
+    modules: &ModuleFilter,
     file_map: &mut HashMap<String, String>,
 ) -> Result<(), String> {
     let entries = fs::read_dir(current_path)
@@ -632,7 +1041,7 @@ fn build_local_file_map_recursive(
         }
 
         if path.is_dir() {
-            build_local_file_map_recursive(base_path, &path, file_map)?;
+            build_local_file_map_recursive(base_path, &path, modules, file_map)?;
         } else if path.is_file() {
             let relative_path = path
                 .strip_prefix(base_path)
@@ -643,8 +1052,8 @@ fn build_local_file_map_recursive(
             // Use forward slashes for consistency
             let relative_path = relative_path.replace('\\', "/");
 
-            // Skip user-customs
-            if should_skip_path(&relative_path) {
+            // Skip user-customs and unselected modules
+            if should_skip_path(&relative_path) || modules.excludes(&relative_path) {
                 continue;
             }
 
@@ -656,16 +1065,19 @@ fn build_local_file_map_recursive(
     Ok(())
 }
 
-/// Download a file from GitHub raw content
-async fn download_file(
+/// Download a file (relative to a sparse path) as it is at a commit
+/// Pass the analyzed commit SHA rather than a branch, so a push during the sync can't mix in newer files
+pub(crate) async fn download_file_at(
     client: &Client,
+    commit_sha: &str,
+    sparse_path: &str,
     relative_path: &str,
     dest_path: &Path,
     token: &Option<String>,
 ) -> Result<(), String> {
     let url = format!(
-        "https://raw.githubusercontent.com/{}/{}/main/{}/{}",
-        REPO_OWNER, REPO_NAME, SPARSE_PATH, relative_path
+        "https://raw.githubusercontent.com/{}/{}/{}/{}/{}",
+        REPO_OWNER, REPO_NAME, commit_sha, sparse_path, relative_path
     );
 
     let mut req = client
@@ -700,14 +1112,23 @@ async fn download_file(
             .map_err(|e| format!("Failed to create directory: {}", e))?;
     }
 
-    fs::write(dest_path, &bytes).map_err(|e| format!("Failed to write file: {}", e))?;
+    // Write to a .part file first so an interrupted download never looks complete
+    let mut partial_name = dest_path.as_os_str().to_owned();
+    partial_name.push(PARTIAL_SUFFIX);
+    let partial_path = PathBuf::from(partial_name);
+
+    fs::write(&partial_path, &bytes).map_err(|e| format!("Failed to write file: {}", e))?;
+    fs::rename(&partial_path, dest_path).map_err(|e| {
+        let _ = fs::remove_file(&partial_path);
+        format!("Failed to write file: {}", e)
+    })?;
 
     Ok(())
 }
 
 /// Check if a local file exists (either normal or disabled version)
 /// Returns (exists, is_disabled, actual_path)
-fn find_local_file(slus_path: &Path, relative_path: &str) -> (bool, bool, PathBuf) {
+pub(crate) fn find_local_file(slus_path: &Path, relative_path: &str) -> (bool, bool, PathBuf) {
     let normal_path = slus_path.join(relative_path);
     if normal_path.exists() {
         return (true, false, normal_path);
@@ -721,433 +1142,388 @@ fn find_local_file(slus_path: &Path, relative_path: &str) -> (bool, bool, PathBu
     (false, false, normal_path)
 }
 
-/// Run incremental sync (only changes since last sync)
-async fn run_incremental_sync(
-    textures_dir: &str,
-    last_commit: &str,
-    token: &Option<String>,
-    window: &Window,
-) -> Result<SyncResult, String> {
-    let textures_path = PathBuf::from(textures_dir);
-    let slus_path = textures_path.join(SLUS_FOLDER);
-    let client = Client::new();
+/// Turn "delete old path" + "add new path" pairs with the same blob SHA into local moves
+/// Matched entries are removed from `files_to_add` and `files_to_delete`
+fn detect_moves(
+    local_files: &HashMap<String, String>,
+    remote_files: &HashMap<String, String>,
+    files_to_add: &mut Vec<SyncFile>,
+    files_to_delete: &mut Vec<String>,
+) -> Vec<SyncMove> {
+    // Candidates by SHA; a disabled duplicate of a local enabled file is deleted, not moved
+    let mut candidates: HashMap<&str, Vec<String>> = HashMap::new();
+    for path in files_to_delete.iter() {
+        let duplicate = get_enabled_path(path).is_some_and(|p| local_files.contains_key(&p));
+        if let (Some(sha), false) = (local_files.get(path), duplicate) {
+            candidates.entry(sha.as_str()).or_default().push(path.clone());
+        }
+    }
+    if candidates.is_empty() {
+        return Vec::new();
+    }
+    for paths in candidates.values_mut() {
+        paths.sort();
+    }
 
-    let _ = window.emit("sync-progress", SyncProgressPayload {
-        stage: "fetching".to_string(),
-        message: "Fetching changes since last sync...".to_string(),
-        current: None,
-        total: None,
+    let mut moves = Vec::new();
+    files_to_add.retain(|file| {
+        let Some(paths) = remote_files.get(&file.path).and_then(|sha| candidates.get_mut(sha.as_str())) else {
+            return true;
+        };
+        if paths.is_empty() {
+            return true;
+        }
+
+        // Prefer a candidate with the same file name (a folder reorganization)
+        let new_name = get_filename(&file.path);
+        let index = paths
+            .iter()
+            .position(|p| {
+                let name = get_filename(p);
+                name == new_name || name.strip_prefix('-') == Some(new_name)
+            })
+            .unwrap_or(0);
+
+        let from = paths.remove(index);
+        let to_disabled = is_disabled_filename(get_filename(&from));
+        moves.push(SyncMove { from, to: file.path.clone(), to_disabled });
+        false
     });
 
-    // Get latest commit
-    let latest_sha = get_latest_commit_with_token(token).await?;
+    let moved: HashSet<&str> = moves.iter().map(|m| m.from.as_str()).collect();
+    files_to_delete.retain(|path| !moved.contains(path.as_str()));
+    moves
+}
 
-    if latest_sha == last_commit {
-        let _ = window.emit("sync-progress", SyncProgressPayload {
-            stage: "complete".to_string(),
-            message: "Already up to date!".to_string(),
-            current: None,
-            total: None,
-        });
-        return Ok(SyncResult {
-            files_downloaded: 0,
-            files_deleted: 0,
-            files_renamed: 0,
-            files_skipped: 0,
-            new_commit_sha: latest_sha,
-        });
+/// Changes an incremental sync would make to one target folder
+pub(crate) struct IncrementalPlan {
+    pub analysis: TargetAnalysis,
+    policy: ConflictPolicy,
+    pub skipped: Vec<SkippedFile>,
+    /// SHAs of the local files the plan touches
+    pub local_shas: HashMap<String, String>,
+    /// SHAs of the repo files the plan writes
+    pub remote_shas: HashMap<String, String>,
+}
+
+impl IncrementalPlan {
+    /// Remember the SHA of a local file the plan touches
+    fn record_local(&mut self, slus_path: &Path, local_path: &str) -> Result<(), String> {
+        let sha = compute_git_blob_sha(&slus_path.join(local_path))?;
+        self.local_shas.insert(local_path.to_string(), sha);
+        Ok(())
     }
 
-    // Get changed files
-    let (changed_files, is_truncated) = fetch_changed_files(last_commit, &latest_sha, token).await?;
+    /// Download a repo file, keeping a local disabled copy disabled
+    /// `disabled_if_new` writes a file that doesn't exist locally as its disabled version
+    fn download(
+        &mut self,
+        slus_path: &Path,
+        file: &CompareFile,
+        relative_path: &str,
+        disabled_if_new: bool,
+    ) -> Result<(), String> {
+        if let Some(sha) = &file.sha {
+            self.remote_shas.insert(relative_path.to_string(), sha.clone());
+        }
 
-    // If the response is truncated (300+ files), fall back to full sync
-    if is_truncated {
-        return Err("TRUNCATED: Too many changed files, falling back to full sync".to_string());
+        match self.local_copy(slus_path, relative_path)? {
+            LocalCopy::Found(local_path, is_disabled) => {
+                self.record_local(slus_path, &local_path)?;
+                self.analysis.files_to_replace.push(SyncFile {
+                    path: relative_path.to_string(),
+                    to_disabled: is_disabled,
+                    sha: file.sha.clone(),
+                });
+            }
+            LocalCopy::Missing => self.analysis.files_to_add.push(SyncFile {
+                path: relative_path.to_string(),
+                to_disabled: disabled_if_new,
+                sha: file.sha.clone(),
+            }),
+            LocalCopy::Unresolved => self.skip_conflict(file),
+        }
+        Ok(())
     }
 
-    // Filter to only files in our sparse path
-    let prefix = format!("{}/", SPARSE_PATH);
-    let relevant_files: Vec<CompareFile> = changed_files
-        .into_iter()
-        .filter(|f| f.filename.starts_with(&prefix) && !should_skip_path(&f.filename))
-        .collect();
+    /// Delete a local file (both its normal and disabled version, if they exist)
+    fn delete(&mut self, slus_path: &Path, relative_path: &str) -> Result<(), String> {
+        for local_path in [relative_path.to_string(), get_disabled_path(relative_path)] {
+            if slus_path.join(&local_path).exists() {
+                self.record_local(slus_path, &local_path)?;
+                self.analysis.files_to_delete.push(local_path);
+            }
+        }
+        Ok(())
+    }
 
-    let total = relevant_files.len() as u32;
-    let _ = window.emit("sync-progress", SyncProgressPayload {
-        stage: "comparing".to_string(),
-        message: format!("Found {} changed files", total),
-        current: None,
-        total: None,
-    });
+    /// Local copy of a repo file (its disabled version if that is what exists)
+    /// When both copies exist, the conflict policy picks one and the other is deleted
+    fn local_copy(&mut self, slus_path: &Path, relative_path: &str) -> Result<LocalCopy, String> {
+        let disabled_path = get_disabled_path(relative_path);
+        match (slus_path.join(relative_path).exists(), slus_path.join(&disabled_path).exists()) {
+            (false, false) => Ok(LocalCopy::Missing),
+            (true, false) => Ok(LocalCopy::Found(relative_path.to_string(), false)),
+            (false, true) => Ok(LocalCopy::Found(disabled_path, true)),
+            (true, true) => {
+                self.analysis.conflicts.push(relative_path.to_string());
+                let Some(keep_disabled) = self.policy.keeps_disabled() else {
+                    return Ok(LocalCopy::Unresolved);
+                };
+                let (kept, losing) = if keep_disabled {
+                    (disabled_path, relative_path.to_string())
+                } else {
+                    (relative_path.to_string(), disabled_path)
+                };
+                self.record_local(slus_path, &losing)?;
+                self.analysis.files_to_delete.push(losing);
+                Ok(LocalCopy::Found(kept, keep_disabled))
+            }
+        }
+    }
 
-    let mut downloaded: u32 = 0;
-    let mut deleted: u32 = 0;
-    let mut renamed: u32 = 0;
-    let mut skipped: u32 = 0;
+    /// Leave a change to a file with both copies until the user picks one
+    fn skip_conflict(&mut self, file: &CompareFile) {
+        self.skipped.push(SkippedFile {
+            path: file.filename.clone(),
+            reason: "Both the enabled and the disabled copy exist locally; choose which one to keep".to_string(),
+            violation: None,
+        });
+    }
+}
 
-    for (i, file) in relevant_files.iter().enumerate() {
-        let relative_path = file.filename.strip_prefix(&prefix).unwrap().to_string();
+/// Local copy of a repo file an incremental change works on
+enum LocalCopy {
+    Missing,
+    /// Local path, and whether it is the disabled version
+    Found(String, bool),
+    /// Both copies exist and the user decides which one to keep
+    Unresolved,
+}
 
-        let _ = window.emit("sync-progress", SyncProgressPayload {
-            stage: "syncing".to_string(),
-            message: format!("[{}] {}", file.status, relative_path),
-            current: Some(i as u32 + 1),
-            total: Some(total),
-        });
+/// Turn compare API changes into the adds, replacements, moves and deletions for one target folder
+pub(crate) fn plan_incremental_changes(
+    slus_path: &Path,
+    target: &PackTarget,
+    changed_files: &[CompareFile],
+    modules: &ModuleFilter,
+    policy: ConflictPolicy,
+) -> Result<IncrementalPlan, String> {
+    // Path relative to the target folder, if the file is part of what we sync
+    let prefix = format!("{}/", target.sparse_path);
+    let relevant_path = |filename: &str| -> Option<String> {
+        filename
+            .strip_prefix(&prefix)
+            .filter(|rel| !should_skip_path(rel) && !modules.excludes(rel))
+            .map(|rel| rel.to_string())
+    };
 
-        match file.status.as_str() {
-            "added" | "modified" => {
-                // Check if we have a disabled version locally
-                let (exists, is_disabled, local_path) = find_local_file(&slus_path, &relative_path);
+    let mut plan = IncrementalPlan {
+        analysis: TargetAnalysis {
+            serial: target.folder.clone(),
+            files_to_add: Vec::new(),
+            files_to_replace: Vec::new(),
+            files_to_delete: Vec::new(),
+            files_to_move: Vec::new(),
+            files_to_rescue: Vec::new(),
+            conflicts: Vec::new(),
+            held: Vec::new(),
+        },
+        policy,
+        skipped: Vec::new(),
+        local_shas: HashMap::new(),
+        remote_shas: HashMap::new(),
+    };
 
-                if exists && is_disabled {
-                    // Download to the disabled path (preserve disabled state)
-                    let disabled_rel_path = get_disabled_path(&relative_path);
-                    let dest = slus_path.join(&disabled_rel_path);
-                    download_file(&client, &relative_path, &dest, token).await?;
-                } else {
-                    // Download to normal path
-                    download_file(&client, &relative_path, &local_path, token).await?;
-                }
-                downloaded += 1;
-            }
-            "removed" => {
-                // Delete the file (check both normal and disabled versions)
-                let (exists, _, local_path) = find_local_file(&slus_path, &relative_path);
-                if exists {
-                    fs::remove_file(&local_path)
-                        .map_err(|e| format!("Failed to delete {}: {}", relative_path, e))?;
-                    deleted += 1;
-
-                    // Try to remove empty parent directories
-                    if let Some(parent) = local_path.parent() {
-                        let _ = fs::remove_dir(parent);
-                    }
-                }
+    for file in changed_files {
+        // A file at a path that isn't safe is left alone (a rename from one is handled like an addition)
+        let mut safe_path = |relative_path: Option<String>| match relative_path.as_deref().map(validate_repo_path) {
+            Some(Err(e)) => {
+                plan.skipped.push(SkippedFile::unsafe_path(&target.sparse_path, e));
+                None
             }
-            "renamed" => {
-                if let Some(old_filename) = &file.previous_filename {
-                    if old_filename.starts_with(&prefix) {
-                        let old_rel_path = old_filename.strip_prefix(&prefix).unwrap();
-                        let (exists, is_disabled, old_local_path) = find_local_file(&slus_path, old_rel_path);
-
-                        if exists {
-                            // Determine new path (preserve disabled state)
-                            let new_local_path = if is_disabled {
-                                slus_path.join(get_disabled_path(&relative_path))
-                            } else {
-                                slus_path.join(&relative_path)
-                            };
-
-                            // Ensure parent directory exists
-                            if let Some(parent) = new_local_path.parent() {
-                                fs::create_dir_all(parent)
-                                    .map_err(|e| format!("Failed to create directory: {}", e))?;
-                            }
-
-                            // Move the file
-                            fs::rename(&old_local_path, &new_local_path)
-                                .map_err(|e| format!("Failed to rename {}: {}", old_rel_path, e))?;
-                            renamed += 1;
+            _ => relative_path,
+        };
+        let new_rel = safe_path(relevant_path(&file.filename));
+        let old_rel = safe_path(file.previous_filename.as_deref().and_then(relevant_path));
 
-                            // Try to remove empty old parent directories
-                            if let Some(parent) = old_local_path.parent() {
-                                let _ = fs::remove_dir(parent);
+        match (file.status.as_str(), new_rel, old_rel) {
+            // "changed" is a mode/type change, "copied" a new file copied from another path
+            ("added" | "modified" | "changed" | "copied", Some(relative_path), _) => {
+                plan.download(slus_path, file, &relative_path, false)?;
+            }
+            ("removed", Some(relative_path), _) => {
+                plan.delete(slus_path, &relative_path)?;
+            }
+            ("renamed", Some(relative_path), Some(old_rel_path)) => {
+                let old_local = plan.local_copy(slus_path, &old_rel_path)?;
+                let new_exists = find_local_file(slus_path, &relative_path).0;
+
+                match old_local {
+                    LocalCopy::Found(from, is_disabled) if !new_exists => {
+                        let expected_sha = file.sha.as_deref();
+                        let local_sha = compute_git_blob_sha_with_normalization(&slus_path.join(&from), expected_sha)?;
+                        plan.record_local(slus_path, &from)?;
+
+                        if expected_sha.is_none_or(|sha| sha == local_sha) {
+                            // Pure rename: move the file (preserve disabled state)
+                            if let Some(sha) = expected_sha {
+                                plan.remote_shas.insert(relative_path.clone(), sha.to_string());
                             }
+                            plan.analysis.files_to_move.push(SyncMove {
+                                from,
+                                to: relative_path,
+                                to_disabled: is_disabled,
+                            });
                         } else {
-                            // Old file doesn't exist locally, download the new one
-                            let dest = slus_path.join(&relative_path);
-                            download_file(&client, &relative_path, &dest, token).await?;
-                            downloaded += 1;
+                            // Renamed and modified: download the new content (preserve disabled state)
+                            plan.download(slus_path, file, &relative_path, is_disabled)?;
+                            plan.analysis.files_to_delete.push(from);
                         }
                     }
+                    LocalCopy::Found(from, _) => {
+                        // The new path already exists locally, so the old file just goes away
+                        plan.download(slus_path, file, &relative_path, false)?;
+                        plan.record_local(slus_path, &from)?;
+                        plan.analysis.files_to_delete.push(from);
+                    }
+                    LocalCopy::Missing => {
+                        // Old file doesn't exist locally, download the new one
+                        plan.download(slus_path, file, &relative_path, false)?;
+                    }
+                    LocalCopy::Unresolved => plan.skip_conflict(file),
                 }
             }
-            _ => {
-                skipped += 1;
+            // Moved out of the pack (or into user-customs / an unselected module)
+            ("renamed", None, Some(old_rel_path)) => {
+                plan.delete(slus_path, &old_rel_path)?;
+            }
+            // Moved into the pack, or a rename without a previous name
+            ("renamed", Some(relative_path), None) => {
+                plan.download(slus_path, file, &relative_path, false)?;
+            }
+            // Not part of this target folder
+            (_, None, _) => {}
+            ("unchanged", _, _) => {
+                plan.skipped.push(SkippedFile {
+                    path: file.filename.clone(),
+                    reason: "Unchanged between the two commits".to_string(),
+                    violation: None,
+                });
+            }
+            (status, _, _) => {
+                plan.skipped.push(SkippedFile {
+                    path: file.filename.clone(),
+                    reason: format!("Unsupported change status \"{}\"", status),
+                    violation: None,
+                });
             }
         }
     }
 
+    Ok(plan)
+}
+
+/// Plan an incremental sync of one target folder (only changes since last sync)
+/// Returns the analysis and the changes that were skipped
+async fn plan_incremental_sync(
+    target: &PackTarget,
+    slus_path: &Path,
+    last_commit: &str,
+    latest_sha: &str,
+    token: &Option<String>,
+    modules: &ModuleFilter,
+    window: &Window,
+) -> Result<(TargetAnalysis, Vec<SkippedFile>), String> {
     let _ = window.emit("sync-progress", SyncProgressPayload {
-        stage: "complete".to_string(),
-        message: format!(
-            "Sync complete! Downloaded: {}, Deleted: {}, Renamed: {}, Skipped: {}",
-            downloaded, deleted, renamed, skipped
-        ),
+        stage: "fetching".to_string(),
+        message: "Fetching changes since last sync...".to_string(),
         current: None,
         total: None,
     });
 
-    Ok(SyncResult {
-        files_downloaded: downloaded,
-        files_deleted: deleted,
-        files_renamed: renamed,
-        files_skipped: skipped,
-        new_commit_sha: latest_sha,
-    })
+    // Get changed files
+    let changed_files = if latest_sha == last_commit {
+        Vec::new()
+    } else {
+        match fetch_changed_files(last_commit, latest_sha, token).await {
+            // If the response is truncated (300+ files), fall back to full sync
+            Ok((_, true)) => return Err("TRUNCATED: Too many changed files, falling back to full sync".to_string()),
+            Ok((files, false)) => files,
+            // The sync ref moved to a commit that isn't ahead (e.g. another channel): compare the trees
+            Err(e) if e.starts_with("NOT_AHEAD") => diff_trees(&target.sparse_path, last_commit, latest_sha, token).await?,
+            Err(e) => return Err(e),
+        }
+    };
+
+    let policy = load_conflict_policy(window.app_handle())?;
+    let plan = plan_incremental_changes(slus_path, target, &changed_files, modules, policy)?;
+    let mut analysis = plan.analysis;
+    // Held files are left alone
+    analysis.held = HoldList::load(window.app_handle(), &target.folder)?.take_held(&mut analysis);
+    Ok((analysis, plan.skipped))
 }
 
-/// Run full sync (compare all files)
-async fn run_full_sync(
-    textures_dir: &str,
-    token: &Option<String>,
-    window: &Window,
-) -> Result<SyncResult, String> {
-    let textures_path = PathBuf::from(textures_dir);
-    let slus_path = textures_path.join(SLUS_FOLDER);
+/// Run post-sync verification scan to find discrepancies (does NOT fix them)
+/// `serial` selects an additional serial folder with its own pack (defaults to the SLUS folder)
+#[tauri::command]
+pub async fn run_verification_scan(
+    textures_dir: String,
+    github_token: Option<String>,
+    serial: Option<String>,
+    window: Window,
+) -> Result<VerificationResult, String> {
+    let textures_path = PathBuf::from(&textures_dir);
+    let target = resolve_target(window.app_handle(), serial)?;
+    let slus_path = textures_path.join(&target.folder);
 
     let _ = window.emit("sync-progress", SyncProgressPayload {
-        stage: "fetching".to_string(),
-        message: "Fetching repository tree (this may take a while)...".to_string(),
+        stage: "verifying".to_string(),
+        message: "Fetching repository file list...".to_string(),
         current: None,
         total: None,
     });
 
-    // Fetch GitHub tree
-    let (remote_files, commit_sha) = fetch_github_tree(token).await?;
-    // Count excluding user-customs and hidden files for accurate comparison
-    let remote_count = remote_files.keys().filter(|p| !should_skip_path(p)).count();
+    // Fetch full repo tree (only selected modules)
+    let modules = if target.is_primary() {
+        load_module_filter(window.app_handle()).await?
+    } else {
+        ModuleFilter::default()
+    };
+    let (mut remote_files, commit_sha) = fetch_github_tree(window.app_handle(), &target.sparse_path, &github_token).await?;
+    remote_files.retain(|path, _| !modules.excludes(path));
+
+    // Count remote files excluding user-customs and hidden files
+    let remote_file_count = remote_files.keys().filter(|p| !should_skip_path(p)).count();
 
     let _ = window.emit("sync-progress", SyncProgressPayload {
-        stage: "scanning".to_string(),
-        message: format!("Found {} files in repository", remote_count),
+        stage: "verifying".to_string(),
+        message: format!("Scanning local files and computing hashes (this may take a few minutes)..."),
         current: None,
         total: None,
     });
 
-    // Build local file map
+    // Build local file map (with hashes)
+    let local_files = build_local_file_map(&slus_path, &modules)?;
+    let conflicts = DisabledConflicts::find(load_conflict_policy(window.app_handle())?, &local_files, &remote_files);
+
     let _ = window.emit("sync-progress", SyncProgressPayload {
-        stage: "scanning".to_string(),
-        message: "Scanning local files (this may take a few minutes)...".to_string(),
-        current: None,
-        total: None,
-    });
-
-    let local_files = build_local_file_map(&textures_path)?;
-
-    let _ = window.emit("sync-progress", SyncProgressPayload {
-        stage: "scanning".to_string(),
-        message: format!("Found {} local files (excluding user-customs)...", local_files.len()),
-        current: None,
-        total: None,
-    });
-
-    let _ = window.emit("sync-progress", SyncProgressPayload {
-        stage: "comparing".to_string(),
-        message: "Comparing file hashes...".to_string(),
-        current: None,
-        total: None,
-    });
-
-    // Determine files to download (new or modified)
-    let mut files_to_download: Vec<(String, bool)> = Vec::new(); // (path, is_disabled)
-    let total_to_compare = remote_files.len();
-    let mut compared = 0;
-
-    for (path, remote_sha) in &remote_files {
-        // Emit progress every 1000 files
-        compared += 1;
-        if compared % 1000 == 0 {
-            let percent = (compared * 100) / total_to_compare;
-            let _ = window.emit("sync-progress", SyncProgressPayload {
-                stage: "comparing".to_string(),
-                message: format!("Comparing file hashes ({}/{}) {}%...", compared, total_to_compare, percent),
-                current: Some(compared as u32),
-                total: Some(total_to_compare as u32),
-            });
-        }
-        if should_skip_path(path) {
-            continue;
-        }
-
-        // Check normal path
-        if local_files.contains_key(path) {
-            // File exists - check SHA with normalization support
-            let local_path = slus_path.join(path);
-            if let Ok(local_sha) = compute_git_blob_sha_with_normalization(&local_path, Some(remote_sha)) {
-                if &local_sha == remote_sha {
-                    continue; // Up to date (raw or normalized match)
-                }
-            }
-            files_to_download.push((path.clone(), false));
-            continue;
-        }
-
-        // Check disabled version
-        let disabled_path = get_disabled_path(path);
-        if local_files.contains_key(&disabled_path) {
-            // Disabled file exists - check SHA with normalization support
-            let local_path = slus_path.join(&disabled_path);
-            if let Ok(local_sha) = compute_git_blob_sha_with_normalization(&local_path, Some(remote_sha)) {
-                if &local_sha == remote_sha {
-                    continue; // Up to date (disabled, raw or normalized match)
-                }
-            }
-            files_to_download.push((path.clone(), true)); // Download to disabled path
-            continue;
-        }
-
-        // File doesn't exist locally
-        files_to_download.push((path.clone(), false));
-    }
-
-    // Determine files to delete (in local but not in remote)
-    let mut files_to_delete: Vec<String> = Vec::new();
-
-    for local_path in local_files.keys() {
-        if should_skip_path(local_path) {
-            continue;
-        }
-
-        // First, check if the exact local path exists in remote
-        // (handles files like "-.png" that are actual repo files with dash in name)
-        if remote_files.contains_key(local_path) {
-            continue;
-        }
-
-        // If this looks like a disabled file (dash prefix), check if enabled version exists
-        if is_disabled_filename(get_filename(local_path)) {
-            if let Some(enabled_path) = get_enabled_path(local_path) {
-                // If enabled version exists LOCALLY, delete the disabled version
-                // (having both doesn't make sense - enabled takes precedence)
-                if local_files.contains_key(&enabled_path) {
-                    files_to_delete.push(local_path.clone());
-                    continue;
-                }
-                // If enabled version exists in remote (but not locally), keep disabled version
-                if remote_files.contains_key(&enabled_path) {
-                    continue; // This is a user-disabled version of a repo file
-                }
-            }
-        }
-
-        // File doesn't exist in remote (neither exact path nor enabled version)
-        files_to_delete.push(local_path.clone());
-    }
-
-    let download_count = files_to_download.len() as u32;
-    let delete_count = files_to_delete.len() as u32;
-
-    let _ = window.emit("sync-progress", SyncProgressPayload {
-        stage: "comparing".to_string(),
-        message: format!("Changes: {} to download, {} to delete", download_count, delete_count),
-        current: None,
-        total: None,
-    });
-
-    // Download files
-    let client = Client::new();
-    let mut downloaded: u32 = 0;
-
-    for (i, (path, is_disabled)) in files_to_download.iter().enumerate() {
-        let _ = window.emit("sync-progress", SyncProgressPayload {
-            stage: "downloading".to_string(),
-            message: format!("Downloading: {}", path),
-            current: Some(i as u32 + 1),
-            total: Some(download_count),
-        });
-
-        let dest_path = if *is_disabled {
-            slus_path.join(get_disabled_path(path))
-        } else {
-            slus_path.join(path)
-        };
-
-        download_file(&client, path, &dest_path, token).await?;
-        downloaded += 1;
-    }
-
-    // Delete files
-    let mut deleted: u32 = 0;
-
-    for (i, path) in files_to_delete.iter().enumerate() {
-        let _ = window.emit("sync-progress", SyncProgressPayload {
-            stage: "deleting".to_string(),
-            message: format!("Deleting: {}", path),
-            current: Some(i as u32 + 1),
-            total: Some(delete_count),
-        });
-
-        let file_path = slus_path.join(path);
-        if file_path.exists() {
-            fs::remove_file(&file_path)
-                .map_err(|e| format!("Failed to delete {}: {}", path, e))?;
-            deleted += 1;
-
-            if let Some(parent) = file_path.parent() {
-                let _ = fs::remove_dir(parent);
-            }
-        }
-    }
-
-    let _ = window.emit("sync-progress", SyncProgressPayload {
-        stage: "complete".to_string(),
-        message: format!("Sync complete! Downloaded: {}, Deleted: {}", downloaded, deleted),
-        current: None,
-        total: None,
-    });
-
-    Ok(SyncResult {
-        files_downloaded: downloaded,
-        files_deleted: deleted,
-        files_renamed: 0,
-        files_skipped: 0,
-        new_commit_sha: commit_sha,
-    })
-}
-
-/// Run post-sync verification scan to find discrepancies (does NOT fix them)
-#[tauri::command]
-pub async fn run_verification_scan(
-    textures_dir: String,
-    github_token: Option<String>,
-    window: Window,
-) -> Result<VerificationResult, String> {
-    let textures_path = PathBuf::from(&textures_dir);
-
-    let _ = window.emit("sync-progress", SyncProgressPayload {
-        stage: "verifying".to_string(),
-        message: "Fetching repository file list...".to_string(),
-        current: None,
-        total: None,
-    });
-
-    // Fetch full repo tree
-    let (remote_files, _) = fetch_github_tree(&github_token).await?;
-
-    // Count remote files excluding user-customs and hidden files
-    let remote_file_count = remote_files.keys().filter(|p| !should_skip_path(p)).count();
-
-    let _ = window.emit("sync-progress", SyncProgressPayload {
-        stage: "verifying".to_string(),
-        message: format!("Scanning local files and computing hashes (this may take a few minutes)..."),
-        current: None,
-        total: None,
-    });
-
-    // Build local file map (with hashes)
-    let local_files = build_local_file_map(&textures_path)?;
-
-    let _ = window.emit("sync-progress", SyncProgressPayload {
-        stage: "verifying".to_string(),
-        message: format!("Comparing {} local files against {} repo files (this may take a few minutes)...", local_files.len(), remote_file_count),
+        stage: "verifying".to_string(),
+        message: format!("Comparing {} local files against {} repo files (this may take a few minutes)...", local_files.len(), remote_file_count),
         current: None,
         total: None,
     });
 
     // Find files that need to be downloaded (missing or hash mismatch)
     let mut files_to_download: Vec<VerificationFile> = Vec::new();
-    let slus_path = textures_path.join(SLUS_FOLDER);
 
     for (repo_path, repo_sha) in &remote_files {
-        if should_skip_path(repo_path) {
+        if should_skip_path(repo_path) || conflicts.is_unresolved(repo_path) {
             continue;
         }
 
-        // Check if normal version exists and matches
-        if local_files.contains_key(repo_path) {
+        // Check if normal version exists and matches (unless the policy keeps the disabled copy of a conflict)
+        if local_files.contains_key(repo_path) && !conflicts.keeps_disabled(repo_path) {
             // File exists - check SHA with normalization support
             let local_path = slus_path.join(repo_path);
             if let Ok(local_sha) = compute_git_blob_sha_with_normalization(&local_path, Some(repo_sha)) {
@@ -1159,6 +1535,7 @@ pub async fn run_verification_scan(
             files_to_download.push(VerificationFile {
                 path: repo_path.clone(),
                 to_disabled: false,
+                sha: Some(repo_sha.clone()),
             });
             continue;
         }
@@ -1177,6 +1554,7 @@ pub async fn run_verification_scan(
             files_to_download.push(VerificationFile {
                 path: repo_path.clone(),
                 to_disabled: true,
+                sha: Some(repo_sha.clone()),
             });
             continue;
         }
@@ -1185,6 +1563,7 @@ pub async fn run_verification_scan(
         files_to_download.push(VerificationFile {
             path: repo_path.clone(),
             to_disabled: false,
+            sha: Some(repo_sha.clone()),
         });
     }
 
@@ -1192,7 +1571,8 @@ pub async fn run_verification_scan(
     let mut files_to_delete: Vec<String> = Vec::new();
 
     for local_path in local_files.keys() {
-        if should_skip_path(local_path) {
+        // Both copies of a conflict are handled by the conflict policy
+        if should_skip_path(local_path) || conflicts.involves(local_path) {
             continue;
         }
 
@@ -1214,8 +1594,16 @@ pub async fn run_verification_scan(
         // File doesn't exist in remote (neither exact path nor enabled version)
         files_to_delete.push(local_path.clone());
     }
+    files_to_delete.extend(conflicts.losing_copies());
+    let conflicts = conflicts.paths();
+
+    // Held files are reported as held, not as discrepancies
+    let holds = HoldList::load(window.app_handle(), &target.folder)?;
+    let mut held = Vec::new();
+    holds.retain_unheld(&mut files_to_download, |f| &f.path, &mut held);
+    holds.retain_unheld(&mut files_to_delete, |p| p, &mut held);
 
-    let has_discrepancies = !files_to_download.is_empty() || !files_to_delete.is_empty();
+    let has_discrepancies = !files_to_download.is_empty() || !files_to_delete.is_empty() || !conflicts.is_empty();
 
     let _ = window.emit("sync-progress", SyncProgressPayload {
         stage: "verifying".to_string(),
@@ -1231,160 +1619,376 @@ pub async fn run_verification_scan(
     Ok(VerificationResult {
         files_to_download,
         files_to_delete,
+        conflicts,
+        held,
         has_discrepancies,
+        commit_sha,
     })
 }
 
 /// Apply verification fixes after user approval
+/// `commit_sha` is the commit of the scan (None downloads at the current sync ref)
+/// `allow_mass_changes` confirms fixes above the mass-change limit
+/// Fixes that fail are returned and kept for `retry_failed_files`
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn apply_verification_fixes(
     textures_dir: String,
     files_to_download: Vec<VerificationFile>,
     files_to_delete: Vec<String>,
+    commit_sha: Option<String>,
     github_token: Option<String>,
+    serial: Option<String>,
+    allow_mass_changes: Option<bool>,
     window: Window,
-) -> Result<(u32, u32), String> {
+) -> Result<VerificationFixResult, String> {
     let textures_path = PathBuf::from(&textures_dir);
-    let slus_path = textures_path.join(SLUS_FOLDER);
-    let client = Client::new();
+    let target = resolve_target(window.app_handle(), serial)?;
+    let slus_path = textures_path.join(&target.folder);
+    let mut undo = SyncUndo::start(window.app_handle(), &textures_path, "verification")?;
+    let mut guard =
+        MassChangeGuard::load(window.app_handle(), "verification", allow_mass_changes.unwrap_or(false)).await?;
 
-    let mut downloaded: u32 = 0;
-    let mut deleted: u32 = 0;
+    let files_to_download: Vec<SyncFile> = files_to_download
+        .into_iter()
+        .map(|file| SyncFile { path: file.path, to_disabled: file.to_disabled, sha: file.sha })
+        .collect();
+    guard.check(&slus_path, &files_to_download, &files_to_delete, &[])?;
+    let commit_sha = match commit_sha {
+        Some(sha) => sha,
+        None => resolve_sync_commit(window.app_handle(), &github_token).await?,
+    };
 
-    // Download missing/mismatched files
-    if !files_to_download.is_empty() {
-        let total = files_to_download.len() as u32;
+    let _ = window.emit("sync-progress", SyncProgressPayload {
+        stage: "verifying".to_string(),
+        message: format!(
+            "Fixing discrepancies: {} to download, {} to delete...",
+            files_to_download.len(),
+            files_to_delete.len()
+        ),
+        current: None,
+        total: None,
+    });
+
+    let (downloaded, deleted, _, failed) = apply_target_changes(
+        &slus_path,
+        &target.sparse_path,
+        &commit_sha,
+        &files_to_download,
+        &files_to_delete,
+        &[],
+        &[],
+        &github_token,
+        &mut undo,
+        &window,
+    )
+    .await?;
+    undo.finish(None)?;
+    guard.record_completed(None, downloaded, deleted, 0);
+    record_failed_fixes(window.app_handle(), &commit_sha, &failed)?;
+
+    // Clean up empty directories
+    let _ = window.emit("sync-progress", SyncProgressPayload {
+        stage: "verifying".to_string(),
+        message: "Cleaning up empty directories...".to_string(),
+        current: None,
+        total: None,
+    });
+
+    let dirs_removed = cleanup_empty_directories(&slus_path, &window);
+    let _ = window.emit("sync-progress", SyncProgressPayload {
+        stage: "verifying".to_string(),
+        message: format!("Removed {} empty directories", dirs_removed),
+        current: None,
+        total: None,
+    });
+
+    if target.is_primary() {
+        refresh_mirror_folders(window.app_handle(), &textures_path, |message| {
+            let _ = window.emit("sync-progress", SyncProgressPayload {
+                stage: "verifying".to_string(),
+                message,
+                current: None,
+                total: None,
+            });
+        })?;
+    }
+
+    for file in &failed {
         let _ = window.emit("sync-progress", SyncProgressPayload {
             stage: "verifying".to_string(),
-            message: format!("Downloading {} files...", total),
+            message: format!("Failed to fix {}: {}", file.path, file.reason),
             current: None,
             total: None,
         });
+    }
 
-        for (i, file) in files_to_download.iter().enumerate() {
+    let _ = window.emit("sync-progress", SyncProgressPayload {
+        stage: "complete".to_string(),
+        message: format!(
+            "Verification fixes applied! Downloaded: {}, Deleted: {}, Failed: {}",
+            downloaded,
+            deleted,
+            failed.len()
+        ),
+        current: None,
+        total: None,
+    });
+
+    Ok(VerificationFixResult { files_downloaded: downloaded, files_deleted: deleted, failed })
+}
+
+/// Plan the sync of one target folder, falling back to a full sync when incremental isn't possible
+#[allow(clippy::too_many_arguments)]
+async fn plan_target_sync(
+    textures_path: &Path,
+    target: &PackTarget,
+    last_sync_commit: &Option<String>,
+    commit_sha: &str,
+    full_sync: bool,
+    github_token: &Option<String>,
+    modules: &ModuleFilter,
+    window: &Window,
+) -> Result<(TargetAnalysis, Vec<SkippedFile>), String> {
+    // A newly configured serial folder has nothing to apply changes to yet
+    let slus_path = textures_path.join(&target.folder);
+    let is_new_folder = !slus_path.exists();
+    if is_new_folder {
+        fs::create_dir_all(&slus_path)
+            .map_err(|e| format!("Failed to create {}: {}", target.folder, e))?;
+    }
+
+    let full = || async {
+        let (analysis, _, skipped) = analyze_target(textures_path, target, commit_sha, modules, github_token, window).await?;
+        Ok::<_, String>((analysis, skipped))
+    };
+    let last_commit = match last_sync_commit {
+        Some(commit) if !full_sync && !is_new_folder => commit,
+        _ => return full().await,
+    };
+
+    // Try incremental sync, fall back to full sync if it fails (e.g., commit not found or too many changes)
+    match plan_incremental_sync(target, &slus_path, last_commit, commit_sha, github_token, modules, window).await {
+        Ok(r) => Ok(r),
+        Err(e) if e.contains("404") || e.contains("Not Found") => {
             let _ = window.emit("sync-progress", SyncProgressPayload {
-                stage: "verifying".to_string(),
-                message: format!("Downloading: {}", file.path),
-                current: Some(i as u32 + 1),
-                total: Some(total),
+                stage: "fetching".to_string(),
+                message: "Previous sync commit not found, running full sync...".to_string(),
+                current: None,
+                total: None,
             });
+            full().await
+        }
+        Err(e) if e.contains("TRUNCATED") => {
+            let _ = window.emit("sync-progress", SyncProgressPayload {
+                stage: "fetching".to_string(),
+                message: "Too many changes since last sync (300+), running full sync...".to_string(),
+                current: None,
+                total: None,
+            });
+            full().await
+        }
+        Err(e) => Err(e),
+    }
+}
 
-            let dest_path = if file.to_disabled {
-                slus_path.join(get_disabled_path(&file.path))
-            } else {
-                slus_path.join(&file.path)
-            };
+/// Sync the SLUS folder and every serial folder with its own pack to `commit_sha`
+/// Every folder is analyzed and checked against `guard` before any is changed;
+/// changes are recorded in `undo` and mirror folders are not refreshed
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn sync_folders(
+    textures_dir: &str,
+    last_sync_commit: &Option<String>,
+    commit_sha: &str,
+    full_sync: bool,
+    github_token: &Option<String>,
+    undo: &mut SyncUndo,
+    guard: &mut MassChangeGuard,
+    window: &Window,
+) -> Result<SyncResult, String> {
+    let modules = load_module_filter(window.app_handle()).await?;
+    let no_modules = ModuleFilter::default();
+    let textures_path = PathBuf::from(textures_dir);
 
-            download_file(&client, &file.path, &dest_path, &github_token).await?;
-            downloaded += 1;
+    let mut planned: Vec<(PackTarget, Vec<SyncFile>, TargetAnalysis, Vec<SkippedFile>)> = Vec::new();
+    for target in load_pack_targets(window.app_handle())? {
+        if !target.is_primary() {
+            let _ = window.emit("sync-progress", SyncProgressPayload {
+                stage: "fetching".to_string(),
+                message: format!("Analyzing serial folder {}...", target.folder),
+                current: None,
+                total: None,
+            });
         }
+
+        let target_modules = if target.is_primary() { &modules } else { &no_modules };
+        let (mut analysis, skipped) = plan_target_sync(
+            &textures_path,
+            &target,
+            last_sync_commit,
+            commit_sha,
+            full_sync,
+            github_token,
+            target_modules,
+            window,
+        )
+        .await?;
+
+        let mut files_to_download = std::mem::take(&mut analysis.files_to_replace);
+        files_to_download.append(&mut analysis.files_to_add);
+
+        let _ = window.emit("sync-progress", SyncProgressPayload {
+            stage: "comparing".to_string(),
+            message: format!(
+                "Changes: {} to download, {} to move, {} to delete, {} to rescue, {} held",
+                files_to_download.len(),
+                analysis.files_to_move.len(),
+                analysis.files_to_delete.len(),
+                analysis.files_to_rescue.len(),
+                analysis.held.len()
+            ),
+            current: None,
+            total: None,
+        });
+        planned.push((target, files_to_download, analysis, skipped));
+    }
+
+    // Nothing is changed unless every folder is within the limit
+    for (target, files_to_download, analysis, _) in &planned {
+        guard.check(
+            &textures_path.join(&target.folder),
+            files_to_download,
+            &analysis.files_to_delete,
+            &analysis.files_to_rescue,
+        )?;
     }
 
-    // Delete orphaned files
-    if !files_to_delete.is_empty() {
-        let total = files_to_delete.len() as u32;
-        for (i, path) in files_to_delete.iter().enumerate() {
+    let mut result = SyncResult {
+        files_downloaded: 0,
+        files_deleted: 0,
+        files_renamed: 0,
+        files_skipped: 0,
+        new_commit_sha: commit_sha.to_string(),
+        skipped: Vec::new(),
+        rescued: Vec::new(),
+        conflicts: Vec::new(),
+        held: Vec::new(),
+        failed: Vec::new(),
+    };
+
+    for (target, files_to_download, analysis, skipped) in planned {
+        if !target.is_primary() {
             let _ = window.emit("sync-progress", SyncProgressPayload {
-                stage: "verifying".to_string(),
-                message: format!("Deleting: {}", path),
-                current: Some(i as u32 + 1),
-                total: Some(total),
+                stage: "syncing".to_string(),
+                message: format!("Syncing serial folder {}...", target.folder),
+                current: None,
+                total: None,
             });
+        }
 
-            let file_path = slus_path.join(path);
-            if file_path.exists() {
-                fs::remove_file(&file_path)
-                    .map_err(|e| format!("Failed to delete {}: {}", path, e))?;
-                deleted += 1;
+        let slus_path = textures_path.join(&target.folder);
+        let (downloaded, deleted, moved, failed) = apply_target_changes(
+            &slus_path,
+            &target.sparse_path,
+            commit_sha,
+            &files_to_download,
+            &analysis.files_to_delete,
+            &analysis.files_to_move,
+            &analysis.files_to_rescue,
+            github_token,
+            undo,
+            window,
+        )
+        .await?;
 
-                if let Some(parent) = file_path.parent() {
-                    let _ = fs::remove_dir(parent);
-                }
-            }
-        }
-    }
+        let _ = window.emit("sync-progress", SyncProgressPayload {
+            stage: if target.is_primary() { "complete" } else { "syncing" }.to_string(),
+            message: format!(
+                "Sync complete! Downloaded: {}, Deleted: {}, Moved: {}, Skipped: {}",
+                downloaded, deleted, moved, skipped.len()
+            ),
+            current: None,
+            total: None,
+        });
 
-    // Clean up empty directories
-    let _ = window.emit("sync-progress", SyncProgressPayload {
-        stage: "verifying".to_string(),
-        message: "Cleaning up empty directories...".to_string(),
-        current: None,
-        total: None,
-    });
+        // Clean up empty directories
+        let _ = window.emit("sync-progress", SyncProgressPayload {
+            stage: "sync_complete".to_string(),
+            message: "Cleaning up empty directories...".to_string(),
+            current: None,
+            total: None,
+        });
 
-    let dirs_removed = cleanup_empty_directories(&slus_path, &window);
-    let _ = window.emit("sync-progress", SyncProgressPayload {
-        stage: "verifying".to_string(),
-        message: format!("Removed {} empty directories", dirs_removed),
-        current: None,
-        total: None,
-    });
+        let dirs_removed = cleanup_empty_directories(&slus_path, window);
+        let _ = window.emit("sync-progress", SyncProgressPayload {
+            stage: "sync_complete".to_string(),
+            message: format!("Removed {} empty directories", dirs_removed),
+            current: None,
+            total: None,
+        });
 
-    let _ = window.emit("sync-progress", SyncProgressPayload {
-        stage: "complete".to_string(),
-        message: format!("Verification fixes applied! Downloaded: {}, Deleted: {}", downloaded, deleted),
-        current: None,
-        total: None,
-    });
+        result.files_downloaded += downloaded;
+        result.files_deleted += deleted;
+        result.files_renamed += moved;
+        result.files_skipped += skipped.len() as u32;
+        result.skipped.extend(skipped);
+        result.rescued.extend(rescued_paths(&target, &analysis.files_to_rescue));
+        result.conflicts.extend(target_conflicts(&target, &analysis.conflicts));
+        result.held.extend(target_paths(&target, &analysis.held));
+        result.failed.extend(failed);
+    }
 
-    Ok((downloaded, deleted))
+    Ok(result)
 }
 
 /// Run the sync operation (does NOT run verification - call run_verification_scan separately)
+/// Syncs the SLUS folder and every serial folder with its own pack, then refreshes mirror folders
+/// `allow_mass_changes` confirms changes above the mass-change limit
 #[tauri::command]
 pub async fn run_sync(
     textures_dir: String,
     last_sync_commit: Option<String>,
     github_token: Option<String>,
     full_sync: bool,
+    allow_mass_changes: Option<bool>,
     window: Window,
 ) -> Result<SyncResult, String> {
-    let result = if full_sync || last_sync_commit.is_none() {
-        run_full_sync(&textures_dir, &github_token, &window).await?
-    } else {
-        // Try incremental sync, fall back to full sync if it fails (e.g., commit not found or too many changes)
-        match run_incremental_sync(&textures_dir, last_sync_commit.as_ref().unwrap(), &github_token, &window).await {
-            Ok(r) => r,
-            Err(e) if e.contains("404") || e.contains("Not Found") => {
-                let _ = window.emit("sync-progress", SyncProgressPayload {
-                    stage: "fetching".to_string(),
-                    message: "Previous sync commit not found, running full sync...".to_string(),
-                    current: None,
-                    total: None,
-                });
-                run_full_sync(&textures_dir, &github_token, &window).await?
-            }
-            Err(e) if e.contains("TRUNCATED") => {
-                let _ = window.emit("sync-progress", SyncProgressPayload {
-                    stage: "fetching".to_string(),
-                    message: "Too many changes since last sync (300+), running full sync...".to_string(),
-                    current: None,
-                    total: None,
-                });
-                run_full_sync(&textures_dir, &github_token, &window).await?
-            }
-            Err(e) => return Err(e),
-        }
-    };
-
-    // Clean up empty directories
     let textures_path = PathBuf::from(&textures_dir);
-    let slus_path = textures_path.join(SLUS_FOLDER);
-
-    let _ = window.emit("sync-progress", SyncProgressPayload {
-        stage: "sync_complete".to_string(),
-        message: "Cleaning up empty directories...".to_string(),
-        current: None,
-        total: None,
-    });
+    let mut undo = SyncUndo::start(window.app_handle(), &textures_path, "sync")?;
+    let mut guard = MassChangeGuard::load(window.app_handle(), "sync", allow_mass_changes.unwrap_or(false)).await?;
+    // Every folder syncs to the same commit even if the ref moves meanwhile
+    let commit_sha = resolve_sync_commit(window.app_handle(), &github_token).await?;
+
+    let synced = sync_folders(
+        &textures_dir,
+        &last_sync_commit,
+        &commit_sha,
+        full_sync,
+        &github_token,
+        &mut undo,
+        &mut guard,
+        &window,
+    )
+    .await;
+    // Folders changed before a failure can still be undone
+    undo.finish(synced.as_ref().ok().map(|result| result.new_commit_sha.as_str()))?;
+    let result = synced?;
+    guard.record_completed(
+        Some(&result.new_commit_sha),
+        result.files_downloaded,
+        result.files_deleted,
+        result.files_renamed,
+    );
+    record_failed_sync(window.app_handle(), &result.new_commit_sha, &result.failed)?;
 
-    let dirs_removed = cleanup_empty_directories(&slus_path, &window);
-    let _ = window.emit("sync-progress", SyncProgressPayload {
-        stage: "sync_complete".to_string(),
-        message: format!("Removed {} empty directories", dirs_removed),
-        current: None,
-        total: None,
-    });
+    refresh_mirror_folders(window.app_handle(), &textures_path, |message| {
+        let _ = window.emit("sync-progress", SyncProgressPayload {
+            stage: "sync_complete".to_string(),
+            message,
+            current: None,
+            total: None,
+        });
+    })?;
 
     // Sync portion complete - verification will be triggered separately by frontend
     let _ = window.emit("sync-progress", SyncProgressPayload {
@@ -1401,14 +2005,17 @@ pub async fn run_sync(
 }
 
 /// Check sync status without making changes
+/// The latest commit is the one the sync ref points to
 #[tauri::command]
 pub async fn check_sync_status(
+    app: AppHandle,
     _textures_dir: String,
     last_sync_commit: Option<String>,
     github_token: Option<String>,
 ) -> Result<SyncStatusResult, String> {
     // Get latest commit details
-    let (latest_sha, latest_date) = get_commit_details_with_token("main", &github_token).await?;
+    let sync_ref = load_sync_ref(&app).await?;
+    let (latest_sha, latest_date) = get_commit_details_with_token(&sync_ref, &github_token).await?;
 
     let has_changes = match &last_sync_commit {
         Some(last) if last == &latest_sha => false,
@@ -1420,6 +2027,7 @@ pub async fn check_sync_status(
         latest_commit_date: latest_date,
         last_sync_commit,
         has_changes,
+        sync_ref,
     })
 }
 
@@ -1429,6 +2037,8 @@ pub struct SyncStatusResult {
     pub latest_commit_date: String,
     pub last_sync_commit: Option<String>,
     pub has_changes: bool,
+    /// Branch, tag or commit SHA syncs install
+    pub sync_ref: String,
 }
 
 /// Quick count check - compares file counts without computing SHA hashes
@@ -1447,8 +2057,15 @@ pub async fn run_quick_count_check(
         total: None,
     });
 
-    // Count local files (fast, no SHA)
-    let local_count = count_local_files(&textures_path)?;
+    // Count local files (fast, no SHA) across the SLUS folder and serial folders with their own pack
+    let modules = load_module_filter(window.app_handle()).await?;
+    let no_modules = ModuleFilter::default();
+    let targets = load_pack_targets(window.app_handle())?;
+    let mut local_count = 0;
+    for target in &targets {
+        let target_modules = if target.is_primary() { &modules } else { &no_modules };
+        local_count += count_local_files(&textures_path.join(&target.folder), target_modules)?;
+    }
 
     let _ = window.emit("sync-progress", SyncProgressPayload {
         stage: "counting".to_string(),
@@ -1458,8 +2075,16 @@ pub async fn run_quick_count_check(
     });
 
     // Fetch remote tree and count (excluding user-customs)
-    let (remote_files, _) = fetch_github_tree(&github_token).await?;
-    let remote_count = remote_files.keys().filter(|p| !should_skip_path(p)).count();
+    let commit_sha = resolve_sync_commit(window.app_handle(), &github_token).await?;
+    let mut remote_count = 0;
+    for target in &targets {
+        let target_modules = if target.is_primary() { &modules } else { &no_modules };
+        let remote_files = fetch_github_tree_at(&target.sparse_path, &commit_sha, &github_token).await?;
+        remote_count += remote_files
+            .keys()
+            .filter(|p| !should_skip_path(p) && !target_modules.excludes(p))
+            .count();
+    }
 
     let counts_match = local_count == remote_count;
 
@@ -1477,15 +2102,17 @@ pub async fn run_quick_count_check(
     })
 }
 
-/// Analyze what a full sync would do (without actually performing it)
-#[tauri::command]
-pub async fn analyze_full_sync(
-    textures_dir: String,
-    github_token: Option<String>,
-    window: Window,
-) -> Result<SyncAnalysis, String> {
-    let textures_path = PathBuf::from(&textures_dir);
-    let slus_path = textures_path.join(SLUS_FOLDER);
+/// Analyze what a full sync to `commit_sha` would do for one target folder
+/// Returns the analysis, its plan entries and the repository files left out because their paths aren't safe
+async fn analyze_target(
+    textures_path: &Path,
+    target: &PackTarget,
+    commit_sha: &str,
+    modules: &ModuleFilter,
+    github_token: &Option<String>,
+    window: &Window,
+) -> Result<(TargetAnalysis, PlanTarget, Vec<SkippedFile>), String> {
+    let slus_path = textures_path.join(&target.folder);
 
     let _ = window.emit("sync-progress", SyncProgressPayload {
         stage: "fetching".to_string(),
@@ -1494,8 +2121,11 @@ pub async fn analyze_full_sync(
         total: None,
     });
 
-    // Fetch GitHub tree
-    let (remote_files, commit_sha) = fetch_github_tree(&github_token).await?;
+    // Fetch GitHub tree (only selected modules)
+    let (mut remote_files, refused) = fetch_github_tree_checked(&target.sparse_path, commit_sha, github_token).await?;
+    remote_files.retain(|path, _| !modules.excludes(path));
+    let skipped: Vec<SkippedFile> =
+        refused.into_iter().map(|e| SkippedFile::unsafe_path(&target.sparse_path, e)).collect();
     let remote_count = remote_files.keys().filter(|p| !should_skip_path(p)).count();
 
     let _ = window.emit("sync-progress", SyncProgressPayload {
@@ -1513,7 +2143,13 @@ pub async fn analyze_full_sync(
         total: None,
     });
 
-    let local_files = build_local_file_map(&textures_path)?;
+    // A newly configured serial folder starts out empty
+    let local_files = if !target.is_primary() && !slus_path.exists() {
+        HashMap::new()
+    } else {
+        build_local_file_map(&slus_path, modules)?
+    };
+    let conflicts = DisabledConflicts::find(load_conflict_policy(window.app_handle())?, &local_files, &remote_files);
 
     let _ = window.emit("sync-progress", SyncProgressPayload {
         stage: "scanning".to_string(),
@@ -1547,12 +2183,12 @@ pub async fn analyze_full_sync(
             });
         }
 
-        if should_skip_path(path) {
+        if should_skip_path(path) || conflicts.is_unresolved(path) {
             continue;
         }
 
-        // Check normal path
-        if local_files.contains_key(path) {
+        // Check normal path (unless the policy keeps the disabled copy of a conflict)
+        if local_files.contains_key(path) && !conflicts.keeps_disabled(path) {
             let local_path = slus_path.join(path);
             if let Ok(local_sha) = compute_git_blob_sha_with_normalization(&local_path, Some(remote_sha)) {
                 if &local_sha == remote_sha {
@@ -1560,7 +2196,7 @@ pub async fn analyze_full_sync(
                 }
             }
             // File exists but different - will be REPLACED
-            files_to_replace.push(SyncFile { path: path.clone(), to_disabled: false });
+            files_to_replace.push(SyncFile { path: path.clone(), to_disabled: false, sha: Some(remote_sha.clone()) });
             continue;
         }
 
@@ -1574,19 +2210,20 @@ pub async fn analyze_full_sync(
                 }
             }
             // Disabled file exists but different - will be REPLACED
-            files_to_replace.push(SyncFile { path: path.clone(), to_disabled: true });
+            files_to_replace.push(SyncFile { path: path.clone(), to_disabled: true, sha: Some(remote_sha.clone()) });
             continue;
         }
 
         // File doesn't exist locally - will be ADDED
-        files_to_add.push(SyncFile { path: path.clone(), to_disabled: false });
+        files_to_add.push(SyncFile { path: path.clone(), to_disabled: false, sha: Some(remote_sha.clone()) });
     }
 
     // Determine files to delete
     let mut files_to_delete: Vec<String> = Vec::new();
 
     for local_path in local_files.keys() {
-        if should_skip_path(local_path) {
+        // Both copies of a conflict are handled by the conflict policy
+        if should_skip_path(local_path) || conflicts.involves(local_path) {
             continue;
         }
 
@@ -1596,13 +2233,7 @@ pub async fn analyze_full_sync(
 
         if is_disabled_filename(get_filename(local_path)) {
             if let Some(enabled_path) = get_enabled_path(local_path) {
-                // If enabled version exists LOCALLY, delete the disabled version
-                // (having both doesn't make sense - enabled takes precedence)
-                if local_files.contains_key(&enabled_path) {
-                    files_to_delete.push(local_path.clone());
-                    continue;
-                }
-                // If enabled version exists in remote (but not locally), keep disabled version
+                // If enabled version exists in remote, keep disabled version
                 if remote_files.contains_key(&enabled_path) {
                     continue;
                 }
@@ -1611,57 +2242,390 @@ pub async fn analyze_full_sync(
 
         files_to_delete.push(local_path.clone());
     }
+    files_to_delete.extend(conflicts.losing_copies());
+
+    // Moved files are moved locally instead of deleted and downloaded again
+    let files_to_move = detect_moves(&local_files, &remote_files, &mut files_to_add, &mut files_to_delete);
+
+    // Files the app never installed are moved to user-customs instead of being lost
+    let known = load_known_blobs(window.app_handle(), target, &remote_files, commit_sha, github_token).await;
+    let files_to_rescue = plan_rescues(&slus_path, &known, &files_to_replace, &mut files_to_delete);
+
+    let mut analysis = TargetAnalysis {
+        serial: target.folder.clone(),
+        files_to_add,
+        files_to_replace,
+        files_to_delete,
+        files_to_move,
+        files_to_rescue,
+        conflicts: conflicts.paths(),
+        held: Vec::new(),
+    };
+    // Held files are left alone
+    analysis.held = HoldList::load(window.app_handle(), &target.folder)?.take_held(&mut analysis);
+    let plan_target = build_plan_target(
+        &target.folder,
+        &target.sparse_path,
+        &analysis,
+        &local_files,
+        &remote_files,
+    );
+
+    Ok((analysis, plan_target, skipped))
+}
+
+/// Analyze what a full sync would do (without actually performing it)
+/// Covers the SLUS folder and every serial folder with its own pack
+#[tauri::command]
+pub async fn analyze_full_sync(
+    textures_dir: String,
+    github_token: Option<String>,
+    window: Window,
+) -> Result<SyncAnalysis, String> {
+    let textures_path = PathBuf::from(&textures_dir);
+    let modules = load_module_filter(window.app_handle()).await?;
+    let no_modules = ModuleFilter::default();
+
+    let commit_sha = resolve_sync_commit(window.app_handle(), &github_token).await?;
+    let mut primary: Option<(TargetAnalysis, PlanTarget)> = None;
+    let mut serial_targets = Vec::new();
+    let mut serial_plans = Vec::new();
+    let mut skipped = Vec::new();
+
+    for target in load_pack_targets(window.app_handle())? {
+        if target.is_primary() {
+            let (analysis, plan_target, target_skipped) =
+                analyze_target(&textures_path, &target, &commit_sha, &modules, &github_token, &window).await?;
+            primary = Some((analysis, plan_target));
+            skipped.extend(target_skipped);
+        } else {
+            let _ = window.emit("sync-progress", SyncProgressPayload {
+                stage: "fetching".to_string(),
+                message: format!("Analyzing serial folder {}...", target.folder),
+                current: None,
+                total: None,
+            });
+            let (analysis, plan_target, target_skipped) =
+                analyze_target(&textures_path, &target, &commit_sha, &no_modules, &github_token, &window).await?;
+            serial_targets.push(analysis);
+            serial_plans.push(plan_target);
+            skipped.extend(target_skipped);
+        }
+    }
+
+    let (analysis, primary_plan) = primary.ok_or("No folders to analyze")?;
+    let plan = SyncPlan::new(
+        commit_sha.clone(),
+        std::iter::once(primary_plan).chain(serial_plans).collect(),
+    );
+    let count = |f: fn(&TargetAnalysis) -> usize| {
+        f(&analysis) + serial_targets.iter().map(f).sum::<usize>()
+    };
 
     let _ = window.emit("sync-progress", SyncProgressPayload {
         stage: "analysis_complete".to_string(),
         message: format!(
-            "Analysis complete: {} new, {} to replace, {} to delete",
-            files_to_add.len(), files_to_replace.len(), files_to_delete.len()
+            "Analysis complete: {} new, {} to move, {} to replace, {} to delete",
+            count(|t| t.files_to_add.len()),
+            count(|t| t.files_to_move.len()),
+            count(|t| t.files_to_replace.len()),
+            count(|t| t.files_to_delete.len())
         ),
         current: None,
         total: None,
     });
 
     Ok(SyncAnalysis {
-        files_to_add,
-        files_to_replace,
-        files_to_delete,
+        files_to_add: analysis.files_to_add,
+        files_to_replace: analysis.files_to_replace,
+        files_to_delete: analysis.files_to_delete,
+        files_to_move: analysis.files_to_move,
+        files_to_rescue: analysis.files_to_rescue,
+        conflicts: analysis.conflicts,
+        held: analysis.held,
+        skipped,
         commit_sha,
+        serial_targets,
+        plan,
     })
 }
 
-/// Execute sync with pre-analyzed file lists (skips analysis phase)
+/// Preview entries for the planned changes of one target folder
+/// `remote_sizes` are the sizes of the repository files, for the files that are downloaded
+fn preview_incremental_target(
+    slus_path: &Path,
+    analysis: &TargetAnalysis,
+    remote_sizes: &HashMap<String, u64>,
+    preview: &mut IncrementalAnalysis,
+) {
+    let local_size = |local_path: &str| fs::metadata(slus_path.join(local_path)).ok().map(|m| m.len());
+    let entry = |path: &str, previous_path: Option<&str>, size_bytes: Option<u64>, is_disabled: bool| PreviewFile {
+        serial: analysis.serial.clone(),
+        path: path.to_string(),
+        previous_path: previous_path.map(|p| p.to_string()),
+        size_bytes,
+        is_disabled,
+    };
+
+    for file in &analysis.files_to_add {
+        let size = remote_sizes.get(&file.path).copied();
+        preview.files_to_add.push(entry(&file.path, None, size, file.to_disabled));
+    }
+    for file in &analysis.files_to_replace {
+        let size = remote_sizes.get(&file.path).copied();
+        preview.files_to_replace.push(entry(&file.path, None, size, file.to_disabled));
+    }
+    for file_move in &analysis.files_to_move {
+        preview.files_to_rename.push(entry(
+            &file_move.to,
+            Some(&file_move.from),
+            local_size(&file_move.from),
+            file_move.to_disabled,
+        ));
+    }
+    for path in &analysis.files_to_delete {
+        let is_disabled = is_disabled_filename(get_filename(path));
+        preview.files_to_delete.push(entry(path, None, local_size(path), is_disabled));
+    }
+}
+
+/// Analyze what "Download New Content" would do (without actually performing it)
+/// Covers the SLUS folder and every serial folder with its own pack.
+/// Errors starting with TRUNCATED or FULL_SYNC_REQUIRED mean a Full Sync is needed instead
 #[tauri::command]
-pub async fn execute_analyzed_sync(
+pub async fn analyze_incremental_sync(
     textures_dir: String,
-    files_to_add: Vec<SyncFile>,
-    files_to_replace: Vec<SyncFile>,
-    files_to_delete: Vec<String>,
-    commit_sha: String,
+    last_sync_commit: String,
     github_token: Option<String>,
     window: Window,
-) -> Result<SyncResult, String> {
+) -> Result<IncrementalAnalysis, String> {
     let textures_path = PathBuf::from(&textures_dir);
-    let slus_path = textures_path.join(SLUS_FOLDER);
+    let modules = load_module_filter(window.app_handle()).await?;
+    let no_modules = ModuleFilter::default();
+    let policy = load_conflict_policy(window.app_handle())?;
 
-    // Combine add and replace into single download list
-    let mut files_to_download: Vec<SyncFile> = Vec::new();
-    files_to_download.extend(files_to_add);
-    files_to_download.extend(files_to_replace);
+    let _ = window.emit("sync-progress", SyncProgressPayload {
+        stage: "fetching".to_string(),
+        message: "Fetching changes since last sync...".to_string(),
+        current: None,
+        total: None,
+    });
 
-    let download_count = files_to_download.len() as u32;
-    let delete_count = files_to_delete.len() as u32;
+    let latest_sha = resolve_sync_commit(window.app_handle(), &github_token).await?;
+    // None when the sync ref moved to a commit that isn't ahead: each folder's trees are compared instead
+    let changed_files = if latest_sha == last_sync_commit {
+        Some(Vec::new())
+    } else {
+        match fetch_changed_files(&last_sync_commit, &latest_sha, &github_token).await {
+            Ok((_, true)) => {
+                return Err("TRUNCATED: Too many changed files (300+), run a Full Sync instead".to_string());
+            }
+            Ok((files, false)) => Some(files),
+            Err(e) if e.starts_with("NOT_AHEAD") => None,
+            Err(e) => return Err(e),
+        }
+    };
+
+    let mut preview = IncrementalAnalysis {
+        files_to_add: Vec::new(),
+        files_to_replace: Vec::new(),
+        files_to_rename: Vec::new(),
+        files_to_delete: Vec::new(),
+        skipped: Vec::new(),
+        conflicts: Vec::new(),
+        held: Vec::new(),
+        commit_sha: latest_sha.clone(),
+        plan: SyncPlan::new(latest_sha.clone(), Vec::new()),
+    };
+
+    for target in load_pack_targets(window.app_handle())? {
+        let slus_path = textures_path.join(&target.folder);
+        if !slus_path.exists() {
+            return Err(if target.is_primary() {
+                format!("{} folder not found", target.folder)
+            } else {
+                format!("FULL_SYNC_REQUIRED: {} has not been synced yet", target.folder)
+            });
+        }
+
+        let tree_changes;
+        let target_changes = match &changed_files {
+            Some(files) => files,
+            None => {
+                tree_changes = diff_trees(&target.sparse_path, &last_sync_commit, &latest_sha, &github_token).await?;
+                &tree_changes
+            }
+        };
+
+        let target_modules = if target.is_primary() { &modules } else { &no_modules };
+        let mut plan = plan_incremental_changes(&slus_path, &target, target_changes, target_modules, policy)?;
+        plan.analysis.held = HoldList::load(window.app_handle(), &target.folder)?.take_held(&mut plan.analysis);
+
+        let remote_sizes = if plan.analysis.files_to_add.is_empty() && plan.analysis.files_to_replace.is_empty() {
+            HashMap::new()
+        } else {
+            fetch_github_file_sizes_at(&target.sparse_path, &latest_sha, &github_token).await?
+        };
+        preview_incremental_target(&slus_path, &plan.analysis, &remote_sizes, &mut preview);
+        preview.skipped.extend(plan.skipped);
+        preview.conflicts.extend(target_conflicts(&target, &plan.analysis.conflicts));
+        preview.held.extend(target_paths(&target, &plan.analysis.held));
+        preview.plan.targets.push(build_plan_target(
+            &target.folder,
+            &target.sparse_path,
+            &plan.analysis,
+            &plan.local_shas,
+            &plan.remote_shas,
+        ));
+    }
 
     let _ = window.emit("sync-progress", SyncProgressPayload {
-        stage: "syncing".to_string(),
-        message: format!("Starting sync: {} to download, {} to delete", download_count, delete_count),
+        stage: "analysis_complete".to_string(),
+        message: format!(
+            "Analysis complete: {} new, {} to replace, {} to rename, {} to delete",
+            preview.files_to_add.len(),
+            preview.files_to_replace.len(),
+            preview.files_to_rename.len(),
+            preview.files_to_delete.len()
+        ),
         current: None,
         total: None,
     });
 
-    // Download files
+    Ok(preview)
+}
+
+type TargetChanges = (Vec<SyncFile>, Vec<String>, Vec<SyncMove>, Vec<SyncMove>);
+
+/// Normalize a target's change lists, refusing any path that could leave `slus_path`
+fn validate_target_changes(
+    slus_path: &Path,
+    files_to_download: &[SyncFile],
+    files_to_delete: &[String],
+    files_to_move: &[SyncMove],
+    files_to_rescue: &[SyncMove],
+) -> Result<TargetChanges, PathSecurityError> {
+    let files_to_download = files_to_download
+        .iter()
+        .map(|f| Ok(SyncFile { path: validate_sync_path(&f.path)?, ..f.clone() }))
+        .collect::<Result<Vec<_>, PathSecurityError>>()?;
+    let files_to_delete = files_to_delete
+        .iter()
+        .map(|path| validate_sync_path(path))
+        .collect::<Result<Vec<_>, _>>()?;
+    let files_to_move = files_to_move
+        .iter()
+        .map(|m| Ok(SyncMove { from: validate_sync_path(&m.from)?, to: validate_sync_path(&m.to)?, ..m.clone() }))
+        .collect::<Result<Vec<_>, PathSecurityError>>()?;
+    let files_to_rescue = files_to_rescue
+        .iter()
+        .map(|m| Ok(SyncMove { from: validate_sync_path(&m.from)?, to: validate_rescue_path(&m.to)?, ..m.clone() }))
+        .collect::<Result<Vec<_>, PathSecurityError>>()?;
+
+    check_changes_contained(slus_path, &files_to_download, &files_to_delete, &files_to_move, &files_to_rescue)?;
+    Ok((files_to_download, files_to_delete, files_to_move, files_to_rescue))
+}
+
+/// Check that no local path a target's changes touch leads out of `slus_path` through a link
+fn check_changes_contained(
+    slus_path: &Path,
+    files_to_download: &[SyncFile],
+    files_to_delete: &[String],
+    files_to_move: &[SyncMove],
+    files_to_rescue: &[SyncMove],
+) -> Result<(), PathSecurityError> {
+    let local = |path: &str, to_disabled: bool| if to_disabled { get_disabled_path(path) } else { path.to_string() };
+    let paths = files_to_download
+        .iter()
+        .map(|f| local(&f.path, f.to_disabled))
+        .chain(files_to_delete.iter().cloned())
+        .chain(files_to_move.iter().flat_map(|m| [m.from.clone(), local(&m.to, m.to_disabled)]))
+        .chain(files_to_rescue.iter().flat_map(|m| [m.from.clone(), m.to.clone()]));
+    for path in paths {
+        check_contained(slus_path, &path)?;
+    }
+    Ok(())
+}
+
+/// Check a target's change lists and start its transaction
+/// The lists come from the repository or the frontend, so a single bad path refuses them all
+/// before the folder or its transaction folder is touched
+fn begin_target_transaction(
+    slus_path: &Path,
+    files_to_download: &[SyncFile],
+    files_to_delete: &[String],
+    files_to_move: &[SyncMove],
+    files_to_rescue: &[SyncMove],
+) -> Result<(TargetChanges, SyncTransaction), String> {
+    let changes =
+        validate_target_changes(slus_path, files_to_download, files_to_delete, files_to_move, files_to_rescue)?;
+    Ok((changes, SyncTransaction::begin(slus_path)?))
+}
+
+/// Add a target's rescues, moves and deletes to its transaction
+/// Returns the number of files to delete (those that still exist)
+fn stage_local_changes(
+    transaction: &mut SyncTransaction,
+    slus_path: &Path,
+    files_to_delete: &[String],
+    files_to_move: &[SyncMove],
+    files_to_rescue: &[SyncMove],
+) -> u32 {
+    for rescue in files_to_rescue {
+        transaction.rescue(&rescue.from, &rescue.to);
+    }
+
+    for file_move in files_to_move {
+        let to = if file_move.to_disabled {
+            get_disabled_path(&file_move.to)
+        } else {
+            file_move.to.clone()
+        };
+        transaction.rename(&file_move.from, &to);
+    }
+
+    let mut deleted: u32 = 0;
+    for path in files_to_delete {
+        if slus_path.join(path).exists() {
+            transaction.delete(path);
+            deleted += 1;
+        }
+    }
+    deleted
+}
+
+/// Move, download and delete the analyzed files of one target folder as a transaction
+/// Downloads go to a staging folder and are verified before anything in the folder changes;
+/// the changes are then applied together and rolled back if any of them fails
+/// Replaced and deleted files are kept for `undo`; `files_to_rescue` are moved into user-customs first
+/// Files are downloaded as they are at `commit_sha`
+/// When continuing on errors, failed downloads, writes, moves and deletes are left out and returned
+/// instead of aborting (a failed move is returned as a download of its destination)
+/// Returns (downloaded, deleted, moved, failed)
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn apply_target_changes(
+    slus_path: &Path,
+    sparse_path: &str,
+    commit_sha: &str,
+    files_to_download: &[SyncFile],
+    files_to_delete: &[String],
+    files_to_move: &[SyncMove],
+    files_to_rescue: &[SyncMove],
+    github_token: &Option<String>,
+    undo: &mut SyncUndo,
+    window: &Window,
+) -> Result<(u32, u32, u32, Vec<FailedFile>), String> {
+    let continue_on_errors = load_state(window.app_handle().clone())?.continue_on_errors;
+    let ((files_to_download, files_to_delete, files_to_move, files_to_rescue), mut transaction) =
+        begin_target_transaction(slus_path, files_to_download, files_to_delete, files_to_move, files_to_rescue)?;
+
+    let download_count = files_to_download.len() as u32;
+    let folder = folder_name(slus_path);
+
+    // Download files into the staging folder
     let client = Client::new();
     let mut downloaded: u32 = 0;
+    let mut failed: Vec<FailedFile> = Vec::new();
 
     for (i, file) in files_to_download.iter().enumerate() {
         let _ = window.emit("sync-progress", SyncProgressPayload {
@@ -1671,43 +2635,206 @@ pub async fn execute_analyzed_sync(
             total: Some(download_count),
         });
 
-        let dest_path = if file.to_disabled {
-            slus_path.join(get_disabled_path(&file.path))
+        let local_path = if file.to_disabled {
+            get_disabled_path(&file.path)
         } else {
-            slus_path.join(&file.path)
+            file.path.clone()
         };
 
-        download_file(&client, &file.path, &dest_path, &github_token).await?;
+        let staged_path = transaction.staged_path(&local_path);
+        if let Err(e) = download_file_at(&client, commit_sha, sparse_path, &file.path, &staged_path, github_token).await {
+            if !continue_on_errors {
+                transaction.abort();
+                return Err(e);
+            }
+            let _ = fs::remove_file(&staged_path);
+            failed.push(FailedFile::new(&folder, file, e));
+            continue;
+        }
+        transaction.write(&local_path, file.sha.clone());
         downloaded += 1;
     }
 
-    // Delete files
-    let mut deleted: u32 = 0;
+    let _ = window.emit("sync-progress", SyncProgressPayload {
+        stage: "syncing".to_string(),
+        message: "Verifying downloads...".to_string(),
+        current: None,
+        total: None,
+    });
 
-    for (i, path) in files_to_delete.iter().enumerate() {
-        let _ = window.emit("sync-progress", SyncProgressPayload {
-            stage: "deleting".to_string(),
-            message: format!("Deleting: {}", path),
-            current: Some(i as u32 + 1),
-            total: Some(delete_count),
-        });
+    let sha_of = |path: &Path, expected: &str| compute_git_blob_sha_with_normalization(path, Some(expected));
+    if continue_on_errors {
+        for (local_path, reason) in transaction.drop_unverified(sha_of) {
+            let file = files_to_download.iter().find(|f| {
+                local_path == if f.to_disabled { get_disabled_path(&f.path) } else { f.path.clone() }
+            });
+            if let Some(file) = file {
+                failed.push(FailedFile::new(&folder, file, reason));
+                downloaded -= 1;
+            }
+        }
+    } else if let Err(e) = transaction.verify(sha_of) {
+        transaction.abort();
+        return Err(e);
+    }
 
-        let file_path = slus_path.join(path);
-        if file_path.exists() {
-            fs::remove_file(&file_path)
-                .map_err(|e| format!("Failed to delete {}: {}", path, e))?;
-            deleted += 1;
+    let mut deleted = stage_local_changes(&mut transaction, slus_path, &files_to_delete, &files_to_move, &files_to_rescue);
+
+    let _ = window.emit("sync-progress", SyncProgressPayload {
+        stage: "syncing".to_string(),
+        message: format!(
+            "Applying changes: {} downloaded, {} to move, {} to delete...",
+            downloaded,
+            files_to_move.len(),
+            deleted
+        ),
+        current: None,
+        total: None,
+    });
+
+    // Links may have been swapped in while downloading
+    if let Err(e) = check_changes_contained(slus_path, &files_to_download, &files_to_delete, &files_to_move, &files_to_rescue) {
+        transaction.abort();
+        return Err(e.into());
+    }
+
+    undo.keep_backup(&mut transaction, &folder_name(slus_path));
+    let mut moved = files_to_move.len() as u32;
+    if continue_on_errors {
+        let (changes, skipped) = transaction.commit_skipping_failures()?;
+        undo.record_target(changes)?;
+        for (op, reason) in skipped {
+            match op {
+                TransactionOp::Write { path } => {
+                    let file = files_to_download.iter().find(|f| {
+                        path == if f.to_disabled { get_disabled_path(&f.path) } else { f.path.clone() }
+                    });
+                    if let Some(file) = file {
+                        failed.push(FailedFile::new(&folder, file, reason));
+                        downloaded -= 1;
+                    }
+                }
+                TransactionOp::Move { from, .. } => {
+                    if let Some(file_move) = files_to_move.iter().find(|m| m.from == from) {
+                        let file = SyncFile { path: file_move.to.clone(), to_disabled: file_move.to_disabled, sha: None };
+                        failed.push(FailedFile::new(&folder, &file, reason));
+                        moved -= 1;
+                    }
+                }
+                TransactionOp::Delete { path } => {
+                    failed.push(FailedFile::delete(&folder, &path, reason));
+                    deleted -= 1;
+                }
+                // A failed rescue fails the whole commit
+                TransactionOp::Rescue { .. } => {}
+            }
+        }
+    } else {
+        undo.record_target(transaction.commit()?)?;
+    }
+    let installed = files_to_download
+        .iter()
+        .filter(|f| !failed.iter().any(|failure| failure.path == f.path && failure.to_disabled == f.to_disabled));
+    remember_installed_blobs(window.app_handle(), installed.filter_map(|f| f.sha.clone()));
+
+    Ok((downloaded, deleted, moved, failed))
+}
+
+/// Apply analyzed changes to the SLUS folder and serial folders, then refresh mirror folders
+/// Every folder is checked against the mass-change limit before anything is applied
+pub(crate) async fn apply_analyzed_targets(
+    textures_path: &Path,
+    targets: Vec<(PackTarget, TargetAnalysis)>,
+    commit_sha: String,
+    github_token: &Option<String>,
+    allow_mass_changes: bool,
+    window: &Window,
+) -> Result<SyncResult, String> {
+    let mut guard = MassChangeGuard::load(window.app_handle(), "sync", allow_mass_changes).await?;
+    let mut prepared: Vec<(PackTarget, Vec<SyncFile>, TargetAnalysis)> = Vec::new();
+    for (target, mut analysis) in targets {
+        // Files held after the analysis was made are left alone too
+        let held = HoldList::load(window.app_handle(), &target.folder)?.take_held(&mut analysis);
+        analysis.held.extend(held);
+
+        // Combine add and replace into single download list
+        let mut files_to_download = std::mem::take(&mut analysis.files_to_add);
+        files_to_download.append(&mut analysis.files_to_replace);
+        prepared.push((target, files_to_download, analysis));
+    }
+    let targets = prepared;
+    for (target, files_to_download, analysis) in &targets {
+        guard.check(
+            &textures_path.join(&target.folder),
+            files_to_download,
+            &analysis.files_to_delete,
+            &analysis.files_to_rescue,
+        )?;
+    }
+
+    let mut undo = SyncUndo::start(window.app_handle(), textures_path, "sync")?;
+    let mut downloaded: u32 = 0;
+    let mut deleted: u32 = 0;
+    let mut moved: u32 = 0;
+    let mut rescued = Vec::new();
+    let mut conflicts = Vec::new();
+    let mut held = Vec::new();
+    let mut failed = Vec::new();
+
+    for (target, files_to_download, analysis) in targets {
+        if !target.is_primary() {
+            let _ = window.emit("sync-progress", SyncProgressPayload {
+                stage: "syncing".to_string(),
+                message: format!("Syncing serial folder {}...", target.folder),
+                current: None,
+                total: None,
+            });
         }
+
+        let slus_path = textures_path.join(&target.folder);
+        let (target_downloaded, target_deleted, target_moved, target_failed) = apply_target_changes(
+            &slus_path,
+            &target.sparse_path,
+            &commit_sha,
+            &files_to_download,
+            &analysis.files_to_delete,
+            &analysis.files_to_move,
+            &analysis.files_to_rescue,
+            github_token,
+            &mut undo,
+            window,
+        )
+        .await?;
+
+        // Cleanup empty directories
+        cleanup_empty_directories(&slus_path, window);
+
+        downloaded += target_downloaded;
+        deleted += target_deleted;
+        moved += target_moved;
+        rescued.extend(rescued_paths(&target, &analysis.files_to_rescue));
+        conflicts.extend(target_conflicts(&target, &analysis.conflicts));
+        held.extend(target_paths(&target, &analysis.held));
+        failed.extend(target_failed);
     }
+    undo.finish(Some(&commit_sha))?;
+    guard.record_completed(Some(&commit_sha), downloaded, deleted, moved);
+    record_failed_sync(window.app_handle(), &commit_sha, &failed)?;
 
-    // Cleanup empty directories
-    cleanup_empty_directories(&slus_path, &window);
+    refresh_mirror_folders(window.app_handle(), textures_path, |message| {
+        let _ = window.emit("sync-progress", SyncProgressPayload {
+            stage: "syncing".to_string(),
+            message,
+            current: None,
+            total: None,
+        });
+    })?;
 
     let _ = window.emit("sync-progress", SyncProgressPayload {
         stage: "complete".to_string(),
         message: format!(
-            "Sync complete! Downloaded: {}, Deleted: {}",
-            downloaded, deleted
+            "Sync complete! Downloaded: {}, Deleted: {}, Moved: {}",
+            downloaded, deleted, moved
         ),
         current: None,
         total: None,
@@ -1716,8 +2843,133 @@ pub async fn execute_analyzed_sync(
     Ok(SyncResult {
         files_downloaded: downloaded,
         files_deleted: deleted,
-        files_renamed: 0,
+        files_renamed: moved,
         files_skipped: 0,
         new_commit_sha: commit_sha,
+        skipped: Vec::new(),
+        rescued,
+        conflicts,
+        held,
+        failed,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::TempFolder;
+
+    fn sync_file(path: &str) -> SyncFile {
+        SyncFile { path: path.to_string(), to_disabled: false, sha: None }
+    }
+
+    fn sync_move(from: &str, to: &str) -> SyncMove {
+        SyncMove { from: from.to_string(), to: to.to_string(), to_disabled: false }
+    }
+
+    fn refused(result: Result<TargetChanges, PathSecurityError>) -> PathViolation {
+        result.expect_err("changes should be refused").violation
+    }
+
+    /// A pack folder holding textures/abc.png and textures/old.png
+    fn pack_folder(temp: &TempFolder) -> PathBuf {
+        let root = temp.0.join("SLUS-21770");
+        fs::create_dir_all(root.join("textures")).unwrap();
+        fs::write(root.join("textures/abc.png"), b"abc").unwrap();
+        fs::write(root.join("textures/old.png"), b"old").unwrap();
+        root
+    }
+
+    /// Stage and commit good changes the way `apply_target_changes` does
+    fn apply_changes(
+        root: &Path,
+        downloads: &[SyncFile],
+        deletes: &[String],
+        moves: &[SyncMove],
+        rescues: &[SyncMove],
+    ) -> Result<(), String> {
+        let ((downloads, deletes, moves, rescues), mut transaction) =
+            begin_target_transaction(root, downloads, deletes, moves, rescues)?;
+        for file in &downloads {
+            let staged_path = transaction.staged_path(&file.path);
+            fs::create_dir_all(staged_path.parent().unwrap()).unwrap();
+            fs::write(&staged_path, b"new").unwrap();
+            transaction.write(&file.path, None);
+        }
+        stage_local_changes(&mut transaction, root, &deletes, &moves, &rescues);
+        transaction.commit().map(|_| ())
+    }
+
+    #[test]
+    fn applies_good_changes() {
+        let temp = TempFolder::new("sync-good");
+        let root = pack_folder(&temp);
+
+        apply_changes(
+            &root,
+            &[sync_file("textures/new.png")],
+            &["textures/abc.png".to_string()],
+            &[sync_move("textures/old.png", "textures/renamed.png")],
+            &[],
+        )
+        .unwrap();
+
+        assert_eq!(fs::read(root.join("textures/new.png")).unwrap(), b"new");
+        assert!(!root.join("textures/abc.png").exists());
+        assert!(!root.join("textures/old.png").exists());
+        assert_eq!(fs::read(root.join("textures/renamed.png")).unwrap(), b"old");
+    }
+
+    #[test]
+    fn refuses_parent_traversal_before_changing_anything() {
+        let temp = TempFolder::new("sync-mixed");
+        let root = pack_folder(&temp);
+
+        let downloads = vec![sync_file("textures/new.png")];
+        let deletes = vec!["textures/abc.png".to_string()];
+        let moves = vec![sync_move("textures/old.png", "textures/renamed.png")];
+        let rescues = Vec::new();
+
+        // One bad entry among the good ones in each list
+        let mut cases = Vec::new();
+        let mut bad = (downloads.clone(), deletes.clone(), moves.clone(), rescues.clone());
+        bad.0.push(sync_file("../outside.png"));
+        cases.push(bad);
+        let mut bad = (downloads.clone(), deletes.clone(), moves.clone(), rescues.clone());
+        bad.1.push("textures/../../outside.png".to_string());
+        cases.push(bad);
+        let mut bad = (downloads.clone(), deletes.clone(), moves.clone(), rescues.clone());
+        bad.2.push(sync_move("textures/abc.png", "../abc.png"));
+        cases.push(bad);
+        let mut bad = (downloads.clone(), deletes.clone(), moves.clone(), rescues.clone());
+        bad.3.push(sync_move("../abc.png", "user-customs/rescued/2026-01-01/abc.png"));
+        cases.push(bad);
+
+        for (downloads, deletes, moves, rescues) in &cases {
+            assert_eq!(
+                refused(validate_target_changes(&root, downloads, deletes, moves, rescues)),
+                PathViolation::ParentTraversal
+            );
+            let error = apply_changes(&root, downloads, deletes, moves, rescues).expect_err("changes should be refused");
+            assert!(error.starts_with("UNSAFE_PATH:"), "{}", error);
+
+            // None of the good changes were applied and no transaction was started
+            assert_eq!(fs::read(root.join("textures/abc.png")).unwrap(), b"abc");
+            assert_eq!(fs::read(root.join("textures/old.png")).unwrap(), b"old");
+            assert!(!root.join("textures/new.png").exists());
+            assert!(!root.join("textures/renamed.png").exists());
+            let entries: Vec<_> = fs::read_dir(&temp.0).unwrap().map(|e| e.unwrap().file_name()).collect();
+            assert_eq!(entries, ["SLUS-21770"]);
+        }
+    }
+
+    #[test]
+    fn accepts_contained_changes() {
+        let temp = TempFolder::new("sync-contained");
+        fs::create_dir_all(temp.0.join("textures")).unwrap();
+
+        let (downloads, ..) =
+            validate_target_changes(&temp.0, &[sync_file("textures\\sub\\new.png")], &[], &[], &[]).unwrap();
+        assert_eq!(downloads[0].path, "textures/sub/new.png");
+    }
+}
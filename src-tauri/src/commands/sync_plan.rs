@@ -0,0 +1,385 @@
+use super::path_guard::{check_contained, validate_rescue_path, validate_sync_path, PathSecurityError};
+use super::serials::resolve_target;
+use super::sync::{
+    apply_analyzed_targets, compute_git_blob_sha, find_local_file, get_disabled_path, resolve_sync_commit,
+    SyncFile, SyncMove, SyncResult, TargetAnalysis,
+};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{Manager, Window};
+
+/// Current sync plan format version
+pub const SYNC_PLAN_VERSION: u32 = 1;
+
+/// Stale entries listed in the rejection message
+const MAX_STALE_REPORTED: usize = 10;
+
+/// What a plan entry does
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PlanAction {
+    Add,
+    Replace,
+    Delete,
+    Move,
+    /// Move a user-made or edited file into user-customs/rescued
+    Rescue,
+}
+
+/// A single file change in a sync plan
+/// Paths are relative to the serial folder; SHAs are git blob SHAs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanEntry {
+    pub action: PlanAction,
+    /// Local file the action replaces, deletes or moves (None for add)
+    #[serde(default)]
+    pub local_path: Option<String>,
+    /// Repository file the action writes (None for delete; the user-customs destination for rescue)
+    #[serde(default)]
+    pub remote_path: Option<String>,
+    /// Write the file as its dash-disabled version
+    #[serde(default)]
+    pub to_disabled: bool,
+    /// Expected SHA of `local_path` when the plan was made
+    #[serde(default)]
+    pub old_sha: Option<String>,
+    /// SHA of `remote_path` at the plan's commit
+    #[serde(default)]
+    pub new_sha: Option<String>,
+}
+
+/// Planned changes of one serial folder
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanTarget {
+    pub serial: String,
+    pub sparse_path: String,
+    pub entries: Vec<PlanEntry>,
+    /// Files with both copies that the plan leaves to the conflict policy or the user (reported only)
+    #[serde(default)]
+    pub conflicts: Vec<String>,
+    /// Held files the plan leaves alone (reported only)
+    #[serde(default)]
+    pub held: Vec<String>,
+}
+
+/// Versioned, self-contained description of a full sync
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncPlan {
+    pub version: u32,
+    /// Commit the plan was made against
+    pub commit_sha: String,
+    pub created_at: String,
+    pub targets: Vec<PlanTarget>,
+}
+
+impl SyncPlan {
+    pub(crate) fn new(commit_sha: String, targets: Vec<PlanTarget>) -> Self {
+        SyncPlan {
+            version: SYNC_PLAN_VERSION,
+            commit_sha,
+            created_at: Utc::now().to_rfc3339(),
+            targets,
+        }
+    }
+}
+
+/// Record an analysis with the local and remote SHAs it was based on
+pub(crate) fn build_plan_target(
+    serial: &str,
+    sparse_path: &str,
+    analysis: &TargetAnalysis,
+    local_files: &HashMap<String, String>,
+    remote_files: &HashMap<String, String>,
+) -> PlanTarget {
+    let local_path_of = |file: &SyncFile| {
+        if file.to_disabled {
+            get_disabled_path(&file.path)
+        } else {
+            file.path.clone()
+        }
+    };
+
+    let mut entries = Vec::new();
+    for file in &analysis.files_to_add {
+        entries.push(PlanEntry {
+            action: PlanAction::Add,
+            local_path: None,
+            remote_path: Some(file.path.clone()),
+            to_disabled: file.to_disabled,
+            old_sha: None,
+            new_sha: remote_files.get(&file.path).cloned(),
+        });
+    }
+    for file in &analysis.files_to_replace {
+        let local_path = local_path_of(file);
+        entries.push(PlanEntry {
+            action: PlanAction::Replace,
+            old_sha: local_files.get(&local_path).cloned(),
+            local_path: Some(local_path),
+            remote_path: Some(file.path.clone()),
+            to_disabled: file.to_disabled,
+            new_sha: remote_files.get(&file.path).cloned(),
+        });
+    }
+    for path in &analysis.files_to_delete {
+        entries.push(PlanEntry {
+            action: PlanAction::Delete,
+            local_path: Some(path.clone()),
+            remote_path: None,
+            to_disabled: false,
+            old_sha: local_files.get(path).cloned(),
+            new_sha: None,
+        });
+    }
+    for file_move in &analysis.files_to_move {
+        entries.push(PlanEntry {
+            action: PlanAction::Move,
+            local_path: Some(file_move.from.clone()),
+            remote_path: Some(file_move.to.clone()),
+            to_disabled: file_move.to_disabled,
+            old_sha: local_files.get(&file_move.from).cloned(),
+            new_sha: remote_files.get(&file_move.to).cloned(),
+        });
+    }
+    for rescue in &analysis.files_to_rescue {
+        entries.push(PlanEntry {
+            action: PlanAction::Rescue,
+            local_path: Some(rescue.from.clone()),
+            remote_path: Some(rescue.to.clone()),
+            to_disabled: false,
+            old_sha: local_files.get(&rescue.from).cloned(),
+            new_sha: None,
+        });
+    }
+
+    PlanTarget {
+        serial: serial.to_string(),
+        sparse_path: sparse_path.to_string(),
+        entries,
+        conflicts: analysis.conflicts.clone(),
+        held: analysis.held.clone(),
+    }
+}
+
+/// Whether a plan SHA is a full hex object id
+fn is_object_sha(sha: &str) -> bool {
+    sha.len() == 40 && sha.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// Check an imported or passed plan's version and SHAs
+fn check_plan(plan: &SyncPlan) -> Result<(), String> {
+    if plan.version != SYNC_PLAN_VERSION {
+        return Err(format!(
+            "Unsupported sync plan version {} (expected {})",
+            plan.version, SYNC_PLAN_VERSION
+        ));
+    }
+    if !is_object_sha(&plan.commit_sha) {
+        return Err("Invalid sync plan: commit_sha is not a commit SHA".to_string());
+    }
+
+    let entries = plan.targets.iter().flat_map(|target| &target.entries);
+    for entry in entries {
+        let shas = [("old_sha", &entry.old_sha), ("new_sha", &entry.new_sha)];
+        if let Some((field, _)) = shas.iter().find(|(_, sha)| sha.as_deref().is_some_and(|sha| !is_object_sha(sha))) {
+            return Err(format!("Invalid sync plan: {:?} entry with an invalid {}", entry.action, field));
+        }
+    }
+    Ok(())
+}
+
+/// Normalize an entry's paths, refusing any that could leave `slus_path`
+fn check_entry_paths(slus_path: &Path, entry: &PlanEntry) -> Result<PlanEntry, PathSecurityError> {
+    let checked = |path: &Option<String>, validate: fn(&str) -> Result<String, PathSecurityError>| {
+        path.as_deref()
+            .map(|path| {
+                let normalized = validate(path)?;
+                check_contained(slus_path, &normalized)?;
+                Ok(normalized)
+            })
+            .transpose()
+    };
+    let validate_remote = if entry.action == PlanAction::Rescue { validate_rescue_path } else { validate_sync_path };
+    Ok(PlanEntry {
+        local_path: checked(&entry.local_path, validate_sync_path)?,
+        remote_path: checked(&entry.remote_path, validate_remote)?,
+        ..entry.clone()
+    })
+}
+
+/// Turn a plan target back into the lists the executor works with
+/// Every path is checked first, so nothing outside `slus_path` is read; returns the analysis and
+/// the entries with normalized paths, for the staleness check
+/// Entries that change a local file need its SHA, or staleness couldn't be checked
+fn plan_target_to_analysis(slus_path: &Path, target: &PlanTarget) -> Result<(TargetAnalysis, Vec<PlanEntry>), String> {
+    let entries = target
+        .entries
+        .iter()
+        .map(|entry| check_entry_paths(slus_path, entry))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut analysis = TargetAnalysis {
+        serial: target.serial.clone(),
+        files_to_add: Vec::new(),
+        files_to_replace: Vec::new(),
+        files_to_delete: Vec::new(),
+        files_to_move: Vec::new(),
+        files_to_rescue: Vec::new(),
+        conflicts: target.conflicts.clone(),
+        held: target.held.clone(),
+    };
+
+    for entry in &entries {
+        let missing = |field: &str| format!("Invalid sync plan: {:?} entry without {}", entry.action, field);
+        if entry.action != PlanAction::Add && entry.old_sha.is_none() {
+            return Err(missing("old_sha"));
+        }
+        let remote_path = entry.remote_path.clone().ok_or_else(|| missing("remote_path"));
+        let local_path = entry.local_path.clone().ok_or_else(|| missing("local_path"));
+
+        match entry.action {
+            PlanAction::Add => analysis.files_to_add.push(SyncFile {
+                path: remote_path?,
+                to_disabled: entry.to_disabled,
+                sha: entry.new_sha.clone(),
+            }),
+            PlanAction::Replace => analysis.files_to_replace.push(SyncFile {
+                path: remote_path?,
+                to_disabled: entry.to_disabled,
+                sha: entry.new_sha.clone(),
+            }),
+            PlanAction::Delete => analysis.files_to_delete.push(local_path?),
+            PlanAction::Move => analysis.files_to_move.push(SyncMove {
+                from: local_path?,
+                to: remote_path?,
+                to_disabled: entry.to_disabled,
+            }),
+            PlanAction::Rescue => analysis.files_to_rescue.push(SyncMove {
+                from: local_path?,
+                to: remote_path?,
+                to_disabled: false,
+            }),
+        }
+    }
+
+    Ok((analysis, entries))
+}
+
+/// Describe why an entry no longer applies to the local files, if it doesn't
+fn stale_reason(slus_path: &Path, entry: &PlanEntry) -> Option<String> {
+    if entry.action == PlanAction::Add {
+        let remote_path = entry.remote_path.as_deref()?;
+        return find_local_file(slus_path, remote_path)
+            .0
+            .then(|| format!("{} now exists locally", remote_path));
+    }
+
+    let local_path = entry.local_path.as_deref()?;
+    let path = slus_path.join(local_path);
+    if !path.is_file() {
+        return Some(format!("{} no longer exists", local_path));
+    }
+
+    let Some(expected) = entry.old_sha.as_deref() else {
+        return Some(format!("{} has no recorded SHA", local_path));
+    };
+    match compute_git_blob_sha(&path) {
+        Ok(sha) if sha == expected => None,
+        Ok(_) => Some(format!("{} changed since the plan was made", local_path)),
+        Err(e) => Some(format!("{}: {}", local_path, e)),
+    }
+}
+
+/// Save a sync plan as JSON so it can be reviewed or executed later
+#[tauri::command]
+pub fn export_sync_plan(plan: SyncPlan, path: String) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(&plan)
+        .map_err(|e| format!("Failed to serialize sync plan: {}", e))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write sync plan: {}", e))
+}
+
+/// Load a sync plan exported earlier
+#[tauri::command]
+pub fn import_sync_plan(path: String) -> Result<SyncPlan, String> {
+    let content = fs::read_to_string(&path).map_err(|e| format!("Failed to read sync plan: {}", e))?;
+    let plan: SyncPlan = serde_json::from_str(&content)
+        .map_err(|e| format!("Not a valid sync plan: {}", e))?;
+    check_plan(&plan)?;
+    Ok(plan)
+}
+
+/// Execute a sync plan after checking that it still matches the repository and local files
+/// Stale plans are rejected; analyze again to get a fresh one
+/// `allow_mass_changes` confirms changes above the mass-change limit
+#[tauri::command]
+pub async fn execute_sync_plan(
+    textures_dir: String,
+    plan: SyncPlan,
+    github_token: Option<String>,
+    allow_mass_changes: Option<bool>,
+    window: Window,
+) -> Result<SyncResult, String> {
+    check_plan(&plan)?;
+
+    let latest_sha = resolve_sync_commit(window.app_handle(), &github_token).await?;
+    if latest_sha != plan.commit_sha {
+        return Err(format!(
+            "Sync plan is out of date: it was made for commit {} but the repository is now at {}. Analyze again.",
+            short_sha(&plan.commit_sha),
+            short_sha(&latest_sha)
+        ));
+    }
+
+    let textures_path = PathBuf::from(&textures_dir);
+    let mut targets = Vec::new();
+    let mut stale = Vec::new();
+
+    for plan_target in &plan.targets {
+        let target = resolve_target(window.app_handle(), Some(plan_target.serial.clone()))?;
+        if target.sparse_path != plan_target.sparse_path {
+            return Err(format!(
+                "Sync plan is out of date: {} now syncs from {} instead of {}",
+                target.folder, target.sparse_path, plan_target.sparse_path
+            ));
+        }
+
+        let slus_path = textures_path.join(&target.folder);
+        let (analysis, entries) = plan_target_to_analysis(&slus_path, plan_target)?;
+        stale.extend(
+            entries
+                .iter()
+                .filter_map(|entry| stale_reason(&slus_path, entry))
+                .map(|reason| format!("{}/{}", target.folder, reason)),
+        );
+
+        targets.push((target, analysis));
+    }
+
+    if !stale.is_empty() {
+        let mut message = format!(
+            "Sync plan is out of date: {} local files changed since it was made. Analyze again.",
+            stale.len()
+        );
+        for reason in stale.iter().take(MAX_STALE_REPORTED) {
+            message.push_str(&format!("\n- {}", reason));
+        }
+        return Err(message);
+    }
+
+    apply_analyzed_targets(
+        &textures_path,
+        targets,
+        plan.commit_sha,
+        &github_token,
+        allow_mass_changes.unwrap_or(false),
+        &window,
+    )
+    .await
+}
+
+fn short_sha(sha: &str) -> String {
+    sha.chars().take(7).collect()
+}
@@ -0,0 +1,151 @@
+use super::holds::add_hold;
+use super::path_guard::validate_sync_path;
+use super::rescue::remember_installed_blobs;
+use super::serials::{refresh_mirror_folders, resolve_target};
+use super::state::{load_state, save_state};
+use super::sync::{
+    build_request, compute_git_blob_sha_with_normalization, download_file_at, fetch_blob_sha_at, find_local_file,
+    get_disabled_path, get_enabled_path, load_sync_ref, CommitResponse,
+};
+use super::transaction::SyncTransaction;
+use super::undo::SyncUndo;
+use crate::config::{REPO_NAME, REPO_OWNER};
+use reqwest::Client;
+use serde::Serialize;
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager, Window};
+
+/// Commits listed in a texture's history
+const MAX_HISTORY_COMMITS: usize = 100;
+
+/// A commit that changed a texture
+#[derive(Debug, Clone, Serialize)]
+pub struct TextureVersion {
+    pub commit_sha: String,
+    pub date: String,
+    /// First line of the commit message
+    pub message: String,
+    pub author: String,
+}
+
+/// A texture restored to an earlier version
+#[derive(Debug, Clone, Serialize)]
+pub struct RestoredTexture {
+    /// Local path written (the disabled version if the texture is disabled)
+    pub path: String,
+    pub sha: String,
+    pub held: bool,
+}
+
+/// Repo path of a texture given its repo or local (possibly disabled) path
+fn repo_path_of(path: &str) -> String {
+    let path = path.trim().trim_matches('/');
+    get_enabled_path(path).unwrap_or_else(|| path.to_string())
+}
+
+/// Commits up to the sync ref that changed a texture, newest first
+/// `path` is relative to the serial folder; `serial` defaults to the SLUS folder
+#[tauri::command]
+pub async fn get_texture_history(
+    app: AppHandle,
+    path: String,
+    serial: Option<String>,
+    github_token: Option<String>,
+) -> Result<Vec<TextureVersion>, String> {
+    let target = resolve_target(&app, serial)?;
+    let repo_path = repo_path_of(&path);
+
+    let client = Client::new();
+    let url = format!("https://api.github.com/repos/{}/{}/commits", REPO_OWNER, REPO_NAME);
+    let response = build_request(&client, &url, &github_token)
+        .query(&[
+            ("sha", load_sync_ref(&app).await?),
+            ("path", format!("{}/{}", target.sparse_path, repo_path)),
+            ("per_page", MAX_HISTORY_COMMITS.to_string()),
+        ])
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch texture history: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!(
+            "GitHub API error: {} - {}",
+            response.status(),
+            response.text().await.unwrap_or_default()
+        ));
+    }
+
+    let commits: Vec<CommitResponse> = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse texture history: {}", e))?;
+
+    Ok(commits
+        .into_iter()
+        .map(|c| TextureVersion {
+            commit_sha: c.sha,
+            date: c.commit.committer.date,
+            message: c.commit.message.lines().next().unwrap_or_default().to_string(),
+            author: c.commit.committer.name,
+        })
+        .collect())
+}
+
+/// Put a texture back to its version at an earlier commit, keeping it disabled if it is
+/// The previous file is kept for undo; `hold` also holds the texture so syncs keep this version
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn restore_texture_version(
+    textures_dir: String,
+    path: String,
+    commit_sha: String,
+    serial: Option<String>,
+    hold: Option<bool>,
+    github_token: Option<String>,
+    window: Window,
+) -> Result<RestoredTexture, String> {
+    let app = window.app_handle();
+    let textures_path = PathBuf::from(&textures_dir);
+    let target = resolve_target(app, serial)?;
+    let slus_path = textures_path.join(&target.folder);
+    let repo_path = validate_sync_path(&repo_path_of(&path))?;
+
+    let sha = fetch_blob_sha_at(&target.sparse_path, &repo_path, &commit_sha, &github_token)
+        .await?
+        .ok_or_else(|| format!("{} does not exist at commit {}", repo_path, commit_sha))?;
+
+    let (_, is_disabled, _) = find_local_file(&slus_path, &repo_path);
+    let local_path = if is_disabled { get_disabled_path(&repo_path) } else { repo_path.clone() };
+
+    let mut undo = SyncUndo::start(app, &textures_path, "restore")?;
+    let mut transaction = SyncTransaction::begin(&slus_path)?;
+    let staged_path = transaction.staged_path(&local_path);
+    if let Err(e) =
+        download_file_at(&Client::new(), &commit_sha, &target.sparse_path, &repo_path, &staged_path, &github_token).await
+    {
+        transaction.abort();
+        return Err(e);
+    }
+    transaction.write(&local_path, Some(sha.clone()));
+    if let Err(e) = transaction.verify(|path, expected| compute_git_blob_sha_with_normalization(path, Some(expected))) {
+        transaction.abort();
+        return Err(e);
+    }
+    undo.keep_backup(&mut transaction, &target.folder);
+    undo.record_target(transaction.commit()?)?;
+    undo.finish(None)?;
+    remember_installed_blobs(app, [sha.clone()]);
+
+    let held = hold.unwrap_or(false);
+    if held {
+        let mut state = load_state(app.clone())?;
+        add_hold(&mut state, &repo_path, Some(target.folder.clone()), Some(commit_sha))?;
+        save_state(app.clone(), state)?;
+    }
+
+    if target.is_primary() {
+        refresh_mirror_folders(app, &textures_path, |_| {})?;
+    }
+
+    Ok(RestoredTexture { path: local_path, sha, held })
+}
@@ -0,0 +1,457 @@
+use super::serials::load_pack_targets;
+use super::state::load_state;
+use super::undo::{UndoMove, UndoTarget, UndoWrite};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+/// Suffix of the transaction folder next to a serial folder (same drive, so renames are atomic)
+pub(crate) const TRANSACTION_SUFFIX: &str = ".sync-txn";
+const JOURNAL_FILE: &str = "journal.json";
+const STAGED_DIR: &str = "staged";
+const BACKUP_DIR: &str = "backup";
+
+/// Phase of a sync transaction, as recorded in the journal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum TransactionState {
+    /// Downloading into the staging folder; the serial folder is untouched
+    Staging,
+    /// Applying changes to the serial folder; must be rolled back if interrupted
+    Committing,
+    /// All changes applied; only the transaction folder is left to remove
+    Committed,
+}
+
+/// A change applied in the commit phase (paths are local paths relative to the serial folder)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub(crate) enum TransactionOp {
+    /// Move a local file to a new path
+    Move { from: String, to: String },
+    /// Move a file the user made into user-customs/rescued (never skipped, or a write would replace it)
+    Rescue { from: String, to: String },
+    /// Put a staged file in place; the file it replaces goes to the backup folder
+    Write { path: String },
+    /// Move a local file to the backup folder
+    Delete { path: String },
+}
+
+/// Rollback log of a sync transaction
+#[derive(Debug, Serialize, Deserialize)]
+struct Journal {
+    state: TransactionState,
+    ops: Vec<TransactionOp>,
+    /// Where the backup folder goes once the changes are applied
+    #[serde(default)]
+    trash: Option<PathBuf>,
+}
+
+/// Changes to one serial folder that are downloaded first and applied together
+/// If the commit phase fails (or the app dies during it), every change is rolled back
+pub(crate) struct SyncTransaction {
+    slus_path: PathBuf,
+    dir: PathBuf,
+    /// Rescues, then moves
+    moves: Vec<TransactionOp>,
+    writes: Vec<(String, Option<String>)>,
+    deletes: Vec<TransactionOp>,
+    /// Where to keep the replaced and deleted files after committing (discarded if None)
+    trash: Option<PathBuf>,
+}
+
+/// Transaction folder of a serial folder
+fn transaction_dir(slus_path: &Path) -> PathBuf {
+    let name = slus_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    slus_path.with_file_name(format!("{}{}", name, TRANSACTION_SUFFIX))
+}
+
+/// Rename a file, creating the destination's parent folders
+fn rename_file(from: &Path, to: &Path) -> Result<(), String> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
+    }
+    fs::rename(from, to).map_err(|e| format!("Failed to move {}: {}", from.display(), e))
+}
+
+fn write_journal(dir: &Path, journal: &Journal) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(journal)
+        .map_err(|e| format!("Failed to serialize sync journal: {}", e))?;
+    let tmp = dir.join(format!("{}.tmp", JOURNAL_FILE));
+    fs::write(&tmp, content).map_err(|e| format!("Failed to write sync journal: {}", e))?;
+    fs::rename(&tmp, dir.join(JOURNAL_FILE)).map_err(|e| format!("Failed to write sync journal: {}", e))
+}
+
+fn read_journal(dir: &Path) -> Option<Journal> {
+    let content = fs::read_to_string(dir.join(JOURNAL_FILE)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Move every file of a backup folder into `trash`, keeping files already there
+fn move_backup(backup: &Path, trash: &Path) -> Result<(), String> {
+    if !trash.exists() {
+        return rename_file(backup, trash);
+    }
+    let entries = fs::read_dir(backup).map_err(|e| format!("Failed to read {}: {}", backup.display(), e))?;
+    for entry in entries {
+        let entry = entry.map_err(|e| format!("Failed to read {}: {}", backup.display(), e))?;
+        let (from, to) = (entry.path(), trash.join(entry.file_name()));
+        if entry.file_type().map(|t| t.is_dir()).unwrap_or(false) {
+            move_backup(&from, &to)?;
+        } else {
+            rename_file(&from, &to)?;
+        }
+    }
+    let _ = fs::remove_dir(backup);
+    Ok(())
+}
+
+/// Undo the applied ops of an interrupted or failed commit phase, newest first
+/// Backed up files are looked for in the backup folder, then in `trash` (it may already have been moved)
+/// Every step checks the file system, so it is safe to run more than once
+fn rollback(slus_path: &Path, dir: &Path, trash: Option<&Path>, ops: &[TransactionOp]) -> Result<(), String> {
+    let staged = dir.join(STAGED_DIR);
+    let backup = dir.join(BACKUP_DIR);
+    let backup_of = |path: &str| {
+        let file = backup.join(path);
+        match trash {
+            Some(trash) if !file.exists() => trash.join(path),
+            _ => file,
+        }
+    };
+
+    for op in ops.iter().rev() {
+        match op {
+            TransactionOp::Move { from, to } | TransactionOp::Rescue { from, to } => {
+                let (from, to) = (slus_path.join(from), slus_path.join(to));
+                if to.exists() && !from.exists() {
+                    rename_file(&to, &from)?;
+                }
+            }
+            TransactionOp::Write { path } => {
+                let (local, staged_file, backup_file) = (slus_path.join(path), staged.join(path), backup_of(path));
+                if backup_file.exists() {
+                    rename_file(&backup_file, &local)?;
+                } else if !staged_file.exists() && local.exists() {
+                    // A new file that was already put in place
+                    rename_file(&local, &staged_file)?;
+                }
+            }
+            TransactionOp::Delete { path } => {
+                let backup_file = backup_of(path);
+                if backup_file.exists() {
+                    rename_file(&backup_file, &slus_path.join(path))?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+impl SyncTransaction {
+    /// Start a transaction, recovering any earlier one left behind for this folder
+    pub(crate) fn begin(slus_path: &Path) -> Result<Self, String> {
+        recover_folder(slus_path)?;
+
+        let dir = transaction_dir(slus_path);
+        fs::create_dir_all(dir.join(STAGED_DIR))
+            .map_err(|e| format!("Failed to create staging folder: {}", e))?;
+        write_journal(&dir, &Journal { state: TransactionState::Staging, ops: Vec::new(), trash: None })?;
+
+        Ok(SyncTransaction {
+            slus_path: slus_path.to_path_buf(),
+            dir,
+            moves: Vec::new(),
+            writes: Vec::new(),
+            deletes: Vec::new(),
+            trash: None,
+        })
+    }
+
+    /// Where to download a file that will be written to `local_path`
+    pub(crate) fn staged_path(&self, local_path: &str) -> PathBuf {
+        self.dir.join(STAGED_DIR).join(local_path)
+    }
+
+    /// Write a downloaded file to `local_path`, checking it against the expected blob SHA if known
+    pub(crate) fn write(&mut self, local_path: &str, expected_sha: Option<String>) {
+        self.writes.push((local_path.to_string(), expected_sha));
+    }
+
+    pub(crate) fn rename(&mut self, from: &str, to: &str) {
+        self.moves.push(TransactionOp::Move { from: from.to_string(), to: to.to_string() });
+    }
+
+    pub(crate) fn rescue(&mut self, from: &str, to: &str) {
+        self.moves.push(TransactionOp::Rescue { from: from.to_string(), to: to.to_string() });
+    }
+
+    pub(crate) fn delete(&mut self, local_path: &str) {
+        self.deletes.push(TransactionOp::Delete { path: local_path.to_string() });
+    }
+
+    /// Keep the files the commit replaces or deletes in `trash` so the changes can be undone
+    pub(crate) fn keep_backup(&mut self, trash: PathBuf) {
+        self.trash = Some(trash);
+    }
+
+    /// Check that every staged download is complete and has the expected content
+    pub(crate) fn verify(&self, sha_of: impl Fn(&Path, &str) -> Result<String, String>) -> Result<(), String> {
+        for (path, expected_sha) in &self.writes {
+            let staged = self.staged_path(path);
+            if !staged.is_file() {
+                return Err(format!("Staged download missing: {}", path));
+            }
+            if let Some(expected) = expected_sha {
+                if &sha_of(&staged, expected)? != expected {
+                    return Err(format!(
+                        "Downloaded {} does not match the analyzed version (the repository may have changed); nothing was applied",
+                        path
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Leave out staged downloads that are missing or don't have the expected content
+    /// Returns the local path of each with the reason, so the rest can still be committed
+    pub(crate) fn drop_unverified(
+        &mut self,
+        sha_of: impl Fn(&Path, &str) -> Result<String, String>,
+    ) -> Vec<(String, String)> {
+        let mut dropped = Vec::new();
+        let staged_dir = self.dir.join(STAGED_DIR);
+        self.writes.retain(|(path, expected_sha)| {
+            let staged = staged_dir.join(path);
+            let reason = if !staged.is_file() {
+                Some("Staged download missing".to_string())
+            } else {
+                match expected_sha.as_deref().map(|expected| (expected, sha_of(&staged, expected))) {
+                    Some((_, Err(e))) => Some(e),
+                    Some((expected, Ok(sha))) if sha != expected => {
+                        Some("Download does not match the analyzed version".to_string())
+                    }
+                    _ => None,
+                }
+            };
+            match reason {
+                Some(reason) => {
+                    let _ = fs::remove_file(&staged);
+                    dropped.push((path.clone(), reason));
+                    false
+                }
+                None => true,
+            }
+        });
+        dropped
+    }
+
+    /// Apply all changes: deletes, then moves, then writes
+    /// On failure everything applied so far is rolled back and the folder is left as before
+    /// Returns what was changed, for the undo record
+    pub(crate) fn commit(self) -> Result<UndoTarget, String> {
+        self.commit_with(false).map(|(changes, _)| changes)
+    }
+
+    /// Apply all changes like `commit`, but leave out the ones that fail instead of rolling back
+    /// (except rescues); returns what was changed and each change left out with the reason
+    pub(crate) fn commit_skipping_failures(self) -> Result<(UndoTarget, Vec<(TransactionOp, String)>), String> {
+        self.commit_with(true)
+    }
+
+    fn commit_with(self, skip_failures: bool) -> Result<(UndoTarget, Vec<(TransactionOp, String)>), String> {
+        let mut ops = self.deletes.clone();
+        ops.extend(self.moves.iter().cloned());
+        ops.extend(self.writes.iter().map(|(path, _)| TransactionOp::Write { path: path.clone() }));
+
+        let journal = |state| Journal { state, ops: ops.clone(), trash: self.trash.clone() };
+        write_journal(&self.dir, &journal(TransactionState::Committing))?;
+
+        let applied = if skip_failures { self.apply_each(&ops) } else { self.apply(&ops).map(|()| Vec::new()) };
+        let skipped = match applied {
+            Ok(skipped) => skipped,
+            Err(e) => return Err(self.roll_back_all(&ops, e)),
+        };
+
+        let applied_ops: Vec<TransactionOp> =
+            ops.iter().filter(|op| !skipped.iter().any(|(skipped_op, _)| skipped_op == *op)).cloned().collect();
+        let changes = self.applied_changes(&applied_ops);
+
+        // The backup must be safe in the trash before the transaction folder can go
+        let backup = self.dir.join(BACKUP_DIR);
+        if let (Some(trash), true) = (&self.trash, backup.exists()) {
+            if let Err(e) = move_backup(&backup, trash) {
+                return Err(self.roll_back_all(&ops, e));
+            }
+        }
+
+        // Without the Committed mark the next launch would roll the changes back, so drop the journal instead
+        if let Err(e) = write_journal(&self.dir, &journal(TransactionState::Committed)) {
+            if fs::remove_file(self.dir.join(JOURNAL_FILE)).is_err() {
+                return Err(self.roll_back_all(&ops, e));
+            }
+        }
+        let _ = fs::remove_dir_all(&self.dir);
+        Ok((changes, skipped))
+    }
+
+    /// Roll back every op after a failed commit; returns the error to report
+    fn roll_back_all(&self, ops: &[TransactionOp], e: String) -> String {
+        match rollback(&self.slus_path, &self.dir, self.trash.as_deref(), ops) {
+            Ok(()) => {
+                let _ = fs::remove_dir_all(&self.dir);
+                format!("{} (all changes were rolled back)", e)
+            }
+            Err(rollback_error) => format!(
+                "{}; rollback failed: {} (it will be retried on next launch)",
+                e, rollback_error
+            ),
+        }
+    }
+
+    /// Describe committed ops; a write replaced a file if the file is in the backup folder
+    fn applied_changes(&self, ops: &[TransactionOp]) -> UndoTarget {
+        let backup = self.dir.join(BACKUP_DIR);
+        let expected_shas: HashMap<&str, &Option<String>> =
+            self.writes.iter().map(|(path, sha)| (path.as_str(), sha)).collect();
+        let mut changes = UndoTarget {
+            folder: self.slus_path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default(),
+            moves: Vec::new(),
+            written: Vec::new(),
+            deleted: Vec::new(),
+        };
+
+        for op in ops {
+            match op {
+                TransactionOp::Move { from, to } | TransactionOp::Rescue { from, to } => {
+                    changes.moves.push(UndoMove { from: from.clone(), to: to.clone() })
+                }
+                TransactionOp::Write { path } => changes.written.push(UndoWrite {
+                    path: path.clone(),
+                    sha: expected_shas.get(path.as_str()).and_then(|sha| (*sha).clone()),
+                    replaced: backup.join(path).exists(),
+                }),
+                TransactionOp::Delete { path } => {
+                    if backup.join(path).exists() {
+                        changes.deleted.push(path.clone());
+                    }
+                }
+            }
+        }
+        changes
+    }
+
+    fn apply(&self, ops: &[TransactionOp]) -> Result<(), String> {
+        let staged = self.dir.join(STAGED_DIR);
+        let backup = self.dir.join(BACKUP_DIR);
+
+        for op in ops {
+            match op {
+                TransactionOp::Move { from, to } | TransactionOp::Rescue { from, to } => {
+                    rename_file(&self.slus_path.join(from), &self.slus_path.join(to))?;
+                }
+                TransactionOp::Write { path } => {
+                    let local = self.slus_path.join(path);
+                    if local.exists() {
+                        rename_file(&local, &backup.join(path))?;
+                    }
+                    rename_file(&staged.join(path), &local)?;
+                }
+                TransactionOp::Delete { path } => {
+                    let local = self.slus_path.join(path);
+                    if local.exists() {
+                        rename_file(&local, &backup.join(path))?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply ops one at a time, undoing and leaving out each one that fails
+    /// A write to a path whose file couldn't be moved away is left out too; a failed rescue fails the commit
+    fn apply_each(&self, ops: &[TransactionOp]) -> Result<Vec<(TransactionOp, String)>, String> {
+        let mut skipped: Vec<(TransactionOp, String)> = Vec::new();
+        for op in ops {
+            if let TransactionOp::Write { path } = op {
+                let blocked = skipped.iter().any(|(skipped_op, _)| {
+                    matches!(skipped_op, TransactionOp::Move { from, .. } if from == path)
+                });
+                if blocked {
+                    skipped.push((op.clone(), "The file at its path could not be moved away".to_string()));
+                    continue;
+                }
+            }
+
+            let Err(e) = self.apply(std::slice::from_ref(op)) else {
+                continue;
+            };
+            if matches!(op, TransactionOp::Rescue { .. }) {
+                return Err(e);
+            }
+            rollback(&self.slus_path, &self.dir, None, std::slice::from_ref(op))?;
+            skipped.push((op.clone(), e));
+        }
+        Ok(skipped)
+    }
+
+    /// Discard the transaction before anything was applied
+    pub(crate) fn abort(self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// Finish or roll back a transaction left behind for a serial folder
+/// Returns a description of what was done, if there was anything to recover
+pub(crate) fn recover_folder(slus_path: &Path) -> Result<Option<String>, String> {
+    let dir = transaction_dir(slus_path);
+    if !dir.exists() {
+        return Ok(None);
+    }
+
+    let folder = slus_path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let message = match read_journal(&dir) {
+        Some(Journal { state: TransactionState::Committing, ops, trash }) => {
+            rollback(slus_path, &dir, trash.as_deref(), &ops)?;
+            format!("Rolled back an interrupted sync of {}", folder)
+        }
+        Some(Journal { state: TransactionState::Committed, trash, .. }) => {
+            // Keep the replaced and deleted files for undo if they didn't make it to the trash
+            let backup = dir.join(BACKUP_DIR);
+            if let (Some(trash), true) = (trash, backup.exists()) {
+                move_backup(&backup, &trash)?;
+            }
+            format!("Finished cleaning up the last sync of {}", folder)
+        }
+        _ => format!("Discarded the downloads of an interrupted sync of {}", folder),
+    };
+
+    fs::remove_dir_all(&dir).map_err(|e| format!("Failed to remove {}: {}", dir.display(), e))?;
+    Ok(Some(message))
+}
+
+/// Recover interrupted syncs of every serial folder in the saved textures directory
+/// Called on launch; returns a message per recovered folder
+#[tauri::command]
+pub fn recover_interrupted_syncs(app: AppHandle) -> Result<Vec<String>, String> {
+    let Some(textures_path) = load_state(app.clone())?.textures_path else {
+        return Ok(Vec::new());
+    };
+
+    Ok(load_pack_targets(&app)?
+        .iter()
+        .filter_map(|target| {
+            let slus_path = PathBuf::from(&textures_path).join(&target.folder);
+            match recover_folder(&slus_path) {
+                Ok(message) => message,
+                Err(e) => Some(format!("Failed to recover the last sync of {}: {}", target.folder, e)),
+            }
+        })
+        .collect())
+}
@@ -0,0 +1,445 @@
+use super::history::{record_sync_history, SyncHistoryEntry};
+use super::serials::refresh_mirror_folders;
+use super::state::{load_state, save_state};
+use super::sync::{cleanup_empty_directories, compute_git_blob_sha_with_normalization, SyncProgressPayload};
+use super::transaction::SyncTransaction;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter, Manager, Window};
+
+/// Folder in the textures directory holding undo data (same drive, so files are moved, not copied)
+const TRASH_DIR_NAME: &str = ".sync-trash";
+const RECORD_FILE: &str = "record.json";
+const FILES_DIR: &str = "files";
+
+/// Days undo data is kept when the user hasn't chosen
+pub const DEFAULT_UNDO_RETENTION_DAYS: u32 = 14;
+
+/// Problems listed when an undo is refused
+const MAX_UNDO_PROBLEMS_REPORTED: usize = 10;
+
+/// Kind of the record a preview is ended with; kept until then whatever the retention
+pub(crate) const PREVIEW_KIND: &str = "preview";
+
+/// A file written by a sync
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UndoWrite {
+    pub path: String,
+    /// Blob SHA of the written file, if known
+    #[serde(default)]
+    pub sha: Option<String>,
+    /// Whether it replaced a file (kept in the trash) rather than adding one
+    pub replaced: bool,
+}
+
+/// A file moved by a sync
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UndoMove {
+    pub from: String,
+    pub to: String,
+}
+
+/// Changes a sync made to one serial folder (local paths relative to the folder)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UndoTarget {
+    pub folder: String,
+    pub moves: Vec<UndoMove>,
+    pub written: Vec<UndoWrite>,
+    /// Deleted files (kept in the trash)
+    pub deleted: Vec<String>,
+}
+
+impl UndoTarget {
+    fn is_empty(&self) -> bool {
+        self.moves.is_empty() && self.written.is_empty() && self.deleted.is_empty()
+    }
+}
+
+/// Record of one sync, stored next to the files it replaced or deleted
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncUndoRecord {
+    pub id: String,
+    /// What made the changes ("sync", "verification", "preview", ...)
+    pub kind: String,
+    pub created_at: String,
+    /// `last_sync_commit` and timestamp before the sync, restored by undo
+    pub previous_commit: Option<String>,
+    pub previous_timestamp: Option<String>,
+    /// Commit the sync brought the files to (None if it didn't finish)
+    pub new_commit: Option<String>,
+    pub targets: Vec<UndoTarget>,
+}
+
+/// What undoing the last sync would do (or did)
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncUndoSummary {
+    pub id: String,
+    pub kind: String,
+    pub created_at: String,
+    pub previous_commit: Option<String>,
+    pub new_commit: Option<String>,
+    /// Replaced and deleted files put back
+    pub files_restored: u32,
+    /// Added files removed
+    pub files_removed: u32,
+    /// Moved files moved back
+    pub files_moved: u32,
+}
+
+impl SyncUndoRecord {
+    fn summary(&self) -> SyncUndoSummary {
+        let count = |f: &dyn Fn(&UndoTarget) -> usize| self.targets.iter().map(f).sum::<usize>() as u32;
+        SyncUndoSummary {
+            id: self.id.clone(),
+            kind: self.kind.clone(),
+            created_at: self.created_at.clone(),
+            previous_commit: self.previous_commit.clone(),
+            new_commit: self.new_commit.clone(),
+            files_restored: count(&|t| t.written.iter().filter(|w| w.replaced).count() + t.deleted.len()),
+            files_removed: count(&|t| t.written.iter().filter(|w| !w.replaced).count()),
+            files_moved: count(&|t| t.moves.len()),
+        }
+    }
+}
+
+fn write_record(dir: &Path, record: &SyncUndoRecord) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(record)
+        .map_err(|e| format!("Failed to serialize undo record: {}", e))?;
+    fs::write(dir.join(RECORD_FILE), content).map_err(|e| format!("Failed to write undo record: {}", e))
+}
+
+fn read_record(dir: &Path) -> Option<SyncUndoRecord> {
+    let content = fs::read_to_string(dir.join(RECORD_FILE)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Retention chosen by the user (0 keeps no undo data)
+fn retention_days(app: &AppHandle) -> Result<u32, String> {
+    Ok(load_state(app.clone())?.undo_retention_days.unwrap_or(DEFAULT_UNDO_RETENTION_DAYS))
+}
+
+/// Remove undo data older than the retention, and leftovers of syncs that changed nothing
+/// The record of the active preview (`keep_id`) is kept
+fn prune_undo_data(textures_path: &Path, retention_days: u32, keep_id: Option<&str>) {
+    let Ok(entries) = fs::read_dir(textures_path.join(TRASH_DIR_NAME)) else {
+        return;
+    };
+    let cutoff = Utc::now() - Duration::days(retention_days as i64);
+
+    for entry in entries.flatten() {
+        let expired = match read_record(&entry.path()) {
+            Some(record) if keep_id == Some(record.id.as_str()) => false,
+            Some(record) => {
+                record.targets.is_empty()
+                    || DateTime::parse_from_rfc3339(&record.created_at)
+                        .map(|created| created.with_timezone(&Utc) < cutoff)
+                        .unwrap_or(true)
+            }
+            None => true,
+        };
+        if expired {
+            let _ = fs::remove_dir_all(entry.path());
+        }
+    }
+}
+
+/// Most recent sync that can be undone, with its folder (previews are ended instead)
+fn latest_record(textures_path: &Path) -> Option<(PathBuf, SyncUndoRecord)> {
+    let entries = fs::read_dir(textures_path.join(TRASH_DIR_NAME)).ok()?;
+    entries
+        .flatten()
+        .filter_map(|entry| read_record(&entry.path()).map(|record| (entry.path(), record)))
+        .filter(|(_, record)| !record.targets.is_empty() && record.kind != PREVIEW_KIND)
+        .max_by(|(_, a), (_, b)| a.id.cmp(&b.id))
+}
+
+/// Record with the given ID, with its folder
+pub(crate) fn find_record(textures_path: &Path, id: &str) -> Option<(PathBuf, SyncUndoRecord)> {
+    let dir = textures_path.join(TRASH_DIR_NAME).join(id);
+    read_record(&dir).map(|record| (dir, record))
+}
+
+/// Fail while a preview is active, since ending it must find the files as the preview left them
+fn check_no_preview(app: &AppHandle) -> Result<(), String> {
+    match load_state(app.clone())?.preview {
+        Some(preview) => Err(format!(
+            "PREVIEW_ACTIVE: Textures are previewing {}. End the preview first.",
+            preview.preview_ref
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Collects the changes of one sync so it can be undone
+/// The record is saved after every folder, so a sync that fails halfway can still be undone
+pub(crate) struct SyncUndo {
+    /// None when undo data is turned off
+    dir: Option<PathBuf>,
+    record: SyncUndoRecord,
+}
+
+impl SyncUndo {
+    /// Start recording a sync, pruning expired undo data first
+    /// Fails while a preview is active
+    pub(crate) fn start(app: &AppHandle, textures_path: &Path, kind: &str) -> Result<Self, String> {
+        check_no_preview(app)?;
+        let state = load_state(app.clone())?;
+        let retention_days = retention_days(app)?;
+        prune_undo_data(textures_path, retention_days, None);
+
+        let now = Utc::now();
+        let record = SyncUndoRecord {
+            id: now.format("%Y%m%d-%H%M%S%.3f").to_string(),
+            kind: kind.to_string(),
+            created_at: now.to_rfc3339(),
+            previous_commit: state.last_sync_commit,
+            previous_timestamp: state.last_sync_timestamp,
+            new_commit: None,
+            targets: Vec::new(),
+        };
+
+        let dir = if retention_days == 0 && kind != PREVIEW_KIND {
+            None
+        } else {
+            let dir = textures_path.join(TRASH_DIR_NAME).join(&record.id);
+            fs::create_dir_all(&dir).map_err(|e| format!("Failed to create undo folder: {}", e))?;
+            write_record(&dir, &record)?;
+            Some(dir)
+        };
+
+        Ok(SyncUndo { dir, record })
+    }
+
+    /// Let a transaction keep the files it replaces or deletes for this record
+    pub(crate) fn keep_backup(&self, transaction: &mut SyncTransaction, folder: &str) {
+        if let Some(dir) = &self.dir {
+            transaction.keep_backup(dir.join(FILES_DIR).join(folder));
+        }
+    }
+
+    pub(crate) fn id(&self) -> &str {
+        &self.record.id
+    }
+
+    /// Add the committed changes of one folder
+    pub(crate) fn record_target(&mut self, changes: UndoTarget) -> Result<(), String> {
+        if changes.is_empty() {
+            return Ok(());
+        }
+        self.record.targets.push(changes);
+        match &self.dir {
+            Some(dir) => write_record(dir, &self.record),
+            None => Ok(()),
+        }
+    }
+
+    /// Save the commit the sync reached (None if it didn't change the commit)
+    /// A sync that changed nothing leaves no undo data
+    pub(crate) fn finish(mut self, new_commit: Option<&str>) -> Result<(), String> {
+        let Some(dir) = &self.dir else {
+            return Ok(());
+        };
+        if self.record.targets.is_empty() {
+            let _ = fs::remove_dir_all(dir);
+            return Ok(());
+        }
+        self.record.new_commit = new_commit.map(str::to_string).or_else(|| self.record.previous_commit.clone());
+        write_record(dir, &self.record)
+    }
+}
+
+/// Reasons the changes of a folder can't be undone cleanly
+fn undo_problems(slus_path: &Path, trash: &Path, target: &UndoTarget) -> Vec<String> {
+    let mut problems = Vec::new();
+    let folder = &target.folder;
+
+    for write in &target.written {
+        let local = slus_path.join(&write.path);
+        if !local.is_file() {
+            problems.push(format!("{}/{} no longer exists", folder, write.path));
+        } else if let Some(expected) = &write.sha {
+            if compute_git_blob_sha_with_normalization(&local, Some(expected)).ok().as_ref() != Some(expected) {
+                problems.push(format!("{}/{} changed since the sync", folder, write.path));
+            }
+        }
+        if write.replaced && !trash.join(&write.path).is_file() {
+            problems.push(format!("Previous version of {}/{} is missing from the undo data", folder, write.path));
+        }
+    }
+    for path in &target.deleted {
+        if slus_path.join(path).exists() {
+            problems.push(format!("{}/{} exists again", folder, path));
+        }
+        if !trash.join(path).is_file() {
+            problems.push(format!("Deleted file {}/{} is missing from the undo data", folder, path));
+        }
+    }
+    for file_move in &target.moves {
+        if !slus_path.join(&file_move.to).is_file() {
+            problems.push(format!("{}/{} no longer exists", folder, file_move.to));
+        }
+        if slus_path.join(&file_move.from).exists() {
+            problems.push(format!("{}/{} exists again", folder, file_move.from));
+        }
+    }
+    problems
+}
+
+/// Put a file from the undo data where the transaction expects it, keeping the undo data intact
+fn stage_from_trash(source: &Path, staged: &Path) -> Result<(), String> {
+    if let Some(parent) = staged.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
+    }
+    fs::hard_link(source, staged)
+        .or_else(|_| fs::copy(source, staged).map(|_| ()))
+        .map_err(|e| format!("Failed to restore {}: {}", source.display(), e))
+}
+
+/// Reverse the changes of one folder as a single transaction
+fn undo_target(slus_path: &Path, trash: &Path, target: &UndoTarget) -> Result<(), String> {
+    let mut transaction = SyncTransaction::begin(slus_path)?;
+
+    for write in &target.written {
+        if write.replaced {
+            if let Err(e) = stage_from_trash(&trash.join(&write.path), &transaction.staged_path(&write.path)) {
+                transaction.abort();
+                return Err(e);
+            }
+            transaction.write(&write.path, None);
+        } else {
+            transaction.delete(&write.path);
+        }
+    }
+    for path in &target.deleted {
+        if let Err(e) = stage_from_trash(&trash.join(path), &transaction.staged_path(path)) {
+            transaction.abort();
+            return Err(e);
+        }
+        transaction.write(path, None);
+    }
+    for file_move in &target.moves {
+        transaction.rename(&file_move.to, &file_move.from);
+    }
+
+    transaction.commit().map(|_| ())
+}
+
+/// The sync that "undo last sync" would reverse, if undo data is available
+#[tauri::command]
+pub fn get_last_sync_undo(app: AppHandle, textures_dir: String) -> Result<Option<SyncUndoSummary>, String> {
+    let textures_path = PathBuf::from(&textures_dir);
+    let preview_id = load_state(app.clone())?.preview.map(|preview| preview.undo_id);
+    prune_undo_data(&textures_path, retention_days(&app)?, preview_id.as_deref());
+    Ok(latest_record(&textures_path).map(|(_, record)| record.summary()))
+}
+
+/// Restore the files replaced, deleted and moved by the last sync, and its `last_sync_commit`
+/// Refused if any file the sync wrote has been changed since
+#[tauri::command]
+pub fn undo_last_sync(textures_dir: String, window: Window) -> Result<SyncUndoSummary, String> {
+    check_no_preview(window.app_handle())?;
+    let textures_path = PathBuf::from(&textures_dir);
+    let (dir, record) = latest_record(&textures_path).ok_or("There is no sync to undo")?;
+    reverse_record(&textures_path, &dir, record, "last sync", &window)
+}
+
+/// Reverse the changes of a record and restore its `last_sync_commit`, then remove its undo data
+/// `what` names the reversed sync in messages (e.g. "last sync")
+pub(crate) fn reverse_record(
+    textures_path: &Path,
+    dir: &Path,
+    mut record: SyncUndoRecord,
+    what: &str,
+    window: &Window,
+) -> Result<SyncUndoSummary, String> {
+    let app = window.app_handle().clone();
+    let summary = record.summary();
+
+    let _ = window.emit("sync-progress", SyncProgressPayload {
+        stage: "syncing".to_string(),
+        message: format!("Checking files changed by the {}...", what),
+        current: None,
+        total: None,
+    });
+
+    let problems: Vec<String> = record
+        .targets
+        .iter()
+        .flat_map(|target| {
+            undo_problems(
+                &textures_path.join(&target.folder),
+                &dir.join(FILES_DIR).join(&target.folder),
+                target,
+            )
+        })
+        .collect();
+    if !problems.is_empty() {
+        let mut message = format!(
+            "Can't undo the {}: {} files changed or went missing since. Move your changes aside and try again.",
+            what,
+            problems.len()
+        );
+        for problem in problems.iter().take(MAX_UNDO_PROBLEMS_REPORTED) {
+            message.push_str(&format!("\n- {}", problem));
+        }
+        return Err(message);
+    }
+
+    // Undo one folder at a time, saving progress so a failed undo can be retried
+    while let Some(target) = record.targets.last().cloned() {
+        let _ = window.emit("sync-progress", SyncProgressPayload {
+            stage: "syncing".to_string(),
+            message: format!("Restoring {}...", target.folder),
+            current: None,
+            total: None,
+        });
+
+        let slus_path = textures_path.join(&target.folder);
+        undo_target(&slus_path, &dir.join(FILES_DIR).join(&target.folder), &target)?;
+        cleanup_empty_directories(&slus_path, window);
+
+        record.targets.pop();
+        write_record(dir, &record)?;
+    }
+
+    let mut state = load_state(app.clone())?;
+    state.last_sync_commit = record.previous_commit.clone();
+    state.last_sync_timestamp = record.previous_timestamp.clone();
+    save_state(app.clone(), state)?;
+
+    fs::remove_dir_all(dir).map_err(|e| format!("Failed to remove undo data: {}", e))?;
+
+    let mut entry = SyncHistoryEntry::new("undo", "completed");
+    entry.commit_sha = record.previous_commit.clone();
+    entry.files_downloaded = summary.files_restored;
+    entry.files_deleted = summary.files_removed;
+    entry.files_renamed = summary.files_moved;
+    let _ = record_sync_history(&app, entry);
+
+    refresh_mirror_folders(&app, textures_path, |message| {
+        let _ = window.emit("sync-progress", SyncProgressPayload {
+            stage: "syncing".to_string(),
+            message,
+            current: None,
+            total: None,
+        });
+    })?;
+
+    let mut title = what.to_string();
+    if let Some(first) = title.get_mut(..1) {
+        first.make_ascii_uppercase();
+    }
+    let _ = window.emit("sync-progress", SyncProgressPayload {
+        stage: "complete".to_string(),
+        message: format!(
+            "{} undone! Restored: {}, Removed: {}, Moved back: {}",
+            title, summary.files_restored, summary.files_removed, summary.files_moved
+        ),
+        current: None,
+        total: None,
+    });
+
+    Ok(summary)
+}
@@ -4,14 +4,37 @@ mod config;
 use commands::{
     backup_existing_folder, check_existing_folder, check_git_installed, cleanup_processes,
     delete_existing_folder, get_git_error, start_installation, validate_directory,
+    check_native_git_available, get_git_diagnostics, get_git_capabilities,
     // State management
     load_state, save_state, set_textures_path, mark_setup_complete,
     update_last_sync_commit, set_initial_setup_done, set_github_token,
-    set_sync_disclaimer_acknowledged,
+    set_sync_disclaimer_acknowledged, set_temp_path, set_selected_modules,
+    set_undo_retention_days, set_mass_change_limit,
+    // Content modules
+    get_selected_modules, apply_module_selection,
+    // Serial folders
+    set_serial_folders,
+    // Cleanup
+    scan_stale_files, reclaim_stale_files,
     // Sync
-    get_latest_commit, run_sync, check_sync_status,
+    get_latest_commit, set_sync_ref, set_release_channel, run_sync, check_sync_status,
+    start_preview, end_preview,
     run_verification_scan, apply_verification_fixes, run_quick_count_check,
-    analyze_full_sync, execute_analyzed_sync,
+    analyze_full_sync, analyze_incremental_sync,
+    // Sync plans
+    export_sync_plan, import_sync_plan, execute_sync_plan,
+    // Sync recovery and undo
+    recover_interrupted_syncs, get_last_sync_undo, undo_last_sync,
+    // Failed downloads
+    set_continue_on_errors, retry_failed_files,
+    // Sync history
+    get_sync_history,
+    // Enabled/disabled conflicts
+    set_conflict_policy, resolve_disabled_conflicts,
+    // Held paths
+    get_held_paths, hold_paths, release_held_paths, get_held_upstream_changes,
+    // Texture history
+    get_texture_history, restore_texture_version,
     // App info
     get_app_version, fetch_installer_data, compare_versions,
 };
@@ -29,6 +52,9 @@ pub fn run() {
             validate_directory,
             check_git_installed,
             get_git_error,
+            check_native_git_available,
+            get_git_diagnostics,
+            get_git_capabilities,
             start_installation,
             // State management
             load_state,
@@ -39,15 +65,55 @@ pub fn run() {
             set_initial_setup_done,
             set_github_token,
             set_sync_disclaimer_acknowledged,
+            set_temp_path,
+            set_selected_modules,
+            set_undo_retention_days,
+            set_mass_change_limit,
+            // Content modules
+            get_selected_modules,
+            apply_module_selection,
+            // Serial folders
+            set_serial_folders,
+            // Cleanup
+            scan_stale_files,
+            reclaim_stale_files,
             // Sync
             get_latest_commit,
+            set_sync_ref,
+            set_release_channel,
             run_sync,
             check_sync_status,
+            start_preview,
+            end_preview,
             run_verification_scan,
             apply_verification_fixes,
             run_quick_count_check,
             analyze_full_sync,
-            execute_analyzed_sync,
+            analyze_incremental_sync,
+            // Sync plans
+            export_sync_plan,
+            import_sync_plan,
+            execute_sync_plan,
+            // Sync recovery and undo
+            recover_interrupted_syncs,
+            get_last_sync_undo,
+            undo_last_sync,
+            // Failed downloads
+            set_continue_on_errors,
+            retry_failed_files,
+            // Sync history
+            get_sync_history,
+            // Enabled/disabled conflicts
+            set_conflict_policy,
+            resolve_disabled_conflicts,
+            // Held paths
+            get_held_paths,
+            hold_paths,
+            release_held_paths,
+            get_held_upstream_changes,
+            // Texture history
+            get_texture_history,
+            restore_texture_version,
             // App info
             get_app_version,
             fetch_installer_data,